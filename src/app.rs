@@ -1,17 +1,54 @@
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+    MouseEventKind,
+};
+use polars::prelude::DataFrame;
 use ratatui::DefaultTerminal;
+use ratatui::layout::Position;
+use std::cell::RefCell;
+use std::collections::HashMap;
 use std::io;
+use std::ops::RangeInclusive;
+use std::time::Duration;
 
+use crate::clipboard;
+use crate::components::data_table::MIN_COLUMN_WIDTH;
+use crate::components::{RowGroupProgressBar, SchemaTreeComponent};
+use crate::file::browser::FileBrowser;
+use crate::file::chart::{ChartData, ChartKind, ChartSpec};
+use crate::file::column_layout::{ColumnChooser, ColumnLayout};
+use crate::file::compression::CompressionSimulation;
+use crate::file::filter::FilterExpr;
+use crate::file::group_by::GroupBy;
+use crate::file::job::Job;
 use crate::file::parquet_ctx::ParquetCtx;
-use crate::file::sample_data::ParquetSampleData;
-use crate::file::sql::{SqlResult, run_sql};
+use crate::file::profile::ColumnProfile;
+use crate::file::pruning::PruningReport;
+use crate::file::query_library::{LibraryScope, ParameterPrompt, QueryLibrary, SavedQuery};
+use crate::file::record::RecordTree;
+use crate::file::sample_data::{FindMatches, ParquetSampleData, SortSpec};
+use crate::file::schema::SchemaInfo;
+use crate::file::sql::{DEFAULT_ROW_LIMIT, QueryPlan, SqlJob, SqlResult, SqlSession, parse_attach};
+use crate::file::sql_editor::{SqlEditor, SqlHistory};
+use crate::file::summary::ColumnSummary;
+use crate::file::utils::{commas, pretty_value};
+use crate::file::watch::{FileWatch, WATCH_INTERVAL_MS};
+use crate::keymap::{Action, KeyInput, KeyMap, KeySequence};
+use crate::palette::{Command, Palette, PaletteItem};
 use crate::tabs::TabManager;
+use crate::theme;
+use crate::ui::{CHART_COLUMNS_WIDTH, HitAreas};
 
 pub struct AppRenderView<'a> {
     pub title: &'a str,
     pub parquet_ctx: &'a ParquetCtx,
     file_name: &'a str,
+    /// Paths of the open files in the order they were opened, and which one is shown
+    files: Vec<&'a str>,
+    active_file: usize,
     tabs: &'a TabManager,
+    keymap: &'a KeyMap,
+    hits: &'a RefCell<HitAreas>,
     pub state: &'a AppState,
 }
 
@@ -19,9 +56,13 @@ impl<'a> AppRenderView<'a> {
     fn from_app(app: &'a App) -> Self {
         Self {
             title: "parqeye",
-            parquet_ctx: app.parquet_ctx,
+            parquet_ctx: &app.parquet_ctx,
             file_name: &app.file_name,
+            files: app.file_paths(),
+            active_file: app.active_file,
             tabs: &app.tabs,
+            keymap: &app.keymap,
+            hits: &app.hits,
             state: &app.state,
         }
     }
@@ -34,37 +75,254 @@ impl<'a> AppRenderView<'a> {
         self.file_name
     }
 
+    pub fn files(&self) -> &[&str] {
+        &self.files
+    }
+
+    pub fn active_file(&self) -> usize {
+        self.active_file
+    }
+
+    pub fn keymap(&self) -> &KeyMap {
+        self.keymap
+    }
+
+    /// Filled in while rendering, to resolve mouse clicks.
+    pub fn hits(&self) -> &RefCell<HitAreas> {
+        self.hits
+    }
+
     pub fn state(&self) -> &AppState {
         self.state
     }
 }
 
-pub struct App<'a> {
-    pub parquet_ctx: &'a ParquetCtx,
+pub struct App {
+    pub parquet_ctx: ParquetCtx,
     pub file_name: String,
+    /// The other open files, in the order they were opened with the shown one left out
+    other_files: Vec<OpenFile>,
+    /// Position of the shown file among all open files
+    active_file: usize,
+    /// Tables given with `--table`, attached to the SQL session of every file opened
+    attached_tables: Vec<(String, String)>,
+    /// Whether files are read again when they change on disk (`--watch`)
+    watch: bool,
+    file_watch: Option<FileWatch>,
     pub exit: bool,
     pub tabs: TabManager,
     pub state: AppState,
+    /// SQL context kept across queries, with the opened file and attached tables
+    pub sql_session: SqlSession,
+    /// Keys bound to actions, from the config file
+    pub keymap: KeyMap,
+    /// Keys typed so far of a multi-key binding such as `g g`
+    pending_keys: KeySequence,
+    /// Parts of the screen under the mouse, as drawn by the last frame
+    hits: RefCell<HitAreas>,
+    column_drag: Option<ColumnDrag>,
+}
+
+/// An open file and everything shown for it, kept aside while another file is shown so
+/// that its tab, cursor, filters and queries survive switching.
+struct OpenFile {
+    parquet_ctx: ParquetCtx,
+    tabs: TabManager,
+    state: AppState,
+    sql_session: SqlSession,
+    file_watch: Option<FileWatch>,
+}
+
+impl OpenFile {
+    fn new(parquet_ctx: ParquetCtx) -> Self {
+        let tabs = TabManager::new(
+            parquet_ctx.schema.column_size(),
+            parquet_ctx.row_groups.num_row_groups(),
+            parquet_ctx.sample_data.total_rows,
+        );
+
+        let mut state = AppState::new();
+        state.storage_level_len = parquet_ctx.storage.root().children.len();
+        state.column_layout = ColumnLayout::load(
+            &parquet_ctx.file_path,
+            &parquet_ctx.sample_data.flattened_columns,
+        );
+
+        let sql_session = SqlSession::for_file(&parquet_ctx.file_path).unwrap_or_else(|e| {
+            state.sql_result = Some(SqlResult::Err(e));
+            SqlSession::new()
+        });
+
+        Self {
+            parquet_ctx,
+            tabs,
+            state,
+            sql_session,
+            file_watch: None,
+        }
+    }
+}
+
+/// A column being resized by dragging the gap after its header.
+#[derive(Debug, Clone, Copy)]
+struct ColumnDrag {
+    column: usize,
+    x: u16,
+    width: u16,
+}
+
+/// What "y" copies, picked by the key after it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Yank {
+    Cells,
+    RowTsv,
+    RowJson,
+    ColumnName,
+}
+
+/// Lines moved per notch of the mouse wheel.
+const WHEEL_LINES: usize = 3;
+
+/// A filter and sort read over the whole file in the background.
+pub struct ViewJob {
+    query: Option<String>,
+    sort: Option<SortSpec>,
+    after: AfterView,
+    job: Job<(ParquetSampleData, Option<usize>)>,
+}
+
+impl ViewJob {
+    pub fn elapsed(&self) -> Duration {
+        self.job.elapsed()
+    }
+}
+
+/// Where the Visualize cursor goes once a filter or sort is shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AfterView {
+    FirstCell,
+    FirstRow,
+    /// Stays within the rows. The rows shown meanwhile are unfiltered and unsorted, so
+    /// when the read fails or is cancelled the filter and sort are dropped.
+    Refresh,
 }
 
+/// Chars a column is widened or narrowed by with > / <.
+const COLUMN_WIDTH_STEP: i32 = 2;
+
 pub struct AppState {
     horizontal_offset: usize,
     vertical_offset: usize,
     tree_scroll_offset: usize,
     data_vertical_scroll: usize,
+    data_horizontal_scroll: usize,
     visible_data_rows: usize,
     // Search: "/" to enter search mode, Enter to filter, Esc to cancel or clear filter
     pub search_mode: bool,
     pub search_query: String,
     pub search_filter: Option<String>,
     pub filtered_sample_data: Option<ParquetSampleData>,
-    // SQL tab
-    pub sql_query: String,
+    pub search_match_count: Option<usize>,
+    // Find: "f" to type a query, n / N jump between matching cells of the current view
+    pub find_mode: bool,
+    pub find_query: String,
+    pub find: Option<FindMatches>,
+    // Sort: "s" on the current column cycles ascending / descending / unsorted
+    pub sort: Option<SortSpec>,
+    pub sorted_sample_data: Option<ParquetSampleData>,
+    // Filter and sort being read over the whole file, Esc cancels the read
+    pub view_job: Option<ViewJob>,
+    // One-off message shown in the footer until the next key press
+    pub notice: Option<String>,
+    // SQL tab: keys go to the query editor until Esc switches to navigating the results
+    pub sql_editor: SqlEditor,
+    pub sql_history: SqlHistory,
+    pub sql_editing: bool,
     pub sql_result: Option<SqlResult>,
-    // Row detail overlay: when Some(row_idx), show full row data for that row
+    // Query running in the background, Esc cancels it
+    pub sql_job: Option<SqlJob>,
+    // Query behind sql_result, whether it has rows beyond those fetched ("m" fetches more)
+    // and how long the last run took
+    pub sql_result_query: String,
+    pub sql_has_more: bool,
+    pub sql_elapsed: Option<Duration>,
+    // Fetched result rows as a DataFrame and their per-column summary ("s" on the results)
+    pub sql_frame: Option<DataFrame>,
+    pub sql_summary_shown: bool,
+    pub sql_summary: Option<Result<Vec<ColumnSummary>, String>>,
+    // Chart of the result ("c" on the results): X and Y columns picked from a list, and
+    // the plotted points, recomputed when the spec or the number of points that fit changes
+    pub sql_chart: Option<ChartSpec>,
+    pub sql_chart_cursor: usize,
+    pub sql_chart_data: Option<Result<ChartData, String>>,
+    pub sql_chart_points: usize,
+    // Explain pane for the query in the editor (Alt+E while editing, "e" on the results)
+    pub sql_plan: Option<Result<QueryPlan, String>>,
+    pub sql_plan_scroll: usize,
+    // Saved queries: Ctrl+S names the query in the editor, Ctrl+O / "o" opens the library
+    // and a query with :parameters asks for their values before it runs
+    pub save_query_mode: bool,
+    pub save_query_name: String,
+    pub save_query_scope: LibraryScope,
+    pub query_library: Option<QueryLibrary>,
+    pub query_library_selected: usize,
+    pub query_parameters: Option<ParameterPrompt>,
+    // Value counts of the current Visualize column over the whole file ("g")
+    pub group_by: Option<Result<GroupBy, String>>,
+    pub group_by_job: Option<Job<GroupBy>>,
+    pub group_by_selected: usize,
+    // Row detail overlay: when Some(row_idx), show full row data for that row as a tree
+    // of its nested values with a cursor node, or as raw JSON ("r")
     pub row_detail_row: Option<usize>,
+    pub row_detail: Option<Result<RecordTree, String>>,
+    pub detail_cursor: usize,
+    pub detail_raw: bool,
     pub detail_scroll_offset: usize,     // vertical (lines)
     pub detail_scroll_horizontal: usize, // horizontal (columns)
+    // Find within the record ("/" in the row detail), n / N jump between matching nodes
+    pub detail_find_mode: bool,
+    pub detail_query: String,
+    // Compression what-if simulation for the selected schema column ("c" in the Schema tab)
+    pub compression_simulation: Option<Result<CompressionSimulation, String>>,
+    pub compression_job: Option<Job<CompressionSimulation>>,
+    pub compression_scroll: usize,
+    // Column profile shown next to the schema table ("p" in the Schema tab)
+    pub column_profile: Option<Result<ColumnProfile, String>>,
+    pub profile_job: Option<Job<ColumnProfile>>,
+    // Storage tab: drill-down path of group nodes and the size of the current level
+    pub storage_path: Vec<usize>,
+    pub storage_level_len: usize,
+    // Pushdown preview: "w" in the Row Groups tab types a filter, Enter shows what it would skip
+    pub pruning_mode: bool,
+    pub pruning_query: String,
+    pub pruning: Option<PruningReport>,
+    // Column chooser ("c" in the Visualize tab): order, visibility and pinning of the
+    // Visualize columns, saved per file
+    pub column_layout: ColumnLayout,
+    pub column_chooser: Option<ColumnChooser>,
+    // Expanded cell ("x" on a table): column name and the value pretty-printed, wrapped
+    // in a popup; "w" wraps every cell of the selected row in the table
+    pub expanded_cell: Option<(String, String)>,
+    pub expanded_cell_scroll: usize,
+    pub wrap_row: bool,
+    // Clipboard ("y" on a table, then what to copy): Space marks a corner of a rectangle
+    // of cells, (row, column position), copied instead of the cell under the cursor
+    pub yank_mode: bool,
+    pub selection_anchor: Option<(usize, usize)>,
+    // Open files ("b"): the selected line of the list of open files, and the browser
+    // for opening another one
+    pub file_list: Option<usize>,
+    pub file_browser: Option<FileBrowser>,
+    // Help overlay ("?"): the bindings of every action, scrolled with ↑↓
+    pub help_shown: bool,
+    pub help_scroll: usize,
+    // Command palette (":" or Ctrl+P), and the row number prompt of its "Go to row"
+    pub palette: Option<Palette>,
+    pub goto_row_mode: bool,
+    pub goto_row_query: String,
+    // Column widths set with < / > or by dragging header separators, by column index
+    pub visualize_column_widths: HashMap<usize, u16>,
+    pub sql_column_widths: HashMap<usize, u16>,
 }
 
 impl Default for AppState {
@@ -80,16 +338,80 @@ impl AppState {
             vertical_offset: 0,
             tree_scroll_offset: 0,
             data_vertical_scroll: 0,
+            data_horizontal_scroll: 0,
             visible_data_rows: 20, // Default fallback
             search_mode: false,
             search_query: String::new(),
             search_filter: None,
             filtered_sample_data: None,
-            sql_query: String::new(),
+            search_match_count: None,
+            find_mode: false,
+            find_query: String::new(),
+            find: None,
+            sort: None,
+            sorted_sample_data: None,
+            view_job: None,
+            notice: None,
+            sql_editor: SqlEditor::new(),
+            sql_history: SqlHistory::default(),
+            sql_editing: false,
             sql_result: None,
+            sql_job: None,
+            sql_result_query: String::new(),
+            sql_has_more: false,
+            sql_elapsed: None,
+            sql_frame: None,
+            sql_summary_shown: false,
+            sql_summary: None,
+            sql_chart: None,
+            sql_chart_cursor: 0,
+            sql_chart_data: None,
+            sql_chart_points: 0,
+            sql_plan: None,
+            sql_plan_scroll: 0,
+            save_query_mode: false,
+            save_query_name: String::new(),
+            save_query_scope: LibraryScope::User,
+            query_library: None,
+            query_library_selected: 0,
+            query_parameters: None,
+            group_by: None,
+            group_by_job: None,
+            group_by_selected: 0,
             row_detail_row: None,
+            row_detail: None,
+            detail_cursor: 0,
+            detail_raw: false,
             detail_scroll_offset: 0,
             detail_scroll_horizontal: 0,
+            detail_find_mode: false,
+            detail_query: String::new(),
+            compression_simulation: None,
+            compression_job: None,
+            compression_scroll: 0,
+            column_profile: None,
+            profile_job: None,
+            storage_path: Vec::new(),
+            storage_level_len: 0,
+            pruning_mode: false,
+            pruning_query: String::new(),
+            pruning: None,
+            column_layout: ColumnLayout::new(0),
+            column_chooser: None,
+            expanded_cell: None,
+            expanded_cell_scroll: 0,
+            wrap_row: false,
+            yank_mode: false,
+            selection_anchor: None,
+            file_list: None,
+            file_browser: None,
+            help_shown: false,
+            help_scroll: 0,
+            palette: None,
+            goto_row_mode: false,
+            goto_row_query: String::new(),
+            visualize_column_widths: HashMap::new(),
+            sql_column_widths: HashMap::new(),
         }
     }
 
@@ -98,11 +420,63 @@ impl AppState {
         self.vertical_offset = 0;
         self.tree_scroll_offset = 0;
         self.data_vertical_scroll = 0;
+        self.data_horizontal_scroll = 0;
+        self.selection_anchor = None;
+    }
+
+    /// Data shown in the Visualize tab: filtered, else sorted, else the file sample.
+    pub fn visualize_data<'s>(&'s self, sample: &'s ParquetSampleData) -> &'s ParquetSampleData {
+        self.filtered_sample_data
+            .as_ref()
+            .or(self.sorted_sample_data.as_ref())
+            .unwrap_or(sample)
+    }
+
+    pub fn reset_rows(&mut self) {
+        self.vertical_offset = 0;
+        self.data_vertical_scroll = 0;
     }
 
     pub fn clear_search_filter(&mut self) {
         self.search_filter = None;
         self.filtered_sample_data = None;
+        self.search_match_count = None;
+    }
+
+    /// Move the cursor to a cell, scrolling so that it is visible.
+    pub fn select_cell(&mut self, row: usize, column: usize, max_rows: usize) {
+        self.vertical_offset = row;
+        self.horizontal_offset = column;
+        self.adjust_scroll_to_selection(self.visible_data_rows, max_rows);
+    }
+
+    /// Data column under the Visualize cursor, which moves over the shown columns.
+    pub fn current_column(&self) -> Option<usize> {
+        self.column_layout
+            .visible()
+            .0
+            .get(self.horizontal_offset)
+            .copied()
+    }
+
+    /// First column drawn after the pinned ones; it only moves when the cursor leaves
+    /// the columns in view.
+    pub fn data_horizontal_scroll(&self) -> usize {
+        self.data_horizontal_scroll
+    }
+
+    pub fn set_data_horizontal_scroll(&mut self, scroll: usize) {
+        self.data_horizontal_scroll = scroll;
+    }
+
+    /// Marked rectangle between the anchor and the cursor: rows and column positions.
+    pub fn selection(&self) -> Option<(RangeInclusive<usize>, RangeInclusive<usize>)> {
+        let (row, column) = self.selection_anchor?;
+        let (cursor_row, cursor_column) = (self.vertical_offset, self.horizontal_offset);
+        Some((
+            row.min(cursor_row)..=row.max(cursor_row),
+            column.min(cursor_column)..=column.max(cursor_column),
+        ))
     }
 
     pub fn horizontal_offset(&self) -> usize {
@@ -149,6 +523,15 @@ impl AppState {
         self.data_vertical_scroll = scroll;
     }
 
+    /// Whether a query or other background work is running.
+    pub fn job_running(&self) -> bool {
+        self.sql_job.is_some()
+            || self.view_job.is_some()
+            || self.compression_job.is_some()
+            || self.profile_job.is_some()
+            || self.group_by_job.is_some()
+    }
+
     pub fn visible_data_rows(&self) -> usize {
         self.visible_data_rows
     }
@@ -172,6 +555,16 @@ impl AppState {
         self.adjust_scroll_to_selection(visible_rows, max_rows);
     }
 
+    pub fn first_row(&mut self, visible_rows: usize, max_rows: usize) {
+        self.vertical_offset = 0;
+        self.adjust_scroll_to_selection(visible_rows, max_rows);
+    }
+
+    pub fn last_row(&mut self, visible_rows: usize, max_rows: usize) {
+        self.vertical_offset = max_rows.saturating_sub(1);
+        self.adjust_scroll_to_selection(visible_rows, max_rows);
+    }
+
     pub fn adjust_scroll_to_selection(&mut self, visible_rows: usize, max_rows: usize) {
         // Ensure selected row is visible in viewport
         if self.vertical_offset < self.data_vertical_scroll {
@@ -188,22 +581,149 @@ impl AppState {
     }
 }
 
-impl<'a> App<'a> {
-    pub fn new(file_info: &'a ParquetCtx) -> Self {
-        let sample_data_rows = file_info.sample_data.total_rows;
-
-        let tab_manager = TabManager::new(
-            file_info.schema.column_size(),
-            file_info.row_groups.num_row_groups(),
-            sample_data_rows,
-        );
+impl App {
+    pub fn new(parquet_ctx: ParquetCtx) -> Self {
+        let OpenFile {
+            parquet_ctx,
+            tabs,
+            mut state,
+            sql_session,
+            file_watch,
+        } = OpenFile::new(parquet_ctx);
+        // The history is shared by all files and moves along when switching
+        state.sql_history = SqlHistory::load();
 
         Self {
-            parquet_ctx: file_info,
-            file_name: file_info.file_path.clone(),
+            file_name: parquet_ctx.file_path.clone(),
+            parquet_ctx,
+            other_files: Vec::new(),
+            active_file: 0,
+            attached_tables: Vec::new(),
+            watch: false,
+            file_watch,
             exit: false,
-            tabs: tab_manager,
-            state: AppState::new(),
+            tabs,
+            state,
+            sql_session,
+            keymap: KeyMap::default(),
+            pending_keys: Vec::new(),
+            hits: RefCell::new(HitAreas::default()),
+            column_drag: None,
+        }
+    }
+
+    pub fn with_keymap(mut self, keymap: KeyMap) -> Self {
+        self.keymap = keymap;
+        self
+    }
+
+    /// Read files again when they change on disk, including files opened later.
+    pub fn with_watch(mut self, watch: bool) -> Self {
+        self.watch = watch;
+        self.file_watch = watch.then(|| FileWatch::new(&self.parquet_ctx.file_path));
+        self
+    }
+
+    /// Register another Parquet file as a table for the SQL tab, also in files opened
+    /// later.
+    pub fn attach_table(&mut self, name: &str, path: &str) -> Result<(), String> {
+        self.sql_session.attach(name, path)?;
+        self.attached_tables
+            .push((name.to_string(), path.to_string()));
+        Ok(())
+    }
+
+    /// Paths of all open files in the order they were opened.
+    fn file_paths(&self) -> Vec<&str> {
+        let mut paths: Vec<&str> = self
+            .other_files
+            .iter()
+            .map(|file| file.parquet_ctx.file_path.as_str())
+            .collect();
+        paths.insert(self.active_file, &self.parquet_ctx.file_path);
+        paths
+    }
+
+    /// Show the open file at `to`, putting the shown one aside.
+    fn switch_file(&mut self, to: usize) {
+        if to == self.active_file || to > self.other_files.len() {
+            return;
+        }
+        let at = if to < self.active_file { to } else { to - 1 };
+        let other = &mut self.other_files[at];
+        std::mem::swap(&mut self.parquet_ctx, &mut other.parquet_ctx);
+        std::mem::swap(&mut self.tabs, &mut other.tabs);
+        std::mem::swap(&mut self.state, &mut other.state);
+        std::mem::swap(&mut self.sql_session, &mut other.sql_session);
+        std::mem::swap(&mut self.file_watch, &mut other.file_watch);
+        std::mem::swap(&mut self.state.sql_history, &mut other.state.sql_history);
+
+        // The file put aside goes back to its place in the order files were opened
+        let previous = self.other_files.remove(at);
+        let at = if self.active_file < to {
+            self.active_file
+        } else {
+            self.active_file - 1
+        };
+        self.other_files.insert(at, previous);
+        self.active_file = to;
+        self.file_name = self.parquet_ctx.file_path.clone();
+        self.state.notice = Some(format!("Switched to {}", self.file_name));
+    }
+
+    /// Open a Parquet file and show it, or switch to it when it is already open.
+    fn open_file(&mut self, path: &str) {
+        let key = |p: &str| std::fs::canonicalize(p).unwrap_or_else(|_| p.into());
+        if let Some(open) = self.file_paths().iter().position(|p| key(p) == key(path)) {
+            self.switch_file(open);
+            return;
+        }
+        let parquet_ctx = match ParquetCtx::from_file(path) {
+            Ok(parquet_ctx) => parquet_ctx,
+            Err(e) => {
+                self.state.notice = Some(format!("Cannot open {path}: {e}"));
+                return;
+            }
+        };
+        let mut file = OpenFile::new(parquet_ctx);
+        if self.watch {
+            file.file_watch = Some(FileWatch::new(&file.parquet_ctx.file_path));
+        }
+        let failed: Vec<String> = self
+            .attached_tables
+            .iter()
+            .filter_map(|(name, table)| {
+                let e = file.sql_session.attach(name, table).err()?;
+                Some(format!("--table {name}={table}: {e}"))
+            })
+            .collect();
+        self.other_files.push(file);
+        self.switch_file(self.other_files.len());
+        if !failed.is_empty() {
+            self.state.notice = Some(failed.join("; "));
+        }
+    }
+
+    /// Browse for a file to open, from the directory of the file shown.
+    fn open_file_browser(&mut self) {
+        let path = std::path::Path::new(&self.parquet_ctx.file_path);
+        let dir = path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or(std::path::Path::new("."));
+        self.state.file_browser = Some(FileBrowser::new(dir));
+    }
+
+    /// Close an open file other than the one shown.
+    fn close_file(&mut self, idx: usize) {
+        if idx == self.active_file || idx > self.other_files.len() {
+            self.state.notice = Some("The file shown cannot be closed".to_string());
+            return;
+        }
+        let at = if idx < self.active_file { idx } else { idx - 1 };
+        self.other_files.remove(at);
+        if idx < self.active_file {
+            self.active_file -= 1;
         }
     }
 
@@ -215,133 +735,2257 @@ impl<'a> App<'a> {
             let visible_data_rows = (terminal_size.height.saturating_sub(7) as usize).max(1);
             self.state.set_visible_data_rows(visible_data_rows);
 
+            self.poll_sql_job();
+            self.poll_view_job();
+            self.poll_compression_job();
+            self.poll_profile_job();
+            self.poll_group_by_job();
+            self.poll_file_watch();
+            // The chart gets the width next to its column list, less the Y axis labels
+            self.refresh_sql_chart(terminal_size.width.saturating_sub(CHART_COLUMNS_WIDTH + 12));
+            self.refresh_row_detail();
             let render_view = AppRenderView::from_app(self);
             terminal.draw(|frame| crate::ui::render_app(&render_view, frame))?;
+            // Keep the horizontal scroll the table settled on, so it stays put while the
+            // cursor moves among the columns in view
+            if let Some(table) = &self.hits.borrow().table {
+                self.state.set_data_horizontal_scroll(table.first_scrolled);
+            }
             self.handle_events()?;
         }
         Ok(())
     }
 
     fn handle_events(&mut self) -> io::Result<()> {
+        // Redraw regularly while background work runs to advance the spinner and pick up
+        // the result, and check watched files for changes
+        let timeout = if self.state.job_running() {
+            Some(Duration::from_millis(100))
+        } else {
+            self.file_watch
+                .as_ref()
+                .map(|_| Duration::from_millis(WATCH_INTERVAL_MS))
+        };
+        if let Some(timeout) = timeout
+            && !event::poll(timeout)?
+        {
+            return Ok(());
+        }
         match event::read()? {
             Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
                 self.handle_key_event(key_event)
             }
+            Event::Mouse(mouse_event) => self.handle_mouse_event(mouse_event),
             _ => {}
         };
         Ok(())
     }
 
     fn handle_key_event(&mut self, key_event: KeyEvent) {
-        // Row detail overlay: Esc (close), ↑↓ PgUp PgDn (vertical), ←→ (horizontal), Ctrl+X (quit)
+        self.state.notice = None;
+
+        // Overlays, prompts and the SQL editor take keys as they are typed; elsewhere
+        // keys stand for the action they are bound to. Quit answers to its Ctrl and Alt
+        // keys everywhere.
+        let chord = key_event
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+        if self.takes_raw_keys() && (chord || !self.pending_keys.is_empty()) {
+            match self
+                .keymap
+                .resolve_among(&[Action::Quit], &mut self.pending_keys, key_event)
+            {
+                KeyInput::Pending => return,
+                KeyInput::Action(_) => return self.exit(),
+                KeyInput::Unbound(_) => {}
+            }
+        }
+        if self.handle_raw_key(key_event) {
+            self.pending_keys.clear();
+            return;
+        }
+        let tab = self.tabs.active_tab().to_string();
+        match self.keymap.resolve(&tab, &mut self.pending_keys, key_event) {
+            KeyInput::Pending => {}
+            KeyInput::Action(action) => self.handle_action(action),
+            KeyInput::Unbound(key_event) => self.handle_unbound_key(key_event),
+        }
+    }
+
+    /// Keys of an open overlay, prompt or the SQL editor; `false` when none is open.
+    fn handle_raw_key(&mut self, key_event: KeyEvent) -> bool {
+        // Ctrl and Alt letters do not stand for the letter in overlays
+        let plain = !key_event
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT);
+
+        // Help: Esc / ? / q (close), ↑↓ PgUp PgDn (scroll)
+        if self.state.help_shown {
+            const HELP_PAGE_SIZE: usize = 10;
+            let scroll = self.state.help_scroll;
+            match key_event.code {
+                KeyCode::Esc => self.state.help_shown = false,
+                KeyCode::Char('?') | KeyCode::Char('q') if plain => {
+                    self.state.help_shown = false;
+                }
+                KeyCode::Up => self.state.help_scroll = scroll.saturating_sub(1),
+                KeyCode::Down => self.state.help_scroll = scroll + 1,
+                KeyCode::PageUp => self.state.help_scroll = scroll.saturating_sub(HELP_PAGE_SIZE),
+                KeyCode::PageDown => self.state.help_scroll = scroll + HELP_PAGE_SIZE,
+                _ => {}
+            }
+            return true;
+        }
+
+        // Command palette: type to filter, ↑↓ (select), Enter (run), Esc (close)
+        if let Some(palette) = self.state.palette.as_mut() {
+            match key_event.code {
+                KeyCode::Esc => self.state.palette = None,
+                KeyCode::Up => palette.selected = palette.selected.saturating_sub(1),
+                KeyCode::Down if palette.selected + 1 < palette.matches().len() => {
+                    palette.selected += 1;
+                }
+                KeyCode::Enter => {
+                    let command = palette.selected_command();
+                    self.state.palette = None;
+                    if let Some(command) = command {
+                        self.run_command(command);
+                    }
+                }
+                KeyCode::Backspace => {
+                    palette.query.pop();
+                    palette.selected = 0;
+                }
+                KeyCode::Char(c) => {
+                    palette.query.push(c);
+                    palette.selected = 0;
+                }
+                _ => {}
+            }
+            return true;
+        }
+
+        // Go to row prompt: digits, Enter (go), Esc (cancel)
+        if self.state.goto_row_mode {
+            match key_event.code {
+                KeyCode::Esc => self.state.goto_row_mode = false,
+                KeyCode::Enter => {
+                    self.state.goto_row_mode = false;
+                    let tab = self.tabs.active_tab().to_string();
+                    let max_rows = self.table_rows(&tab);
+                    match self.state.goto_row_query.trim().parse::<usize>() {
+                        Ok(row) if (1..=max_rows).contains(&row) => {
+                            let column = self.state.horizontal_offset();
+                            self.state.select_cell(row - 1, column, max_rows);
+                        }
+                        _ => {
+                            self.state.notice = Some(format!(
+                                "Enter a row number from 1 to {}",
+                                commas(max_rows as u64)
+                            ));
+                        }
+                    }
+                }
+                KeyCode::Backspace => {
+                    self.state.goto_row_query.pop();
+                }
+                KeyCode::Char(c) if c.is_ascii_digit() => self.state.goto_row_query.push(c),
+                _ => {}
+            }
+            return true;
+        }
+
+        // Expanded cell: Esc / x (close), ↑↓ PgUp PgDn (scroll)
+        if self.state.expanded_cell.is_some() {
+            const CELL_PAGE_SIZE: usize = 10;
+            let scroll = self.state.expanded_cell_scroll;
+            match key_event.code {
+                KeyCode::Esc => self.state.expanded_cell = None,
+                KeyCode::Char('x') if plain => {
+                    self.state.expanded_cell = None;
+                }
+                KeyCode::Up => self.state.expanded_cell_scroll = scroll.saturating_sub(1),
+                KeyCode::Down => self.state.expanded_cell_scroll = scroll + 1,
+                KeyCode::PageUp => {
+                    self.state.expanded_cell_scroll = scroll.saturating_sub(CELL_PAGE_SIZE);
+                }
+                KeyCode::PageDown => self.state.expanded_cell_scroll = scroll + CELL_PAGE_SIZE,
+                _ => {}
+            }
+            return true;
+        }
+
+        // Yank: the next key picks what to copy, anything else cancels
+        if self.state.yank_mode {
+            self.state.yank_mode = false;
+            match key_event.code {
+                KeyCode::Char('y') | KeyCode::Char('c') if plain => self.yank(Yank::Cells),
+                KeyCode::Char('r') if plain => self.yank(Yank::RowTsv),
+                KeyCode::Char('j') if plain => self.yank(Yank::RowJson),
+                KeyCode::Char('n') if plain => self.yank(Yank::ColumnName),
+                _ => {}
+            }
+            return true;
+        }
+
+        // Open files: ↑↓ (select), Enter (switch), o (open another), d (close), Esc (close)
+        if let Some(selected) = self.state.file_list {
+            let files = self.other_files.len() + 1;
+            match key_event.code {
+                KeyCode::Esc => self.state.file_list = None,
+                KeyCode::Up => self.state.file_list = Some(selected.saturating_sub(1)),
+                KeyCode::Down => self.state.file_list = Some((selected + 1).min(files - 1)),
+                KeyCode::Enter => {
+                    self.state.file_list = None;
+                    self.switch_file(selected);
+                }
+                KeyCode::Char('o') | KeyCode::Char('O') if plain => {
+                    self.state.file_list = None;
+                    self.open_file_browser();
+                }
+                KeyCode::Char('d') | KeyCode::Char('D') if plain => {
+                    self.close_file(selected);
+                    let files = self.other_files.len() + 1;
+                    self.state.file_list = Some(selected.min(files - 1));
+                }
+                KeyCode::Delete => {
+                    self.close_file(selected);
+                    let files = self.other_files.len() + 1;
+                    self.state.file_list = Some(selected.min(files - 1));
+                }
+                _ => {}
+            }
+            return true;
+        }
+
+        // File browser: type to filter, ↑↓ (select), Enter (open a file or directory),
+        // Backspace on an empty filter (parent directory), Esc (close)
+        if let Some(browser) = self.state.file_browser.as_mut() {
+            match key_event.code {
+                KeyCode::Esc => self.state.file_browser = None,
+                KeyCode::Up => browser.selected = browser.selected.saturating_sub(1),
+                KeyCode::Down if browser.selected + 1 < browser.matches().len() => {
+                    browser.selected += 1;
+                }
+                KeyCode::Enter => {
+                    let Some(entry) = browser.selected_entry().cloned() else {
+                        return true;
+                    };
+                    let path = browser.path_of(&entry);
+                    if entry.is_dir {
+                        *browser = FileBrowser::new(&path);
+                    } else {
+                        self.state.file_browser = None;
+                        self.open_file(&path.display().to_string());
+                    }
+                }
+                KeyCode::Backspace if browser.query.is_empty() => {
+                    if let Some(parent) = browser.dir.parent() {
+                        *browser = FileBrowser::new(parent);
+                    }
+                }
+                KeyCode::Backspace => {
+                    browser.query.pop();
+                    browser.selected = 0;
+                }
+                KeyCode::Char(c) => {
+                    browser.query.push(c);
+                    browser.selected = 0;
+                }
+                _ => {}
+            }
+            return true;
+        }
+
         if self.state.row_detail_row.is_some() {
-            const DETAIL_PAGE_SIZE: usize = 10;
+            self.handle_row_detail_key(key_event);
+            return true;
+        }
+
+        // Compression simulation: Esc (close), ↑↓ (scroll trials)
+        if self.state.compression_simulation.is_some() || self.state.compression_job.is_some() {
+            match key_event.code {
+                KeyCode::Esc => {
+                    self.state.compression_simulation = None;
+                    self.state.compression_job = None;
+                }
+                KeyCode::Up => {
+                    self.state.compression_scroll = self.state.compression_scroll.saturating_sub(1);
+                }
+                KeyCode::Down => {
+                    self.state.compression_scroll += 1;
+                }
+                _ => {}
+            }
+            return true;
+        }
+
+        // Explain pane: Esc (close), ↑↓ PgUp PgDn (scroll)
+        if self.state.sql_plan.is_some() && self.tabs.active_tab().to_string() == "SQL" {
+            const PLAN_PAGE_SIZE: usize = 10;
             match key_event.code {
                 KeyCode::Esc => {
-                    self.state.row_detail_row = None;
+                    self.state.sql_plan = None;
                 }
                 KeyCode::Up => {
-                    self.state.detail_scroll_offset =
-                        self.state.detail_scroll_offset.saturating_sub(1);
+                    self.state.sql_plan_scroll = self.state.sql_plan_scroll.saturating_sub(1);
                 }
                 KeyCode::Down => {
-                    self.state.detail_scroll_offset += 1;
+                    self.state.sql_plan_scroll += 1;
                 }
                 KeyCode::PageUp => {
-                    self.state.detail_scroll_offset = self
-                        .state
-                        .detail_scroll_offset
-                        .saturating_sub(DETAIL_PAGE_SIZE);
+                    self.state.sql_plan_scroll =
+                        self.state.sql_plan_scroll.saturating_sub(PLAN_PAGE_SIZE);
                 }
                 KeyCode::PageDown => {
-                    self.state.detail_scroll_offset += DETAIL_PAGE_SIZE;
+                    self.state.sql_plan_scroll += PLAN_PAGE_SIZE;
                 }
-                KeyCode::Left => {
-                    self.state.detail_scroll_horizontal =
-                        self.state.detail_scroll_horizontal.saturating_sub(1);
+                _ => {}
+            }
+            return true;
+        }
+
+        // Group-by still counting: Esc (cancel)
+        if self.state.group_by_job.is_some() && self.tabs.active_tab().to_string() == "Visualize" {
+            if key_event.code == KeyCode::Esc {
+                self.state.group_by_job = None;
+            }
+            return true;
+        }
+
+        // Group-by view: ↑↓ u/d (select), Enter (filter the table to the group), Esc (close)
+        if let Some(group_by) = &self.state.group_by
+            && self.tabs.active_tab().to_string() == "Visualize"
+        {
+            let groups = group_by.as_ref().map_or(0, |g| g.groups.len());
+            let last = groups.saturating_sub(1);
+            let selected = self.state.group_by_selected;
+            let page = self.state.visible_data_rows();
+            match key_event.code {
+                KeyCode::Esc => {
+                    self.state.group_by = None;
+                }
+                KeyCode::Up => {
+                    self.state.group_by_selected = selected.saturating_sub(1);
                 }
-                KeyCode::Right => {
-                    self.state.detail_scroll_horizontal += 1;
+                KeyCode::Down => {
+                    self.state.group_by_selected = (selected + 1).min(last);
+                }
+                KeyCode::Char('u') | KeyCode::Char('U') if plain => {
+                    self.state.group_by_selected = selected.saturating_sub(page);
+                }
+                KeyCode::PageUp => {
+                    self.state.group_by_selected = selected.saturating_sub(page);
+                }
+                KeyCode::Char('d') | KeyCode::Char('D') if plain => {
+                    self.state.group_by_selected = (selected + page).min(last);
+                }
+                KeyCode::PageDown => {
+                    self.state.group_by_selected = (selected + page).min(last);
+                }
+                KeyCode::Enter => {
+                    let query = group_by
+                        .as_ref()
+                        .ok()
+                        .and_then(|g| g.groups.get(selected).map(|group| g.filter_query(group)));
+                    if let Some(query) = query {
+                        self.state.group_by = None;
+                        self.state.search_query = query.clone();
+                        let (_, sort) = self.view_target();
+                        self.apply_view(Some(query), sort, AfterView::FirstCell);
+                    }
+                }
+                _ => {}
+            }
+            return true;
+        }
+
+        // Column chooser: type to filter, ↑↓ (select), Enter (show / hide), Tab (pin),
+        // Shift+↑↓ or Alt+↑↓ (move), Esc (close and save)
+        if self.state.column_chooser.is_some() {
+            self.handle_column_chooser_key(key_event);
+            return true;
+        }
+
+        // Chart: ↑↓ (column), x (use as X), space / y (toggle Y), t (chart type), Esc (close)
+        if let Some(spec) = self.state.sql_chart.as_mut()
+            && self.tabs.active_tab().to_string() == "SQL"
+        {
+            let columns = self.state.sql_frame.as_ref().map_or(0, |df| df.width());
+            let cursor = self.state.sql_chart_cursor;
+            match key_event.code {
+                KeyCode::Esc => {
+                    self.state.sql_chart = None;
+                    self.state.sql_chart_data = None;
+                }
+                KeyCode::Up => {
+                    self.state.sql_chart_cursor = cursor.saturating_sub(1);
                 }
-                KeyCode::Char('x') | KeyCode::Char('X')
-                    if key_event.modifiers.contains(KeyModifiers::CONTROL) =>
+                KeyCode::Down if cursor + 1 < columns => {
+                    self.state.sql_chart_cursor += 1;
+                }
+                KeyCode::Char('x') | KeyCode::Char('X') if plain => {
+                    spec.x = cursor;
+                    spec.ys.retain(|&y| y != cursor);
+                    self.state.sql_chart_data = None;
+                }
+                KeyCode::Char(' ') | KeyCode::Char('y') | KeyCode::Char('Y')
+                    if plain && cursor != spec.x =>
                 {
-                    self.exit();
+                    spec.toggle_y(cursor);
+                    self.state.sql_chart_data = None;
+                }
+                KeyCode::Char('t') | KeyCode::Char('T') if plain => {
+                    spec.kind = spec.kind.next();
+                    self.state.sql_chart_data = None;
                 }
                 _ => {}
             }
-            return;
+            return true;
         }
 
-        // Search mode: consume input until Enter or Esc
-        if self.state.search_mode {
+        // Save query prompt: Enter (save), Tab (user / project library), Esc (cancel)
+        if self.state.save_query_mode {
             match key_event.code {
                 KeyCode::Esc => {
-                    self.state.search_mode = false;
-                    self.state.search_query.clear();
+                    self.state.save_query_mode = false;
                 }
                 KeyCode::Enter => {
-                    let query = self.state.search_query.clone();
-                    let filtered = self.parquet_ctx.sample_data.filter_rows(&query);
-                    self.state.search_filter = Some(query);
-                    self.state.filtered_sample_data = Some(filtered);
-                    self.state.reset();
-                    self.state.search_mode = false;
+                    self.state.save_query_mode = false;
+                    self.save_query();
+                }
+                KeyCode::Tab => {
+                    self.state.save_query_scope = match self.state.save_query_scope {
+                        LibraryScope::User => LibraryScope::Project,
+                        LibraryScope::Project => LibraryScope::User,
+                    };
                 }
                 KeyCode::Backspace => {
-                    self.state.search_query.pop();
+                    self.state.save_query_name.pop();
                 }
                 KeyCode::Char(c) => {
-                    self.state.search_query.push(c);
+                    self.state.save_query_name.push(c);
                 }
                 _ => {}
             }
-            return;
+            return true;
         }
 
-        match key_event.code {
-            KeyCode::Char('x') | KeyCode::Char('X')
-                if key_event.modifiers.contains(KeyModifiers::CONTROL) =>
-            {
-                self.exit()
-            }
-            KeyCode::Esc => {
-                if self.state.search_filter.is_some() {
-                    self.state.clear_search_filter();
-                    self.state.reset();
-                } else if self.tabs.active_tab().to_string() == "SQL" {
-                    self.state.sql_query.clear();
-                    self.state.sql_result = None;
-                } else {
-                    self.state.reset();
+        // Parameters of a saved query: one value per Enter, Esc (cancel)
+        if let Some(prompt) = self.state.query_parameters.as_mut() {
+            match key_event.code {
+                KeyCode::Esc => {
+                    self.state.query_parameters = None;
                 }
+                KeyCode::Enter => {
+                    if let Some(sql) = prompt.submit() {
+                        self.state.query_parameters = None;
+                        self.state.sql_editor.set_text(&sql);
+                        self.run_sql_query();
+                    }
+                }
+                KeyCode::Backspace => {
+                    prompt.input.pop();
+                }
+                KeyCode::Char(c) => {
+                    prompt.input.push(c);
+                }
+                _ => {}
             }
-            KeyCode::Char('/') => {
-                self.state.search_mode = true;
-                self.state.search_query.clear();
-            }
-            KeyCode::Enter if self.tabs.active_tab().to_string() == "SQL" => {
-                self.state.sql_result =
-                    Some(run_sql(&self.parquet_ctx.file_path, &self.state.sql_query));
+            return true;
+        }
+
+        // Query library picker: ↑↓ (select), Enter (run), e (edit), d (delete), Esc (close)
+        if let Some(library) = self.state.query_library.as_mut() {
+            let selected = self.state.query_library_selected;
+            match key_event.code {
+                KeyCode::Esc => {
+                    self.state.query_library = None;
+                }
+                KeyCode::Up => {
+                    self.state.query_library_selected = selected.saturating_sub(1);
+                }
+                KeyCode::Down if selected + 1 < library.queries.len() => {
+                    self.state.query_library_selected += 1;
+                }
+                KeyCode::Enter | KeyCode::Char('e') | KeyCode::Char('E') if plain => {
+                    if let Some((_, query)) = library.queries.get(selected).cloned() {
+                        self.state.query_library = None;
+                        if key_event.code == KeyCode::Enter {
+                            self.run_saved_query(query);
+                        } else {
+                            self.state.sql_editing = true;
+                            self.state.sql_editor.set_text(&query.sql);
+                        }
+                    }
+                }
+                KeyCode::Char('d') | KeyCode::Char('D') | KeyCode::Delete if plain => {
+                    if let Err(e) = library.remove(selected) {
+                        self.state.notice = Some(e);
+                    }
+                    self.state.query_library_selected =
+                        selected.min(library.queries.len().saturating_sub(1));
+                }
+                _ => {}
             }
-            KeyCode::Tab => {
+            return true;
+        }
+
+        // Find mode: consume input until Enter or Esc
+        if self.state.find_mode {
+            match key_event.code {
+                KeyCode::Esc => {
+                    self.state.find_mode = false;
+                }
+                KeyCode::Enter => {
+                    self.state.find_mode = false;
+                    let query = self.state.find_query.clone();
+                    if query.is_empty() {
+                        self.state.find = None;
+                    } else {
+                        let data = self.state.visualize_data(&self.parquet_ctx.sample_data);
+                        let (columns, _) = self.state.column_layout.visible();
+                        self.state.find = Some(FindMatches::search_columns(data, &query, &columns));
+                        self.find_next(true);
+                    }
+                }
+                KeyCode::Backspace => {
+                    self.state.find_query.pop();
+                }
+                KeyCode::Char(c) => {
+                    self.state.find_query.push(c);
+                }
+                _ => {}
+            }
+            return true;
+        }
+
+        // Pushdown preview mode: consume input until Enter or Esc
+        if self.state.pruning_mode {
+            match key_event.code {
+                KeyCode::Esc => {
+                    self.state.pruning_mode = false;
+                }
+                KeyCode::Enter => {
+                    self.state.pruning_mode = false;
+                    let query = self.state.pruning_query.trim().to_string();
+                    if query.is_empty() {
+                        self.state.pruning = None;
+                    } else {
+                        match PruningReport::analyze(&self.parquet_ctx.file_path, &query) {
+                            Ok(report) => self.state.pruning = Some(report),
+                            Err(e) => self.state.notice = Some(format!("Invalid filter: {e}")),
+                        }
+                    }
+                }
+                KeyCode::Backspace => {
+                    self.state.pruning_query.pop();
+                }
+                KeyCode::Char(c) => {
+                    self.state.pruning_query.push(c);
+                }
+                _ => {}
+            }
+            return true;
+        }
+
+        // Search mode: consume input until Enter or Esc
+        if self.state.search_mode {
+            match key_event.code {
+                KeyCode::Esc => {
+                    self.state.search_mode = false;
+                    self.state.search_query.clear();
+                }
+                KeyCode::Enter => {
+                    let query = self.state.search_query.trim().to_string();
+                    self.state.search_mode = false;
+                    if query.is_empty() {
+                        self.clear_filter();
+                    } else {
+                        let (_, sort) = self.view_target();
+                        self.apply_view(Some(query), sort, AfterView::FirstCell);
+                    }
+                }
+                KeyCode::Backspace => {
+                    self.state.search_query.pop();
+                }
+                KeyCode::Char(c) => {
+                    self.state.search_query.push(c);
+                }
+                _ => {}
+            }
+            return true;
+        }
+
+        if self.state.sql_editing && self.tabs.active_tab().to_string() == "SQL" {
+            if key_event.code == KeyCode::Esc && self.cancel_sql_job() {
+                return true;
+            }
+            self.handle_sql_editor_key(key_event);
+            return true;
+        }
+        false
+    }
+
+    /// Stop the query running in the background, if any.
+    fn cancel_sql_job(&mut self) -> bool {
+        let Some(job) = self.state.sql_job.take() else {
+            return false;
+        };
+        job.cancel();
+        self.state.notice = Some("Query cancelled".to_string());
+        true
+    }
+
+    fn handle_action(&mut self, action: Action) {
+        let tab = self.tabs.active_tab().to_string();
+        match action {
+            Action::Quit => self.exit(),
+            Action::Help => {
+                self.state.help_shown = true;
+                self.state.help_scroll = 0;
+            }
+            Action::Palette => self.open_palette(),
+            Action::Files => {
+                // Start on the next file, so b Enter flips between two files
+                let files = self.other_files.len() + 1;
+                self.state.file_list = Some((self.active_file + 1) % files);
+            }
+            Action::NextTab => {
                 self.tabs.next();
                 self.state.reset();
             }
-            KeyCode::BackTab => {
+            Action::PrevTab => {
                 self.tabs.prev();
                 self.state.reset();
             }
+            Action::Cancel if tab == "SQL" && self.cancel_sql_job() => {}
+            Action::Cancel => {
+                if let Some(view) = self.state.view_job.take() {
+                    if view.after == AfterView::Refresh {
+                        self.drop_view();
+                    }
+                } else if self.state.selection_anchor.is_some() {
+                    self.state.selection_anchor = None;
+                } else if self.state.find.is_some() {
+                    self.state.find = None;
+                } else if self.state.search_filter.is_some() {
+                    self.clear_filter();
+                    self.state.reset();
+                } else if self.state.column_profile.is_some() || self.state.profile_job.is_some() {
+                    self.state.column_profile = None;
+                    self.state.profile_job = None;
+                } else if self.state.pruning.is_some() && tab == "Row Groups" {
+                    self.state.pruning = None;
+                } else if tab == "SQL" {
+                    self.state.sql_editing = true;
+                } else {
+                    self.state.reset();
+                }
+            }
+            Action::Search => {
+                self.state.search_mode = true;
+                // Start from the active filter so it can be refined
+                self.state.search_query = self.state.search_filter.clone().unwrap_or_default();
+            }
+            Action::FetchMore if self.state.sql_has_more && self.state.sql_job.is_none() => {
+                let offset = match &self.state.sql_result {
+                    Some(SqlResult::Ok(data)) => data.total_rows,
+                    _ => 0,
+                };
+                let query = self.state.sql_result_query.clone();
+                self.start_sql_job(&query, offset);
+            }
+            Action::Explain => self.explain_sql_query(),
+            Action::SavedQueries => self.open_query_library(),
+            Action::Chart => {
+                if let Some(frame) = &self.state.sql_frame {
+                    self.state.sql_chart = Some(ChartSpec::for_frame(frame));
+                    self.state.sql_chart_cursor = 0;
+                    self.state.sql_chart_data = None;
+                }
+            }
+            Action::Summary => {
+                self.state.sql_summary_shown = !self.state.sql_summary_shown;
+                self.refresh_sql_summary();
+            }
+            Action::Compression => self.simulate_compression(),
+            Action::Profile => {
+                if let Some(leaf_idx) = self.state.vertical_offset().checked_sub(1) {
+                    let file_path = self.parquet_ctx.file_path.clone();
+                    self.state.profile_job = Some(Job::spawn(move || {
+                        ColumnProfile::from_file(&file_path, leaf_idx).map_err(|e| e.to_string())
+                    }));
+                    self.state.column_profile = None;
+                }
+            }
+            Action::Pushdown => {
+                self.state.pruning_mode = true;
+                self.state.pruning_query = self
+                    .state
+                    .pruning
+                    .as_ref()
+                    .map(|p| p.filter.clone())
+                    .unwrap_or_default();
+            }
+            Action::Right if tab == "Storage" => self.storage_drill_down(),
+            Action::Left if tab == "Storage" => self.storage_drill_up(),
+            Action::Sort => self.toggle_sort(),
+            Action::Columns => self.state.column_chooser = Some(ColumnChooser::default()),
+            Action::GroupBy => {
+                let columns = &self.parquet_ctx.sample_data.flattened_columns;
+                if let Some(column) = self.state.current_column().map(|c| columns[c].clone()) {
+                    let file_path = self.parquet_ctx.file_path.clone();
+                    self.state.group_by_job =
+                        Some(Job::spawn(move || GroupBy::from_file(&file_path, &column)));
+                    self.state.group_by = None;
+                    self.state.group_by_selected = 0;
+                }
+            }
+            Action::Find => {
+                self.state.find_mode = true;
+                self.state.find_query = self
+                    .state
+                    .find
+                    .as_ref()
+                    .map(|f| f.query.clone())
+                    .unwrap_or_default();
+            }
+            Action::FindNext if self.state.find.is_some() => self.find_next(false),
+            Action::FindPrev if self.state.find.is_some() => self.find_prev(),
+            Action::NarrowColumn if self.on_data_table() => self.resize_column(-COLUMN_WIDTH_STEP),
+            Action::WidenColumn if self.on_data_table() => self.resize_column(COLUMN_WIDTH_STEP),
+            Action::WrapRow if self.on_data_table() => self.state.wrap_row = !self.state.wrap_row,
+            Action::ExpandCell if self.on_data_table() => self.expand_cell(),
+            Action::Mark if self.on_data_table() => {
+                self.state.selection_anchor = match self.state.selection_anchor {
+                    Some(_) => None,
+                    None => Some((self.state.vertical_offset(), self.state.horizontal_offset())),
+                };
+            }
+            Action::Yank if self.on_data_table() => self.state.yank_mode = true,
             _ => {
                 self.tabs
                     .active_tab()
-                    .on_event(key_event, &mut self.state)
+                    .on_action(action, &mut self.state)
                     .unwrap();
             }
         }
     }
 
+    /// Keys bound to no action that still mean something in a tab.
+    fn handle_unbound_key(&mut self, key_event: KeyEvent) {
+        match (self.tabs.active_tab().to_string().as_str(), key_event.code) {
+            ("Storage", KeyCode::Enter) => self.storage_drill_down(),
+            ("Storage", KeyCode::Backspace) => self.storage_drill_up(),
+            ("SQL", KeyCode::Enter) => self.state.sql_editing = true,
+            _ => {}
+        }
+    }
+
+    /// Mouse: the wheel scrolls like ↑ / ↓ (← / → with Shift), a click selects a tab,
+    /// a table cell, a schema column or a row group, and dragging the gap after a
+    /// column header resizes the column.
+    fn handle_mouse_event(&mut self, mouse: MouseEvent) {
+        let hits = self.hits.borrow().clone();
+        let position = Position::new(mouse.column, mouse.row);
+        let on_table = hits
+            .table
+            .as_ref()
+            .filter(|table| table.area.contains(position));
+        let tab = self.tabs.active_tab().to_string();
+
+        match mouse.kind {
+            MouseEventKind::ScrollUp
+            | MouseEventKind::ScrollDown
+            | MouseEventKind::ScrollLeft
+            | MouseEventKind::ScrollRight => {
+                let sideways = mouse.modifiers.contains(KeyModifiers::SHIFT);
+                let (code, action) = match mouse.kind {
+                    MouseEventKind::ScrollUp if !sideways => (KeyCode::Up, Action::Up),
+                    MouseEventKind::ScrollDown if !sideways => (KeyCode::Down, Action::Down),
+                    MouseEventKind::ScrollUp | MouseEventKind::ScrollLeft => {
+                        (KeyCode::Left, Action::Left)
+                    }
+                    _ => (KeyCode::Right, Action::Right),
+                };
+                // The wheel over the results scrolls them even while the editor has focus
+                if on_table.is_some() && tab == "SQL" {
+                    self.state.sql_editing = false;
+                }
+                if self.takes_text_input() {
+                    return;
+                }
+                self.state.notice = None;
+                // Overlays scroll with the arrow keys whatever they are bound to
+                for _ in 0..WHEEL_LINES {
+                    if !self.handle_raw_key(KeyEvent::new(code, KeyModifiers::NONE)) {
+                        self.handle_action(action);
+                    }
+                }
+            }
+            MouseEventKind::Down(MouseButton::Left) => {
+                if self.overlay_open() {
+                    return;
+                }
+                if let Some(area) = hits.tabs.filter(|area| area.contains(position)) {
+                    if let Some(idx) = self.tabs.tab_at(area, position.x) {
+                        self.tabs.active_tab = idx;
+                        self.state.reset();
+                    }
+                } else if let Some(table) = on_table {
+                    if tab == "SQL" {
+                        self.state.sql_editing = false;
+                    }
+                    if let Some((column, width)) = table.separator_at(position.x, position.y) {
+                        self.column_drag = Some(ColumnDrag {
+                            column,
+                            x: position.x,
+                            width,
+                        });
+                        return;
+                    }
+                    let row = table
+                        .row_at(position.y)
+                        .unwrap_or(self.state.vertical_offset());
+                    let column = table
+                        .column_at(position.x)
+                        .unwrap_or(self.state.horizontal_offset());
+                    let max_rows = self.table_rows(&tab);
+                    self.state.select_cell(row, column, max_rows);
+                } else if let Some((area, scroll)) =
+                    hits.schema_tree.filter(|(area, _)| area.contains(position))
+                {
+                    let columns = &self.parquet_ctx.schema.columns;
+                    let Some(item) = SchemaTreeComponent::item_at(area, scroll, position.y)
+                        .filter(|&item| item < columns.len())
+                    else {
+                        return;
+                    };
+                    // Rows past the root select the n-th leaf column, the root the overview
+                    let selected = match columns[item] {
+                        SchemaInfo::Primitive { .. } => columns[..=item]
+                            .iter()
+                            .filter(|c| matches!(c, SchemaInfo::Primitive { .. }))
+                            .count(),
+                        SchemaInfo::Root { .. } => 0,
+                        SchemaInfo::Group { .. } => return,
+                    };
+                    self.state
+                        .select_cell(selected, self.state.horizontal_offset(), 0);
+                } else if let Some(area) = hits.row_groups.filter(|area| area.contains(position)) {
+                    let row_groups = self.parquet_ctx.row_groups.num_row_groups();
+                    if let Some(idx) = RowGroupProgressBar::segment_at(area, row_groups, position.x)
+                    {
+                        self.state.select_cell(self.state.vertical_offset(), idx, 0);
+                    }
+                }
+            }
+            MouseEventKind::Drag(MouseButton::Left) => {
+                let Some(drag) = self.column_drag else {
+                    return;
+                };
+                let width = (drag.width as i32 + mouse.column as i32 - drag.x as i32)
+                    .clamp(MIN_COLUMN_WIDTH as i32, u16::MAX as i32)
+                    as u16;
+                let widths = if tab == "SQL" {
+                    &mut self.state.sql_column_widths
+                } else {
+                    &mut self.state.visualize_column_widths
+                };
+                widths.insert(drag.column, width);
+            }
+            MouseEventKind::Up(MouseButton::Left) => self.column_drag = None,
+            _ => {}
+        }
+    }
+
+    /// Whether the keys go to the Visualize table or the SQL result table.
+    fn on_data_table(&self) -> bool {
+        match self.tabs.active_tab().to_string().as_str() {
+            "Visualize" => true,
+            "SQL" => matches!(self.state.sql_result, Some(SqlResult::Ok(_))),
+            _ => false,
+        }
+    }
+
+    /// Widths set for the columns of the table in the active tab.
+    fn column_widths_mut(&mut self) -> &mut HashMap<usize, u16> {
+        if self.tabs.active_tab().to_string() == "SQL" {
+            &mut self.state.sql_column_widths
+        } else {
+            &mut self.state.visualize_column_widths
+        }
+    }
+
+    /// Widen or narrow the current column from the width it was last drawn with.
+    fn resize_column(&mut self, delta: i32) {
+        let position = self.state.horizontal_offset();
+        let Some(column) = self
+            .hits
+            .borrow()
+            .table
+            .as_ref()
+            .and_then(|table| table.columns.iter().find(|c| c.position == position))
+            .copied()
+        else {
+            return;
+        };
+        let width = (column.content_width() as i32 + delta)
+            .clamp(MIN_COLUMN_WIDTH as i32, u16::MAX as i32) as u16;
+        self.column_widths_mut().insert(column.column, width);
+    }
+
+    /// Open the selected cell of the table in a popup.
+    fn expand_cell(&mut self) {
+        let row = self.state.vertical_offset();
+        let (data, column) = match self.tabs.active_tab().to_string().as_str() {
+            "SQL" => match &self.state.sql_result {
+                Some(SqlResult::Ok(data)) => (data, Some(self.state.horizontal_offset())),
+                _ => return,
+            },
+            _ => (
+                self.state.visualize_data(&self.parquet_ctx.sample_data),
+                self.state.current_column(),
+            ),
+        };
+        let cell = column.and_then(|c| {
+            let value = data.rows.get(row)?.get(c)?;
+            Some((data.flattened_columns.get(c)?.clone(), pretty_value(value)))
+        });
+        if cell.is_some() {
+            self.state.expanded_cell = cell;
+            self.state.expanded_cell_scroll = 0;
+        }
+    }
+
+    /// Copy part of the table in the active tab to the clipboard.
+    fn yank(&mut self, what: Yank) {
+        let row = self.state.vertical_offset();
+        let (data, columns) = match self.tabs.active_tab().to_string().as_str() {
+            "SQL" => match &self.state.sql_result {
+                Some(SqlResult::Ok(data)) => (data, (0..data.flattened_columns.len()).collect()),
+                _ => return,
+            },
+            _ => (
+                self.state.visualize_data(&self.parquet_ctx.sample_data),
+                self.state.column_layout.visible().0,
+            ),
+        };
+        let Some(cells) = data.rows.get(row) else {
+            return;
+        };
+        let names: Vec<&str> = data.flattened_columns.iter().map(String::as_str).collect();
+        let cells: Vec<&str> = cells.iter().map(String::as_str).collect();
+        let current = columns.get(self.state.horizontal_offset()).copied();
+
+        let (text, copied) = match what {
+            Yank::Cells => match self.state.selection() {
+                Some((rows, positions)) => {
+                    let marked = columns.get(positions.clone()).unwrap_or_default();
+                    let rows: Vec<Vec<&str>> = data
+                        .rows
+                        .get(rows)
+                        .unwrap_or_default()
+                        .iter()
+                        .map(|cells| {
+                            marked
+                                .iter()
+                                .filter_map(|&c| cells.get(c).map(String::as_str))
+                                .collect()
+                        })
+                        .collect();
+                    let copied = format!("{} × {} cells", rows.len(), marked.len());
+                    (clipboard::tsv(&rows), copied)
+                }
+                None => {
+                    let Some(cell) = current.and_then(|c| cells.get(c)) else {
+                        return;
+                    };
+                    (clipboard::cell_text(cell).to_string(), "cell".to_string())
+                }
+            },
+            Yank::RowTsv => (clipboard::tsv(&[cells]), format!("row {}", row + 1)),
+            Yank::RowJson => (
+                clipboard::row_json(&names, &cells),
+                format!("row {} as JSON", row + 1),
+            ),
+            Yank::ColumnName => {
+                let Some(name) = current.and_then(|c| names.get(c)) else {
+                    return;
+                };
+                (name.to_string(), format!("column name {name}"))
+            }
+        };
+        self.state.notice = Some(match clipboard::copy(&text) {
+            Ok(()) => format!("Copied {copied}"),
+            Err(e) => format!("Copy failed: {e}"),
+        });
+        if what == Yank::Cells {
+            self.state.selection_anchor = None;
+        }
+    }
+
+    /// Row detail overlay: ↑↓ PgUp PgDn Home End move between nodes, → / ← expand /
+    /// collapse (or step into / out of a node), Enter or Space toggles, + / - expand /
+    /// collapse all, / finds, n / N jump between matches, r shows the raw JSON (scrolled
+    /// with the arrows), y copies it, [ / ] open the previous / next row, Esc closes.
+    fn handle_row_detail_key(&mut self, key_event: KeyEvent) {
+        const DETAIL_PAGE_SIZE: usize = 10;
+        let state = &mut self.state;
+        if state.detail_find_mode {
+            match key_event.code {
+                KeyCode::Esc => state.detail_find_mode = false,
+                KeyCode::Enter => {
+                    state.detail_find_mode = false;
+                    self.detail_find_next(true, true);
+                }
+                KeyCode::Backspace => {
+                    state.detail_query.pop();
+                }
+                KeyCode::Char(c) => state.detail_query.push(c),
+                _ => {}
+            }
+            return;
+        }
+
+        match key_event.code {
+            KeyCode::Esc if !state.detail_query.is_empty() => state.detail_query.clear(),
+            KeyCode::Esc => {
+                state.row_detail_row = None;
+                state.row_detail = None;
+                state.detail_raw = false;
+            }
+            KeyCode::Char('[') => self.step_row_detail(false),
+            KeyCode::Char(']') => self.step_row_detail(true),
+            KeyCode::Char('r') | KeyCode::Char('R') => {
+                state.detail_raw = !state.detail_raw;
+                state.detail_scroll_offset = 0;
+                state.detail_scroll_horizontal = 0;
+                self.scroll_to_detail_cursor();
+            }
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                let Some(Ok(tree)) = &state.row_detail else {
+                    return;
+                };
+                let json = tree.to_json().to_string();
+                let row = state.row_detail_row.unwrap_or(0) + 1;
+                state.notice = Some(match clipboard::copy(&json) {
+                    Ok(()) => format!("Copied row {row} as JSON"),
+                    Err(e) => format!("Copy failed: {e}"),
+                });
+            }
+            KeyCode::Char('/') => state.detail_find_mode = true,
+            _ if state.detail_raw => match key_event.code {
+                KeyCode::Up => {
+                    state.detail_scroll_offset = state.detail_scroll_offset.saturating_sub(1)
+                }
+                KeyCode::Down => state.detail_scroll_offset += 1,
+                KeyCode::PageUp => {
+                    state.detail_scroll_offset =
+                        state.detail_scroll_offset.saturating_sub(DETAIL_PAGE_SIZE);
+                }
+                KeyCode::PageDown => state.detail_scroll_offset += DETAIL_PAGE_SIZE,
+                KeyCode::Left => {
+                    state.detail_scroll_horizontal =
+                        state.detail_scroll_horizontal.saturating_sub(1);
+                }
+                KeyCode::Right => state.detail_scroll_horizontal += 1,
+                _ => {}
+            },
+            KeyCode::Char('n') => self.detail_find_next(true, false),
+            KeyCode::Char('N') => self.detail_find_next(false, false),
+            code => {
+                let Some(Ok(tree)) = state.row_detail.as_mut() else {
+                    return;
+                };
+                let cursor = state.detail_cursor;
+                let visible = tree.visible();
+                let line = visible.iter().position(|&n| n == cursor).unwrap_or(0);
+                let last = visible.len().saturating_sub(1);
+                let is_group = tree.nodes[cursor].is_group();
+                let page = state.visible_data_rows.max(1);
+                state.detail_cursor = match code {
+                    KeyCode::Up => visible[line.saturating_sub(1)],
+                    KeyCode::Down => visible[(line + 1).min(last)],
+                    KeyCode::PageUp => visible[line.saturating_sub(page)],
+                    KeyCode::PageDown => visible[(line + page).min(last)],
+                    KeyCode::Home => visible[0],
+                    KeyCode::End => visible[last],
+                    KeyCode::Right if is_group && tree.is_collapsed(cursor) => {
+                        tree.set_collapsed(cursor, false);
+                        cursor
+                    }
+                    KeyCode::Right => visible[(line + 1).min(last)],
+                    KeyCode::Left if is_group && !tree.is_collapsed(cursor) => {
+                        tree.set_collapsed(cursor, true);
+                        cursor
+                    }
+                    KeyCode::Left => tree.parent(cursor).unwrap_or(cursor),
+                    KeyCode::Enter | KeyCode::Char(' ') => {
+                        tree.set_collapsed(cursor, !tree.is_collapsed(cursor));
+                        cursor
+                    }
+                    KeyCode::Char('+') => {
+                        tree.set_all_collapsed(false);
+                        cursor
+                    }
+                    KeyCode::Char('-') => {
+                        tree.set_all_collapsed(true);
+                        let mut top = cursor;
+                        while let Some(parent) = tree.parent(top) {
+                            top = parent;
+                        }
+                        top
+                    }
+                    _ => cursor,
+                };
+                self.scroll_to_detail_cursor();
+            }
+        }
+    }
+
+    /// Scroll the row detail tree so that the cursor node is in view.
+    fn scroll_to_detail_cursor(&mut self) {
+        let state = &mut self.state;
+        let Some(Ok(tree)) = &state.row_detail else {
+            return;
+        };
+        if state.detail_raw {
+            return;
+        }
+        let line = tree
+            .visible()
+            .iter()
+            .position(|&n| n == state.detail_cursor)
+            .unwrap_or(0);
+        let height = state.visible_data_rows.max(1);
+        if line < state.detail_scroll_offset {
+            state.detail_scroll_offset = line;
+        } else if line >= state.detail_scroll_offset + height {
+            state.detail_scroll_offset = line + 1 - height;
+        }
+    }
+
+    /// Move the row detail cursor to the next (or previous) node matching the query,
+    /// expanding the nodes around it.
+    fn detail_find_next(&mut self, forward: bool, inclusive: bool) {
+        let state = &mut self.state;
+        let Some(Ok(tree)) = state.row_detail.as_mut() else {
+            return;
+        };
+        if state.detail_query.is_empty() {
+            return;
+        }
+        let matches = tree.find(&state.detail_query);
+        let cursor = state.detail_cursor;
+        let next = if forward {
+            matches
+                .iter()
+                .find(|&&n| if inclusive { n >= cursor } else { n > cursor })
+                .or(matches.first())
+        } else {
+            matches
+                .iter()
+                .rev()
+                .find(|&&n| n < cursor)
+                .or(matches.last())
+        };
+        match next {
+            Some(&node) => {
+                tree.reveal(node);
+                state.detail_cursor = node;
+                state.detail_raw = false;
+                self.scroll_to_detail_cursor();
+            }
+            None => {
+                state.notice = Some(format!("No matches for '{}'", state.detail_query));
+            }
+        }
+    }
+
+    /// Open the previous or next row of the table in the row detail, moving the table
+    /// cursor along.
+    fn step_row_detail(&mut self, forward: bool) {
+        let Some(row) = self.state.row_detail_row else {
+            return;
+        };
+        let max_rows = self.table_rows(&self.tabs.active_tab().to_string());
+        let row = if forward {
+            (row + 1).min(max_rows.saturating_sub(1))
+        } else {
+            row.saturating_sub(1)
+        };
+        if Some(row) != self.state.row_detail_row {
+            self.state.row_detail_row = Some(row);
+            self.state.row_detail = None;
+            let column = self.state.horizontal_offset();
+            self.state.select_cell(row, column, max_rows);
+        }
+    }
+
+    /// Read the record of the row opened in the row detail, keeping the cursor on the
+    /// same node when stepping between rows of the same shape.
+    fn refresh_row_detail(&mut self) {
+        let Some(row) = self.state.row_detail_row else {
+            return;
+        };
+        if self.state.row_detail.is_some() {
+            return;
+        }
+        let frame = match self.tabs.active_tab().to_string().as_str() {
+            "SQL" => match &self.state.sql_result {
+                Some(SqlResult::Ok(data)) => &data.frame,
+                _ => return,
+            },
+            _ => {
+                &self
+                    .state
+                    .visualize_data(&self.parquet_ctx.sample_data)
+                    .frame
+            }
+        };
+        let tree = RecordTree::from_frame(frame, row);
+        let nodes = tree.as_ref().map_or(0, |tree| tree.nodes.len());
+        if self.state.detail_cursor >= nodes {
+            self.state.detail_cursor = 0;
+            self.state.detail_scroll_offset = 0;
+        }
+        self.state.row_detail = Some(tree);
+    }
+
+    /// Rows of the table in the Visualize tab or of the SQL result.
+    fn table_rows(&self, tab: &str) -> usize {
+        match (tab, &self.state.sql_result) {
+            ("SQL", Some(SqlResult::Ok(data))) => data.total_rows,
+            ("SQL", _) => 0,
+            _ => {
+                self.state
+                    .visualize_data(&self.parquet_ctx.sample_data)
+                    .total_rows
+            }
+        }
+    }
+
+    /// Whether a prompt or a pane over the tab is open, which clicks leave alone.
+    /// Whether keys go as typed to an overlay, a prompt or the SQL editor.
+    fn takes_raw_keys(&self) -> bool {
+        self.overlay_open()
+            || (self.state.sql_editing && self.tabs.active_tab().to_string() == "SQL")
+    }
+
+    fn overlay_open(&self) -> bool {
+        let state = &self.state;
+        state.help_shown
+            || state.palette.is_some()
+            || state.file_list.is_some()
+            || state.file_browser.is_some()
+            || state.goto_row_mode
+            || state.row_detail_row.is_some()
+            || state.expanded_cell.is_some()
+            || state.yank_mode
+            || state.compression_simulation.is_some()
+            || state.compression_job.is_some()
+            || state.sql_plan.is_some()
+            || state.sql_chart.is_some()
+            || state.query_library.is_some()
+            || state.group_by.is_some()
+            || state.group_by_job.is_some()
+            || state.column_chooser.is_some()
+            || state.search_mode
+            || state.find_mode
+            || state.pruning_mode
+            || state.save_query_mode
+            || state.query_parameters.is_some()
+    }
+
+    /// Whether keys are typed into a prompt or the SQL editor rather than bound to actions.
+    fn takes_text_input(&self) -> bool {
+        let state = &self.state;
+        state.search_mode
+            || state.find_mode
+            || state.pruning_mode
+            || state.save_query_mode
+            || state.query_parameters.is_some()
+            || state.column_chooser.is_some()
+            || state.yank_mode
+            || state.detail_find_mode
+            || state.palette.is_some()
+            || state.goto_row_mode
+            || state.file_browser.is_some()
+            || (state.sql_editing
+                && self.tabs.active_tab().to_string() == "SQL"
+                && state.sql_plan.is_none()
+                && state.query_library.is_none()
+                && state.sql_chart.is_none())
+    }
+
+    /// SQL editor: Enter runs the query, Alt+Enter / Shift+Enter start a new line,
+    /// ↑ / ↓ leave the first / last line to browse history, Ctrl+Space completes, Esc
+    /// switches to the results and Tab / Shift+Tab to the other tabs like anywhere else.
+    fn handle_sql_editor_key(&mut self, key_event: KeyEvent) {
+        let ctrl = key_event.modifiers.contains(KeyModifiers::CONTROL);
+        let word = ctrl || key_event.modifiers.contains(KeyModifiers::ALT);
+        // Chords bound to opening help or the palette, or to cancelling, work while
+        // typing too
+        if word {
+            match self.keymap.resolve("SQL", &mut Vec::new(), key_event) {
+                KeyInput::Action(action @ (Action::Help | Action::Palette)) => {
+                    self.handle_action(action);
+                    return;
+                }
+                KeyInput::Action(Action::Cancel) => {
+                    if !self.cancel_sql_job() {
+                        self.state.sql_editing = false;
+                    }
+                    return;
+                }
+                _ => {}
+            }
+        }
+        let editor = &mut self.state.sql_editor;
+        match key_event.code {
+            KeyCode::Esc => self.state.sql_editing = false,
+            KeyCode::Tab | KeyCode::BackTab => {
+                self.state.sql_editing = false;
+                if key_event.code == KeyCode::Tab {
+                    self.tabs.next();
+                } else {
+                    self.tabs.prev();
+                }
+                self.state.reset();
+            }
+            KeyCode::Enter
+                if key_event
+                    .modifiers
+                    .intersects(KeyModifiers::ALT | KeyModifiers::SHIFT) =>
+            {
+                editor.insert_char('\n');
+            }
+            KeyCode::Enter => self.run_sql_query(),
+            KeyCode::Char(' ') if ctrl => {
+                let mut columns = self.parquet_ctx.schema.primitive_column_names();
+                columns.extend(self.sql_session.tables());
+                let matches = editor.complete(&columns);
+                if matches.len() > 1 {
+                    self.state.notice = Some(format!("Completions: {}", matches.join("  ")));
+                }
+            }
+            KeyCode::Up => {
+                if !editor.up()
+                    && let Some(query) = self.state.sql_history.older(editor.text())
+                {
+                    editor.set_text(query);
+                }
+            }
+            KeyCode::Down => {
+                if !editor.down()
+                    && let Some(query) = self.state.sql_history.newer()
+                {
+                    editor.set_text(query);
+                }
+            }
+            KeyCode::Left if word => editor.word_left(),
+            KeyCode::Right if word => editor.word_right(),
+            KeyCode::Left => editor.left(),
+            KeyCode::Right => editor.right(),
+            KeyCode::Home => editor.home(),
+            KeyCode::End => editor.end(),
+            KeyCode::Backspace if word => editor.delete_word_back(),
+            KeyCode::Backspace => editor.backspace(),
+            KeyCode::Delete => editor.delete(),
+            KeyCode::Char('w') if ctrl => editor.delete_word_back(),
+            KeyCode::Char('e') | KeyCode::Char('E') if !ctrl && word => self.explain_sql_query(),
+            KeyCode::Char('o') if ctrl => self.open_query_library(),
+            KeyCode::Char('s') if ctrl && !editor.is_empty() => {
+                self.state.save_query_mode = true;
+                self.state.save_query_name.clear();
+            }
+            KeyCode::Char('z') if ctrl => editor.undo(),
+            KeyCode::Char('l') if ctrl => editor.clear(),
+            KeyCode::Char('a') if ctrl => editor.home(),
+            KeyCode::Char('e') if ctrl => editor.end(),
+            KeyCode::Char(c) if !ctrl => {
+                editor.insert_char(c);
+                self.state.sql_history.reset_position();
+            }
+            _ => {}
+        }
+    }
+
+    fn run_sql_query(&mut self) {
+        let query = self.state.sql_editor.text().to_string();
+        if self.state.sql_editor.is_empty() {
+            return;
+        }
+        self.state.sql_history.push(&query);
+        match parse_attach(&query) {
+            Some(Ok((name, path))) => {
+                self.state.notice = Some(match self.sql_session.attach(&name, &path) {
+                    Ok(()) => format!("Attached {path} as table {name}"),
+                    Err(e) => format!("Cannot attach {path}: {e}"),
+                });
+                self.state.sql_editor.clear();
+            }
+            Some(Err(usage)) => self.state.notice = Some(usage),
+            None if query.trim() == ".tables" => {
+                self.state.notice =
+                    Some(format!("Tables: {}", self.sql_session.tables().join(", ")));
+            }
+            None => self.start_sql_job(&query, 0),
+        }
+    }
+
+    /// Run a saved query, asking for its parameters first if it has any.
+    fn run_saved_query(&mut self, query: SavedQuery) {
+        self.state.sql_editing = true;
+        if query.parameters().is_empty() {
+            self.state.sql_editor.set_text(&query.sql);
+            self.run_sql_query();
+        } else {
+            self.state.query_parameters = Some(ParameterPrompt::new(query));
+        }
+    }
+
+    /// Open the command palette with the actions of the current tab, the other tabs and
+    /// the saved queries.
+    fn open_palette(&mut self) {
+        let tab = self.tabs.active_tab().to_string();
+        let mut items: Vec<PaletteItem> = self
+            .tabs
+            .tabs
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != self.tabs.active_tab)
+            .map(|(i, t)| PaletteItem {
+                label: format!("Go to tab: {}", t.to_string()),
+                key: String::new(),
+                command: Command::Tab(i),
+            })
+            .collect();
+        if self.on_data_table() {
+            items.push(PaletteItem {
+                label: "Go to row".to_string(),
+                key: String::new(),
+                command: Command::GoToRow,
+            });
+        }
+        items.push(PaletteItem {
+            label: "Open file".to_string(),
+            key: String::new(),
+            command: Command::OpenFile,
+        });
+        items.extend(
+            self.file_paths()
+                .into_iter()
+                .enumerate()
+                .filter(|(i, _)| *i != self.active_file)
+                .map(|(i, path)| PaletteItem {
+                    label: format!("Switch to file: {path}"),
+                    key: String::new(),
+                    command: Command::File(i),
+                }),
+        );
+        items.push(PaletteItem {
+            label: "Switch theme".to_string(),
+            key: String::new(),
+            command: Command::SwitchTheme,
+        });
+        // Moving the cursor one step is not worth a command
+        let movement = [
+            Action::Up,
+            Action::Down,
+            Action::Left,
+            Action::Right,
+            Action::Palette,
+        ];
+        items.extend(
+            Action::ALL
+                .into_iter()
+                .filter(|a| !movement.contains(a) && a.tab().is_none_or(|t| t == tab))
+                .map(|action| PaletteItem {
+                    label: action.description().to_string(),
+                    key: self.keymap.hint(action),
+                    command: Command::Action(action),
+                }),
+        );
+        if let Ok(library) = QueryLibrary::load(&self.parquet_ctx.file_path) {
+            items.extend(
+                library
+                    .queries
+                    .into_iter()
+                    .map(|(scope, query)| PaletteItem {
+                        label: format!("Run saved query: {}", query.name),
+                        key: scope.describe().to_string(),
+                        command: Command::RunQuery(query),
+                    }),
+            );
+        }
+        self.state.palette = Some(Palette::new(items));
+    }
+
+    fn run_command(&mut self, command: Command) {
+        match command {
+            Command::Action(action) => {
+                // SQL actions act on the results, not the editor
+                if self.tabs.active_tab().to_string() == "SQL" {
+                    self.state.sql_editing = false;
+                }
+                self.handle_action(action);
+            }
+            Command::Tab(idx) => {
+                self.tabs.active_tab = idx;
+                self.state.reset();
+            }
+            Command::OpenFile => self.open_file_browser(),
+            Command::File(idx) => self.switch_file(idx),
+            Command::GoToRow => {
+                self.state.goto_row_mode = true;
+                self.state.goto_row_query.clear();
+            }
+            Command::SwitchTheme => {
+                self.state.notice = Some(format!("Theme: {}", theme::next_theme()));
+            }
+            Command::RunQuery(query) => {
+                if let Some(idx) = self.tabs.tabs.iter().position(|t| t.to_string() == "SQL") {
+                    self.tabs.active_tab = idx;
+                    self.state.reset();
+                }
+                self.run_saved_query(query);
+            }
+        }
+    }
+
+    fn open_query_library(&mut self) {
+        match QueryLibrary::load(&self.parquet_ctx.file_path) {
+            Ok(library) => {
+                self.state.query_library = Some(library);
+                self.state.query_library_selected = 0;
+            }
+            Err(e) => self.state.notice = Some(e),
+        }
+    }
+
+    fn save_query(&mut self) {
+        let name = self.state.save_query_name.trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+        let query = SavedQuery {
+            name: name.clone(),
+            sql: self.state.sql_editor.text().trim().to_string(),
+            description: None,
+        };
+        let scope = self.state.save_query_scope;
+        let saved = QueryLibrary::load(&self.parquet_ctx.file_path)
+            .and_then(|mut library| library.save(scope, query));
+        self.state.notice = Some(match saved {
+            Ok(path) => format!("Saved '{name}' to {}", path.display()),
+            Err(e) => format!("Cannot save query: {e}"),
+        });
+    }
+
+    fn explain_sql_query(&mut self) {
+        if self.state.sql_editor.is_empty() {
+            return;
+        }
+        self.state.sql_plan = Some(QueryPlan::explain(
+            &self.sql_session,
+            self.state.sql_editor.text(),
+        ));
+        self.state.sql_plan_scroll = 0;
+    }
+
+    /// Start fetching result rows from `offset` on, replacing any query still running.
+    fn start_sql_job(&mut self, query: &str, offset: usize) {
+        if offset == 0 {
+            self.state.sql_column_widths.clear();
+        }
+        match SqlJob::start(&mut self.sql_session, query, offset, DEFAULT_ROW_LIMIT) {
+            Ok(job) => self.state.sql_job = Some(job),
+            Err(e) => {
+                self.state.sql_job = None;
+                self.state.sql_result = Some(SqlResult::Err(e));
+                self.state.sql_has_more = false;
+            }
+        }
+    }
+
+    /// Take the result of a finished background query: a fresh run replaces the
+    /// result, fetching more appends to it.
+    fn poll_sql_job(&mut self) {
+        let Some(batch) = self.state.sql_job.as_ref().and_then(|job| job.poll()) else {
+            return;
+        };
+        let Some(job) = self.state.sql_job.take() else {
+            return;
+        };
+        match (batch, &mut self.state.sql_result) {
+            (Ok(batch), Some(SqlResult::Ok(data))) if job.offset > 0 => {
+                data.rows.extend(batch.data.rows);
+                data.total_rows = data.rows.len();
+                if data.frame.vstack_mut(&batch.data.frame).is_err() {
+                    data.frame = DataFrame::empty();
+                }
+                self.state.sql_has_more = batch.has_more;
+                self.state.sql_elapsed = Some(batch.elapsed);
+                if let Some(frame) = self.state.sql_frame.as_mut()
+                    && frame.vstack_mut(&batch.frame).is_err()
+                {
+                    self.state.sql_frame = None;
+                }
+            }
+            (Ok(batch), _) => {
+                self.state.sql_result = Some(SqlResult::Ok(batch.data));
+                self.state.sql_result_query = job.query;
+                self.state.sql_has_more = batch.has_more;
+                self.state.sql_elapsed = Some(batch.elapsed);
+                self.state.sql_frame = Some(batch.frame);
+                self.state.reset();
+            }
+            (Err(e), _) => {
+                self.state.sql_result = Some(SqlResult::Err(e));
+                self.state.sql_has_more = false;
+                self.state.sql_elapsed = None;
+                self.state.sql_frame = None;
+            }
+        }
+        self.refresh_sql_summary();
+        // An open chart follows the new rows; a new query starts from the default columns
+        if let Some(spec) = self.state.sql_chart.as_mut() {
+            match &self.state.sql_frame {
+                Some(frame) if job.offset == 0 => *spec = ChartSpec::for_frame(frame),
+                Some(_) => {}
+                None => self.state.sql_chart = None,
+            }
+            self.state.sql_chart_data = None;
+        }
+    }
+
+    /// Recompute the chart points when the chart changed or the terminal was resized.
+    fn refresh_sql_chart(&mut self, width: u16) {
+        let (Some(spec), Some(frame)) = (&self.state.sql_chart, &self.state.sql_frame) else {
+            return;
+        };
+        // Bars need a column each plus a gap between groups
+        let points = match spec.kind {
+            ChartKind::Bar => width as usize / (spec.ys.len() + 1),
+            ChartKind::Line | ChartKind::Scatter => width as usize,
+        }
+        .max(1);
+        if self.state.sql_chart_data.is_none() || self.state.sql_chart_points != points {
+            self.state.sql_chart_data = Some(ChartData::from_frame(frame, spec, points));
+            self.state.sql_chart_points = points;
+        }
+    }
+
+    /// Recompute the column summary of the fetched rows while it is shown.
+    fn refresh_sql_summary(&mut self) {
+        self.state.sql_summary = match (&self.state.sql_frame, self.state.sql_summary_shown) {
+            (Some(frame), true) => Some(ColumnSummary::from_frame(frame)),
+            _ => None,
+        };
+    }
+
+    fn simulate_compression(&mut self) {
+        // vertical_offset is the 1-based primitive column index, 0 means nothing selected
+        let Some(leaf_idx) = self.state.vertical_offset().checked_sub(1) else {
+            return;
+        };
+        let stats = self
+            .parquet_ctx
+            .schema
+            .columns
+            .iter()
+            .filter_map(|c| match c {
+                SchemaInfo::Primitive { stats, .. } => Some(stats),
+                _ => None,
+            })
+            .nth(leaf_idx);
+        if let Some(stats) = stats.cloned() {
+            let file_path = self.parquet_ctx.file_path.clone();
+            self.state.compression_job = Some(Job::spawn(move || {
+                CompressionSimulation::run(&file_path, leaf_idx, &stats).map_err(|e| e.to_string())
+            }));
+            self.state.compression_simulation = None;
+            self.state.compression_scroll = 0;
+        }
+    }
+
+    /// Take the result of a finished background column profile.
+    fn poll_profile_job(&mut self) {
+        if let Some(result) = self.state.profile_job.as_ref().and_then(|job| job.poll()) {
+            self.state.profile_job = None;
+            self.state.column_profile = Some(result);
+        }
+    }
+
+    /// Take the result of a finished background group-by.
+    fn poll_group_by_job(&mut self) {
+        if let Some(result) = self.state.group_by_job.as_ref().and_then(|job| job.poll()) {
+            self.state.group_by_job = None;
+            self.state.group_by = Some(result);
+        }
+    }
+
+    /// Take the result of a finished background compression simulation.
+    fn poll_compression_job(&mut self) {
+        if let Some(result) = self
+            .state
+            .compression_job
+            .as_ref()
+            .and_then(|job| job.poll())
+        {
+            self.state.compression_job = None;
+            self.state.compression_simulation = Some(result);
+        }
+    }
+
+    fn handle_column_chooser_key(&mut self, key_event: KeyEvent) {
+        let names = &self.parquet_ctx.sample_data.flattened_columns;
+        let layout = &mut self.state.column_layout;
+        let Some(chooser) = self.state.column_chooser.as_mut() else {
+            return;
+        };
+        let matches = chooser.matches(layout, names);
+        let selected = matches.get(chooser.selected).map(|(column, _)| *column);
+        let moving = key_event
+            .modifiers
+            .intersects(KeyModifiers::SHIFT | KeyModifiers::ALT);
+        match key_event.code {
+            KeyCode::Esc => {
+                self.state.column_chooser = None;
+                if let Err(e) = layout.save(&self.parquet_ctx.file_path, names) {
+                    self.state.notice = Some(format!("Column layout not saved: {e}"));
+                }
+            }
+            KeyCode::Up | KeyCode::Down if moving => {
+                if let Some(column) = selected {
+                    layout.move_column(column, key_event.code == KeyCode::Down);
+                    let matches = chooser.matches(layout, names);
+                    chooser.selected = matches
+                        .iter()
+                        .position(|(c, _)| *c == column)
+                        .unwrap_or(chooser.selected);
+                }
+            }
+            KeyCode::Up => {
+                chooser.selected = chooser.selected.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                chooser.selected = (chooser.selected + 1).min(matches.len().saturating_sub(1));
+            }
+            KeyCode::Enter => {
+                if let Some(column) = selected {
+                    layout.toggle_hidden(column);
+                }
+            }
+            KeyCode::Tab => {
+                if let Some(column) = selected {
+                    layout.toggle_pinned(column);
+                    let matches = chooser.matches(layout, names);
+                    chooser.selected = matches
+                        .iter()
+                        .position(|(c, _)| *c == column)
+                        .unwrap_or(chooser.selected);
+                }
+            }
+            KeyCode::Backspace => {
+                chooser.query.pop();
+                chooser.selected = 0;
+            }
+            KeyCode::Char(c) => {
+                chooser.query.push(c);
+                chooser.selected = 0;
+            }
+            _ => {}
+        }
+
+        // Keep the cursor on a shown column and find matches on the shown columns
+        let shown = self.state.column_layout.visible_count();
+        let column = self.state.horizontal_offset().min(shown.saturating_sub(1));
+        let row = self.state.vertical_offset();
+        let max_rows = self
+            .state
+            .visualize_data(&self.parquet_ctx.sample_data)
+            .total_rows;
+        self.state.select_cell(row, column, max_rows);
+        self.refresh_find();
+    }
+
+    fn toggle_sort(&mut self) {
+        let Some(column) = self.state.current_column() else {
+            return;
+        };
+
+        let (query, sort) = self.view_target();
+        self.apply_view(query, SortSpec::toggle(sort, column), AfterView::FirstRow);
+    }
+
+    /// The filter and sort shown, or to be shown once the read in the background finishes.
+    fn view_target(&self) -> (Option<String>, Option<SortSpec>) {
+        match &self.state.view_job {
+            Some(view) => (view.query.clone(), view.sort),
+            None => (self.state.search_filter.clone(), self.state.sort),
+        }
+    }
+
+    /// Re-read the Visualize data for a search filter and sort order, both evaluated
+    /// over the whole file, in the background. An invalid filter shows a notice instead.
+    fn apply_view(&mut self, query: Option<String>, sort: Option<SortSpec>, after: AfterView) {
+        let filter = match query.as_deref().map(FilterExpr::parse).transpose() {
+            Ok(filter) => filter,
+            Err(e) => {
+                self.state.notice = Some(format!("Invalid filter: {e}"));
+                return;
+            }
+        };
+        let columns = &self.parquet_ctx.sample_data.flattened_columns;
+        let sort_by = sort.and_then(|spec| {
+            columns
+                .get(spec.column)
+                .map(|name| (name.clone(), spec.descending))
+        });
+        self.state.view_job = None;
+        if filter.is_none() && sort_by.is_none() {
+            self.state.clear_search_filter();
+            self.state.sort = None;
+            self.state.sorted_sample_data = None;
+            self.refresh_find();
+            return;
+        }
+
+        let file_path = self.parquet_ctx.file_path.clone();
+        let job = Job::spawn(move || {
+            let sort_by = sort_by.as_ref().map(|(name, desc)| (name.as_str(), *desc));
+            ParquetSampleData::read_view(&file_path, filter.as_ref(), sort_by)
+                .map_err(|e| e.to_string())
+        });
+        self.state.view_job = Some(ViewJob {
+            query,
+            sort,
+            after,
+            job,
+        });
+    }
+
+    /// Show a filter and sort once they have been read. On error the current view is
+    /// kept and a notice is shown.
+    fn poll_view_job(&mut self) {
+        let Some(result) = self
+            .state
+            .view_job
+            .as_ref()
+            .and_then(|view| view.job.poll())
+        else {
+            return;
+        };
+        let Some(view) = self.state.view_job.take() else {
+            return;
+        };
+        match result {
+            Ok((data, matches)) => {
+                if view.query.is_some() {
+                    self.state.filtered_sample_data = Some(data);
+                    self.state.search_match_count = matches;
+                    // The sorted-only view is recomputed when the filter is cleared
+                    self.state.sorted_sample_data = None;
+                } else {
+                    self.state.filtered_sample_data = None;
+                    self.state.search_match_count = None;
+                    self.state.sorted_sample_data = Some(data);
+                }
+                self.state.search_filter = view.query;
+                self.state.sort = view.sort;
+                self.refresh_find();
+                match view.after {
+                    AfterView::FirstCell => self.state.reset(),
+                    AfterView::FirstRow => self.state.reset_rows(),
+                    AfterView::Refresh => self.clamp_visualize_cursor(),
+                }
+            }
+            Err(e) if view.after == AfterView::Refresh => {
+                self.drop_view();
+                self.state.notice = Some(format!("Filter and sort cleared: {e}"));
+            }
+            Err(e) => {
+                let columns = &self.parquet_ctx.sample_data.flattened_columns;
+                let sort_column = view.sort.and_then(|spec| columns.get(spec.column));
+                self.state.notice = Some(match (&view.query, sort_column) {
+                    (None, Some(column)) => format!("Cannot sort by {column}: {e}"),
+                    _ => format!("Filter failed: {e}"),
+                });
+            }
+        }
+    }
+
+    /// Show the sample rows as read, without a filter or sort.
+    fn drop_view(&mut self) {
+        self.state.clear_search_filter();
+        self.state.sort = None;
+        self.state.sorted_sample_data = None;
+        self.refresh_find();
+    }
+
+    /// Keep the Visualize cursor on a row and a shown column after the rows changed.
+    fn clamp_visualize_cursor(&mut self) {
+        if self.tabs.active_tab().to_string() != "Visualize" {
+            return;
+        }
+        let max_rows = self
+            .state
+            .visualize_data(&self.parquet_ctx.sample_data)
+            .total_rows;
+        let shown = self.state.column_layout.visible_count();
+        let row = self.state.vertical_offset().min(max_rows.saturating_sub(1));
+        let column = self.state.horizontal_offset().min(shown.saturating_sub(1));
+        self.state.select_cell(row, column, max_rows);
+        if self.state.row_detail_row.is_some_and(|row| row >= max_rows) {
+            self.state.row_detail_row = None;
+        }
+    }
+
+    fn find_next(&mut self, inclusive: bool) {
+        let cursor = (self.state.vertical_offset(), self.state.horizontal_offset());
+        let target = self
+            .state
+            .find
+            .as_mut()
+            .and_then(|find| find.next_from(cursor, inclusive));
+        self.jump_to_match(target);
+    }
+
+    fn find_prev(&mut self) {
+        let cursor = (self.state.vertical_offset(), self.state.horizontal_offset());
+        let target = self
+            .state
+            .find
+            .as_mut()
+            .and_then(|find| find.prev_from(cursor));
+        self.jump_to_match(target);
+    }
+
+    fn jump_to_match(&mut self, target: Option<(usize, usize)>) {
+        match target {
+            Some((row, column)) => {
+                let max_rows = self
+                    .state
+                    .visualize_data(&self.parquet_ctx.sample_data)
+                    .total_rows;
+                self.state.select_cell(row, column, max_rows);
+            }
+            None => {
+                let query = self.state.find.as_ref().map(|f| f.query.as_str());
+                self.state.notice = Some(format!("No matches for '{}'", query.unwrap_or("")));
+            }
+        }
+    }
+
+    fn clear_filter(&mut self) {
+        let (_, sort) = self.view_target();
+        let sorted = sort == self.state.sort && self.state.sorted_sample_data.is_some();
+        self.state.view_job = None;
+        self.state.clear_search_filter();
+        if sort.is_some() && !sorted {
+            self.apply_view(None, sort, AfterView::Refresh);
+        }
+        self.refresh_find();
+    }
+
+    /// Re-run the active find against the current Visualize data after it changed.
+    fn refresh_find(&mut self) {
+        if let Some(query) = self.state.find.as_ref().map(|f| f.query.clone()) {
+            let data = self.state.visualize_data(&self.parquet_ctx.sample_data);
+            let (columns, _) = self.state.column_layout.visible();
+            self.state.find = Some(FindMatches::search_columns(data, &query, &columns));
+        }
+    }
+
+    /// Show the file read again by watch mode once it changed on disk.
+    fn poll_file_watch(&mut self) {
+        let Some(result) = self.file_watch.as_mut().and_then(FileWatch::poll) else {
+            return;
+        };
+        match result {
+            Ok(parquet_ctx) => self.reload_file(parquet_ctx),
+            Err(e) => self.state.notice = Some(format!("Reload failed: {e}")),
+        }
+    }
+
+    /// Swap in a new read of the file shown, keeping the tab, cursor, filter and sort
+    /// where they still apply to it.
+    fn reload_file(&mut self, parquet_ctx: ParquetCtx) {
+        let old = std::mem::replace(&mut self.parquet_ctx, parquet_ctx);
+        let ctx = &self.parquet_ctx;
+        let same_columns = old.sample_data.flattened_columns == ctx.sample_data.flattened_columns;
+        let same_shape = same_columns
+            && old.schema.column_size() == ctx.schema.column_size()
+            && old.row_groups.num_row_groups() == ctx.row_groups.num_row_groups();
+
+        let active_tab = self.tabs.active_tab;
+        self.tabs = TabManager::new(
+            ctx.schema.column_size(),
+            ctx.row_groups.num_row_groups(),
+            ctx.sample_data.total_rows,
+        );
+        self.tabs.active_tab = active_tab;
+        if let Err(e) = self.sql_session.attach("parquet", &ctx.file_path) {
+            self.state.sql_result = Some(SqlResult::Err(e));
+        }
+
+        // Column positions mean other columns when the schema changed
+        if !same_columns {
+            self.state.column_layout =
+                ColumnLayout::load(&ctx.file_path, &ctx.sample_data.flattened_columns);
+            self.state.column_chooser = None;
+            self.state.visualize_column_widths.clear();
+            self.state.sort = None;
+            self.state.selection_anchor = None;
+        }
+        // Views computed from the previous read
+        self.state.storage_path.clear();
+        self.state.storage_level_len = ctx.storage.root().children.len();
+        self.state.column_profile = None;
+        self.state.profile_job = None;
+        self.state.compression_simulation = None;
+        self.state.compression_job = None;
+        self.state.group_by = None;
+        self.state.group_by_job = None;
+        self.state.pruning = None;
+        self.state.row_detail = None;
+        self.state.filtered_sample_data = None;
+        self.state.sorted_sample_data = None;
+        let (query, sort) = self.view_target();
+        let sort = sort.filter(|_| same_columns);
+        self.apply_view(query, sort, AfterView::Refresh);
+        self.refresh_find();
+
+        match self.tabs.active_tab().to_string().as_str() {
+            "Visualize" => self.clamp_visualize_cursor(),
+            "SQL" => {}
+            _ if !same_shape => self.state.reset(),
+            _ => {}
+        }
+
+        let ctx = &self.parquet_ctx;
+        self.state.notice = Some(format!(
+            "Reloaded at {}: {} rows ({}), {} row groups ({})",
+            chrono::Local::now().format("%H:%M:%S"),
+            commas(ctx.metadata.num_rows as u64),
+            delta(old.metadata.num_rows, ctx.metadata.num_rows),
+            ctx.row_groups.num_row_groups(),
+            delta(
+                old.row_groups.num_row_groups(),
+                ctx.row_groups.num_row_groups()
+            ),
+        ));
+    }
+
+    fn storage_drill_down(&mut self) {
+        let storage = &self.parquet_ctx.storage;
+        let current = self.state.storage_path.last().copied().unwrap_or(0);
+        let Some(&selected) = storage
+            .ranked_children(current)
+            .get(self.state.vertical_offset())
+        else {
+            return;
+        };
+        if storage.nodes[selected].is_group {
+            self.state.storage_path.push(selected);
+            self.state.storage_level_len = storage.nodes[selected].children.len();
+            self.state.reset();
+        }
+    }
+
+    fn storage_drill_up(&mut self) {
+        let storage = &self.parquet_ctx.storage;
+        let Some(left) = self.state.storage_path.pop() else {
+            return;
+        };
+        let parent = self.state.storage_path.last().copied().unwrap_or(0);
+        self.state.storage_level_len = storage.nodes[parent].children.len();
+        // Re-select the group we came from
+        self.state.reset();
+        let position = storage
+            .ranked_children(parent)
+            .iter()
+            .position(|idx| *idx == left)
+            .unwrap_or(0);
+        for _ in 0..position {
+            self.state.down();
+        }
+    }
+
     fn exit(&mut self) {
         self.exit = true;
     }
 }
+
+/// Change from `old` to `new`, signed, e.g. "+1,024" or "-3".
+fn delta(old: usize, new: usize) -> String {
+    if new >= old {
+        format!("+{}", commas((new - old) as u64))
+    } else {
+        format!("-{}", commas((old - new) as u64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::{test_path, write_test_parquet};
+    use crate::keymap::{Preset, parse_sequence};
+    use polars::df;
+
+    fn app(name: &str, keymap: KeyMap) -> App {
+        let mut df = df!("id" => [3i64, 1, 2], "name" => ["c", "a", "b"]).unwrap();
+        let path = write_test_parquet(name, &mut df);
+        App::new(ParquetCtx::from_file(&path).unwrap()).with_keymap(keymap)
+    }
+
+    fn press(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
+        app.handle_key_event(KeyEvent::new(code, modifiers));
+    }
+
+    fn finish_view(app: &mut App) {
+        while app.state.view_job.is_some() {
+            app.poll_view_job();
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn test_rebound_key_replaces_the_built_in_key() {
+        let mut keymap = KeyMap::default();
+        keymap.bind(Action::Sort, vec![parse_sequence("o").unwrap()]);
+        let mut app = app("app-rebound", keymap);
+
+        press(&mut app, KeyCode::Char('s'), KeyModifiers::NONE);
+        assert!(app.state.view_job.is_none());
+
+        press(&mut app, KeyCode::Char('o'), KeyModifiers::NONE);
+        finish_view(&mut app);
+        let sorted = app.state.sorted_sample_data.as_ref().unwrap();
+        assert_eq!(
+            vec!["1", "2", "3"],
+            sorted.rows.iter().map(|r| &r[0]).collect::<Vec<_>>()
+        );
+        assert_eq!(Some(0), app.state.sort.map(|s| s.column));
+    }
+
+    #[test]
+    fn test_key_sequences() {
+        let mut app = app("app-sequences", KeyMap::preset(Preset::Vim));
+
+        press(&mut app, KeyCode::Char('G'), KeyModifiers::SHIFT);
+        assert_eq!(2, app.state.vertical_offset());
+        press(&mut app, KeyCode::Char('g'), KeyModifiers::NONE);
+        assert_eq!(2, app.state.vertical_offset());
+        press(&mut app, KeyCode::Char('g'), KeyModifiers::NONE);
+        assert_eq!(0, app.state.vertical_offset());
+    }
+
+    #[test]
+    fn test_overlays_take_keys_as_typed() {
+        let mut app = app("app-overlays", KeyMap::preset(Preset::Vim));
+
+        // "q" quits in the vim keys, but closes the help
+        press(&mut app, KeyCode::Char('?'), KeyModifiers::NONE);
+        assert!(app.state.help_shown);
+        press(&mut app, KeyCode::Char('q'), KeyModifiers::NONE);
+        assert!(!app.state.help_shown);
+        assert!(!app.exit);
+
+        // Ctrl+D pages down in the vim keys, so it does not delete like "d"
+        let dir = test_path("app-library");
+        std::fs::remove_dir_all(&dir).ok();
+        let mut library =
+            QueryLibrary::from_files(Some(dir.join("parqeye-queries.json")), None).unwrap();
+        let query = SavedQuery {
+            name: "all".to_string(),
+            sql: "SELECT * FROM parquet".to_string(),
+            description: None,
+        };
+        library.save(LibraryScope::Project, query).unwrap();
+        app.state.query_library = Some(library);
+        press(&mut app, KeyCode::Char('d'), KeyModifiers::CONTROL);
+        assert_eq!(1, app.state.query_library.as_ref().unwrap().queries.len());
+        press(&mut app, KeyCode::Char('d'), KeyModifiers::NONE);
+        assert!(app.state.query_library.as_ref().unwrap().queries.is_empty());
+
+        press(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        press(&mut app, KeyCode::Char('q'), KeyModifiers::NONE);
+        assert!(app.exit);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_quit_keys_in_overlays() {
+        // The emacs quit is a sequence, its first key does not quit
+        let mut emacs = app("app-quit-emacs", KeyMap::preset(Preset::Emacs));
+        press(&mut emacs, KeyCode::Char('?'), KeyModifiers::NONE);
+        press(&mut emacs, KeyCode::Char('x'), KeyModifiers::CONTROL);
+        assert!(!emacs.exit);
+        assert!(emacs.state.help_shown);
+        press(&mut emacs, KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert!(emacs.exit);
+
+        // A rebound quit no longer answers to Ctrl+X, also in the SQL editor
+        let mut keymap = KeyMap::default();
+        keymap.bind(Action::Quit, vec![parse_sequence("ctrl+q").unwrap()]);
+        let mut rebound = app("app-quit-rebound", keymap);
+        while rebound.tabs.active_tab().to_string() != "SQL" {
+            rebound.tabs.next();
+        }
+        rebound.state.sql_editing = true;
+        press(&mut rebound, KeyCode::Char('x'), KeyModifiers::CONTROL);
+        assert!(!rebound.exit);
+        press(&mut rebound, KeyCode::Char('q'), KeyModifiers::CONTROL);
+        assert!(rebound.exit);
+    }
+
+    #[test]
+    fn test_group_by_runs_in_the_background() {
+        let mut app = app("app-group-by", KeyMap::default());
+        while app.tabs.active_tab().to_string() != "Visualize" {
+            app.tabs.next();
+        }
+
+        // Esc cancels the count before it finishes
+        press(&mut app, KeyCode::Char('g'), KeyModifiers::NONE);
+        assert!(app.state.job_running());
+        press(&mut app, KeyCode::Esc, KeyModifiers::NONE);
+        assert!(app.state.group_by_job.is_none());
+
+        press(&mut app, KeyCode::Char('g'), KeyModifiers::NONE);
+        while app.state.group_by_job.is_some() {
+            app.poll_group_by_job();
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        let group_by = app.state.group_by.as_ref().unwrap().as_ref().unwrap();
+        assert_eq!(3, group_by.groups.len());
+    }
+
+    #[test]
+    fn test_click_selects_the_cell_drawn_there() {
+        let mut app = app("app-click", KeyMap::default());
+        let mut terminal =
+            ratatui::Terminal::new(ratatui::backend::TestBackend::new(80, 20)).unwrap();
+        let view = AppRenderView::from_app(&app);
+        terminal
+            .draw(|frame| crate::ui::render_app(&view, frame))
+            .unwrap();
+
+        let table = app.hits.borrow().table.clone().unwrap();
+        let (_, y, _) = table.rows[2];
+        let x = table.columns[1].x;
+        app.handle_mouse_event(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: x,
+            row: y,
+            modifiers: KeyModifiers::NONE,
+        });
+        assert_eq!(2, app.state.vertical_offset());
+        assert_eq!(1, app.state.horizontal_offset());
+    }
+}
@@ -0,0 +1,114 @@
+//! Copying table values to the system clipboard with the OSC 52 terminal escape, which
+//! the terminal handles on the machine it runs on, so it also works over SSH.
+
+use std::io::{self, Write};
+
+/// Put `text` on the clipboard of the terminal.
+pub fn copy(text: &str) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    stdout.write_all(osc52(text, std::env::var_os("TMUX").is_some()).as_bytes())?;
+    stdout.flush()
+}
+
+/// The escape setting the clipboard to `text`; inside tmux it is wrapped to be passed
+/// through to the outer terminal.
+fn osc52(text: &str, tmux: bool) -> String {
+    let sequence = format!("\x1b]52;c;{}\x07", base64(text.as_bytes()));
+    if tmux {
+        format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+    } else {
+        sequence
+    }
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// A cell as copied: strings without the quotes they are shown with.
+pub fn cell_text(cell: &str) -> &str {
+    cell.strip_prefix('"')
+        .and_then(|c| c.strip_suffix('"'))
+        .unwrap_or(cell)
+}
+
+/// A cell as a JSON value: nulls, booleans and numbers typed, anything else a string.
+fn cell_json(cell: &str) -> serde_json::Value {
+    match cell {
+        "NULL" => serde_json::Value::Null,
+        "true" => serde_json::Value::Bool(true),
+        "false" => serde_json::Value::Bool(false),
+        _ if cell.starts_with('"') => serde_json::Value::String(cell_text(cell).to_string()),
+        _ => serde_json::from_str::<serde_json::Number>(cell)
+            .map(serde_json::Value::Number)
+            .unwrap_or_else(|_| serde_json::Value::String(cell.to_string())),
+    }
+}
+
+/// A row as a JSON object of column names to values, in column order.
+pub fn row_json(names: &[&str], cells: &[&str]) -> String {
+    let fields: Vec<String> = names
+        .iter()
+        .zip(cells)
+        .map(|(name, cell)| format!("{}: {}", serde_json::json!(name), cell_json(cell)))
+        .collect();
+    format!("{{{}}}", fields.join(", "))
+}
+
+/// Rows of cells as tab-separated lines; tabs and newlines in values become spaces.
+pub fn tsv(rows: &[Vec<&str>]) -> String {
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .map(|cell| cell_text(cell).replace(['\t', '\n'], " "))
+                .collect::<Vec<_>>()
+                .join("\t")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_osc52() {
+        assert_eq!("", base64(b""));
+        assert_eq!("Zg==", base64(b"f"));
+        assert_eq!("Zm8=", base64(b"fo"));
+        assert_eq!("Zm9vYmFy", base64(b"foobar"));
+        assert_eq!("\x1b]52;c;aWQ=\x07", osc52("id", false));
+        assert_eq!("\x1bPtmux;\x1b\x1b]52;c;aWQ=\x07\x1b\\", osc52("id", true));
+    }
+
+    #[test]
+    fn test_row_formats() {
+        assert_eq!(
+            r#"{"id": 42, "name": "a \"b\"", "ok": true, "note": null, "ts": "2024-01-01 10:00:00"}"#,
+            row_json(
+                &["id", "name", "ok", "note", "ts"],
+                &["42", r#""a "b"""#, "true", "NULL", "2024-01-01 10:00:00"]
+            )
+        );
+        assert_eq!(
+            "1\ta b\n2\tc",
+            tsv(&[vec!["1", "\"a\tb\""], vec!["2", "c"]])
+        );
+    }
+}
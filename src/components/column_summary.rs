@@ -0,0 +1,129 @@
+use crate::file::summary::ColumnSummary;
+use crate::file::utils::{commas, format_number, truncate_str};
+use crate::theme::theme;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    symbols::border,
+    text::Span,
+    widgets::{Block, Widget},
+};
+
+const LABELS: [&str; 6] = ["type", "nulls", "min", "max", "mean", "distinct"];
+const LABEL_WIDTH: u16 = 10;
+const NUM_SPACES_BETWEEN_COLUMNS: u16 = 2;
+
+/// Summary strip under a result table: one column per result column, one line per statistic.
+pub struct ColumnSummaryStrip<'a> {
+    pub columns: &'a [ColumnSummary],
+    pub rows: usize,
+    pub horizontal_scroll: usize,
+    pub border_style: border::Set,
+}
+
+impl<'a> ColumnSummaryStrip<'a> {
+    pub fn new(columns: &'a [ColumnSummary], rows: usize) -> Self {
+        Self {
+            columns,
+            rows,
+            horizontal_scroll: 0,
+            border_style: border::ROUNDED,
+        }
+    }
+
+    pub fn with_horizontal_scroll(mut self, offset: usize) -> Self {
+        self.horizontal_scroll = offset;
+        self
+    }
+
+    /// Height needed for the header line, one line per statistic and the border.
+    pub fn height() -> u16 {
+        LABELS.len() as u16 + 3
+    }
+
+    fn values(&self, column: &ColumnSummary) -> [String; 6] {
+        let nulls = if column.null_count > 0 && self.rows > 0 {
+            format!(
+                "{} ({:.1}%)",
+                commas(column.null_count as u64),
+                column.null_count as f64 * 100.0 / self.rows as f64
+            )
+        } else {
+            commas(column.null_count as u64)
+        };
+        let or_dash = |v: Option<String>| v.unwrap_or_else(|| "-".to_string());
+        [
+            column.dtype.clone(),
+            nulls,
+            or_dash(column.min.clone()),
+            or_dash(column.max.clone()),
+            or_dash(column.mean.map(format_number)),
+            or_dash(column.distinct.map(|d| commas(d as u64))),
+        ]
+    }
+}
+
+impl<'a> Widget for ColumnSummaryStrip<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered()
+            .border_set(self.border_style)
+            .border_style(Style::default().fg(theme().border))
+            .title(Span::styled(
+                format!(
+                    " Column summary of {} fetched rows (s: hide) ",
+                    commas(self.rows as u64)
+                ),
+                Style::default().fg(theme().title),
+            ));
+        let inner = block.inner(area);
+        block.render(area, buf);
+        if inner.width <= LABEL_WIDTH || inner.height == 0 {
+            return;
+        }
+
+        let label_style = Style::default().fg(theme().muted);
+        for (i, label) in LABELS.iter().enumerate() {
+            let y = inner.y + 1 + i as u16;
+            if y >= inner.bottom() {
+                break;
+            }
+            buf.set_span(inner.x, y, &Span::styled(*label, label_style), LABEL_WIDTH);
+        }
+
+        let mut x = inner.x + LABEL_WIDTH;
+        for column in self.columns.iter().skip(self.horizontal_scroll) {
+            if x >= inner.right() {
+                break;
+            }
+            let values = self.values(column);
+            let width = values
+                .iter()
+                .map(|v| v.chars().count())
+                .chain([column.name.chars().count()])
+                .max()
+                .unwrap_or(0)
+                .clamp(8, 25) as u16;
+            let width = width.min(inner.right() - x);
+
+            let header_style = Style::default()
+                .fg(theme().header)
+                .add_modifier(Modifier::BOLD);
+            buf.set_span(
+                x,
+                inner.y,
+                &Span::styled(truncate_str(&column.name, width as usize), header_style),
+                width,
+            );
+            for (i, value) in values.iter().enumerate() {
+                let y = inner.y + 1 + i as u16;
+                if y >= inner.bottom() {
+                    break;
+                }
+                let span = Span::raw(truncate_str(value, width as usize));
+                buf.set_span(x, y, &span, width);
+            }
+            x = x.saturating_add(width + NUM_SPACES_BETWEEN_COLUMNS);
+        }
+    }
+}
@@ -1,19 +1,92 @@
-use crate::file::sample_data::ParquetSampleData;
+use crate::file::sample_data::{FindMatches, ParquetSampleData, SortSpec};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
     prelude::{Color, Position},
-    style::Modifier,
+    style::{Modifier, Style},
     symbols::{border, line},
-    text::Span,
+    text::{Line, Span},
     widgets::Widget,
 };
-use std::cmp::min;
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
 
 use crate::file::Renderable;
+use crate::file::utils::wrap_text;
+use crate::theme::theme;
 
 const NUM_SPACES_BETWEEN_COLUMNS: u16 = 2;
 const NUM_SPACES_AFTER_LINE_NUMBER: u16 = 2;
+/// Narrowest a column can be made by resizing it.
+pub const MIN_COLUMN_WIDTH: u16 = 3;
+/// Widest a column is made to fit its values, unless resized.
+const MAX_FITTED_COLUMN_WIDTH: u16 = 40;
+
+/// Where a [`DataTable`] draws its header, rows and columns, to map mouse positions
+/// back to cells.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TableLayout {
+    pub area: Rect,
+    /// Data row, screen line and number of lines of each row drawn
+    pub rows: Vec<(usize, u16, u16)>,
+    /// Columns drawn, pinned ones first
+    pub columns: Vec<LayoutColumn>,
+    /// Position of the first column drawn after the pinned ones, the horizontal scroll
+    pub first_scrolled: usize,
+    /// Number of pinned columns at the start of `columns`
+    pub pinned: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayoutColumn {
+    /// Position among the displayed columns, as selected by the current column
+    pub position: usize,
+    /// Index into the data columns
+    pub column: usize,
+    pub x: u16,
+    /// Width including the spacing after the column
+    pub width: u16,
+}
+
+impl LayoutColumn {
+    /// Width without the spacing after the column.
+    pub fn content_width(&self) -> u16 {
+        self.width.saturating_sub(NUM_SPACES_BETWEEN_COLUMNS)
+    }
+}
+
+impl TableLayout {
+    /// Data row at screen line `y`.
+    pub fn row_at(&self, y: u16) -> Option<usize> {
+        self.rows
+            .iter()
+            .find(|(_, top, height)| (*top..top + height).contains(&y))
+            .map(|(row, _, _)| *row)
+    }
+
+    /// Position of the column at screen column `x`.
+    pub fn column_at(&self, x: u16) -> Option<usize> {
+        self.columns
+            .iter()
+            .find(|c| (c.x..c.x + c.width).contains(&x))
+            .map(|c| c.position)
+    }
+
+    /// Column whose right edge, the spacing before the next column in the header, is at
+    /// `(x, y)`; returns the data column and its width without the spacing.
+    pub fn separator_at(&self, x: u16, y: u16) -> Option<(usize, u16)> {
+        if y != self.area.y {
+            return None;
+        }
+        self.columns
+            .iter()
+            .find(|c| {
+                let end = c.x + c.width;
+                (end - NUM_SPACES_BETWEEN_COLUMNS.min(c.width)..end).contains(&x)
+            })
+            .map(|c| (c.column, c.content_width()))
+    }
+}
 
 pub struct DataTable<'a> {
     pub data: &'a ParquetSampleData,
@@ -25,6 +98,19 @@ pub struct DataTable<'a> {
     pub selected_row: Option<usize>,
     pub selected_color: Color,
     pub border_color: Color,
+    pub sort: Option<SortSpec>,
+    pub current_column: Option<usize>,
+    pub find: Option<&'a FindMatches>,
+    /// Widths set by resizing columns, by column index
+    pub column_widths: Option<&'a HashMap<usize, u16>>,
+    /// Data columns in display order, when not all columns in file order
+    pub column_order: Option<&'a [usize]>,
+    /// Number of columns at the start of the order kept at the left while scrolling
+    pub pinned_columns: usize,
+    /// Wrap the cells of the selected row over several lines instead of truncating them
+    pub wrap_selected_row: bool,
+    /// Marked cells: rows and column positions, both inclusive
+    pub selection: Option<(RangeInclusive<usize>, RangeInclusive<usize>)>,
 }
 
 impl<'a> DataTable<'a> {
@@ -32,13 +118,21 @@ impl<'a> DataTable<'a> {
         Self {
             data,
             title: "Data Preview (up to 100 rows)".to_string(),
-            title_color: Color::Cyan,
+            title_color: theme().title,
             border_style: border::ROUNDED,
             horizontal_scroll: 0,
             vertical_scroll: 0,
             selected_row: None,
-            selected_color: Color::Rgb(60, 60, 60),
-            border_color: Color::DarkGray,
+            selected_color: theme().cursor_bg,
+            border_color: theme().border,
+            sort: None,
+            current_column: None,
+            find: None,
+            column_widths: None,
+            column_order: None,
+            pinned_columns: 0,
+            wrap_selected_row: false,
+            selection: None,
         }
     }
 
@@ -73,6 +167,47 @@ impl<'a> DataTable<'a> {
         self
     }
 
+    pub fn with_sort(mut self, sort: Option<SortSpec>) -> Self {
+        self.sort = sort;
+        self
+    }
+
+    pub fn with_current_column(mut self, column: Option<usize>) -> Self {
+        self.current_column = column;
+        self
+    }
+
+    pub fn with_find(mut self, find: Option<&'a FindMatches>) -> Self {
+        self.find = find;
+        self
+    }
+
+    pub fn with_column_widths(mut self, widths: &'a HashMap<usize, u16>) -> Self {
+        self.column_widths = Some(widths);
+        self
+    }
+
+    /// Show only `order`, data column indices in display order, with the first
+    /// `pinned` of them frozen at the left.
+    pub fn with_column_order(mut self, order: &'a [usize], pinned: usize) -> Self {
+        self.column_order = Some(order);
+        self.pinned_columns = pinned;
+        self
+    }
+
+    pub fn with_selection(
+        mut self,
+        selection: Option<(RangeInclusive<usize>, RangeInclusive<usize>)>,
+    ) -> Self {
+        self.selection = selection;
+        self
+    }
+
+    pub fn with_wrapped_row(mut self, wrap: bool) -> Self {
+        self.wrap_selected_row = wrap;
+        self
+    }
+
     pub fn scroll_left(&mut self) {
         if self.horizontal_scroll > 0 {
             self.horizontal_scroll -= 1;
@@ -96,28 +231,32 @@ impl<'a> DataTable<'a> {
         self.data.total_columns.saturating_sub(max_visible_columns)
     }
 
-    fn calculate_column_widths(
-        &self,
-        headers: &[String],
-        visible_rows: &[Vec<String>],
-    ) -> Vec<u16> {
-        let mut widths = Vec::new();
-
-        for (col_idx, header) in headers.iter().enumerate() {
-            let mut max_width = header.len();
-
-            // Check content width for this column
-            for row in visible_rows {
-                if let Some(cell) = row.get(col_idx) {
-                    max_width = max_width.max(cell.len());
-                }
-            }
-
-            // Use minimum width of 8 and maximum of 25 for readability, add spacing
-            widths.push((min(max_width.max(8), 25) as u16) + NUM_SPACES_BETWEEN_COLUMNS);
+    /// Width of a data column including the spacing after it: fitted to its header and
+    /// the values of `rows`, at most `max`, unless it was resized.
+    fn column_width(&self, column: usize, rows: &[Vec<String>], max: u16) -> u16 {
+        if let Some(width) = self.column_widths.and_then(|widths| widths.get(&column)) {
+            return width + NUM_SPACES_BETWEEN_COLUMNS;
         }
+        let header = self
+            .data
+            .flattened_columns
+            .get(column)
+            .map_or(0, |h| h.chars().count());
+        let fitted = rows
+            .iter()
+            .filter_map(|row| row.get(column))
+            .map(|cell| cell.chars().count())
+            .fold(header, usize::max);
+
+        (fitted.min(max as usize) as u16).max(MIN_COLUMN_WIDTH + 1) + NUM_SPACES_BETWEEN_COLUMNS
+    }
 
-        widths
+    /// Data columns in display order.
+    fn view_columns(&self) -> Vec<usize> {
+        match self.column_order {
+            Some(order) => order.to_vec(),
+            None => (0..self.data.total_columns).collect(),
+        }
     }
 
     fn render_header_separator(&self, buf: &mut Buffer, area: Rect, x_row_separator: u16, y: u16) {
@@ -137,49 +276,36 @@ impl<'a> DataTable<'a> {
         }
     }
 
-    fn render_row_numbers(&self, buf: &mut Buffer, area: Rect, rows: &[Vec<String>]) {
-        let mut y = area.y;
+    fn render_row_numbers(&self, buf: &mut Buffer, layout: &TableLayout) {
+        for &(row, y, _) in &layout.rows {
+            let is_selected = self.selected_row == Some(row);
 
-        for (row_idx, _) in rows.iter().enumerate() {
-            let actual_row_num = row_idx + self.vertical_scroll + 1;
-            let is_selected = self
-                .selected_row
-                .is_some_and(|selected| row_idx + self.vertical_scroll == selected);
-
-            let row_num_formatted = format!("{}", actual_row_num);
+            let row_num_formatted = format!("{}", row + 1);
             let mut style: ratatui::prelude::Style =
-                ratatui::style::Style::default().fg(Color::DarkGray);
+                ratatui::style::Style::default().fg(theme().muted);
             if is_selected {
                 style = style
                     .add_modifier(Modifier::BOLD)
                     .add_modifier(Modifier::UNDERLINED);
             }
             let span = Span::styled(row_num_formatted, style);
-            buf.set_span(0, y, &span, area.width);
-            y += 1;
-            if y >= area.bottom() {
-                break;
-            }
+            buf.set_span(0, y, &span, layout.area.width);
         }
     }
 
-    fn render_header(
-        &self,
-        buf: &mut Buffer,
-        x_start: u16,
-        y: u16,
-        headers: &[String],
-        column_widths: &[u16],
-        max_width: u16,
-    ) {
-        let mut x_offset = x_start;
-
-        for (header, &width) in headers.iter().zip(column_widths) {
-            if x_offset >= max_width {
-                break;
-            }
-
-            let effective_width = width.saturating_sub(NUM_SPACES_BETWEEN_COLUMNS);
+    fn render_header(&self, buf: &mut Buffer, y: u16, columns: &[LayoutColumn], right: u16) {
+        for c in columns {
+            let header = &self.data.flattened_columns[c.column];
+
+            // Sorted column: reserve room for the direction indicator
+            let sort_indicator = self
+                .sort
+                .filter(|s| s.column == c.column)
+                .map(|s| s.indicator());
+            let effective_width = c
+                .width
+                .saturating_sub(NUM_SPACES_BETWEEN_COLUMNS)
+                .saturating_sub(sort_indicator.map_or(0, |_| 2));
             let truncated = if header.len() > effective_width as usize {
                 format!(
                     "{}...",
@@ -189,60 +315,111 @@ impl<'a> DataTable<'a> {
                 header.clone()
             };
 
-            let style = ratatui::style::Style::default()
-                .fg(Color::Yellow)
+            let mut style = ratatui::style::Style::default()
+                .fg(theme().header)
                 .add_modifier(Modifier::BOLD);
-            let span = Span::styled(truncated, style);
+            if self.current_column == Some(c.position) {
+                style = style.add_modifier(Modifier::UNDERLINED);
+            }
+            let text = match sort_indicator {
+                Some(indicator) => format!("{truncated} {indicator}"),
+                None => truncated,
+            };
+            let span = Span::styled(text, style);
 
-            buf.set_span(x_offset, y, &span, width);
-            x_offset += width;
+            buf.set_span(c.x, y, &span, c.width.min(right - c.x));
         }
     }
 
-    #[allow(clippy::too_many_arguments)]
+    /// Draw a row over `lines` screen lines, wrapping its cells when there are several.
     fn render_data_row(
         &self,
         buf: &mut Buffer,
-        x_start: u16,
-        y: u16,
+        (row_idx, y, lines): (usize, u16, u16),
         row_data: &[String],
-        column_widths: &[u16],
-        is_selected: bool,
-        max_width: u16,
+        columns: &[LayoutColumn],
+        right: u16,
     ) {
-        let mut x_offset = x_start;
-
-        let style = if is_selected {
-            ratatui::style::Style::default()
+        let is_cursor_row = self.selected_row == Some(row_idx);
+        let row_style = if is_cursor_row {
+            theme()
+                .cursor()
                 .bg(self.selected_color)
-                .fg(Color::White)
+                .fg(theme().text)
                 .add_modifier(Modifier::BOLD)
         } else {
             ratatui::style::Style::default()
         };
 
-        for (cell_data, &width) in row_data.iter().zip(column_widths) {
-            if x_offset >= max_width {
-                break;
-            }
+        for c in columns {
+            let Some(cell_data) = row_data.get(c.column) else {
+                continue;
+            };
+            // The cell cursor, and marked cells, stand out from the cursor row
+            let is_cursor = is_cursor_row && self.current_column == Some(c.position);
+            let is_marked = self
+                .selection
+                .as_ref()
+                .is_some_and(|(rows, cols)| rows.contains(&row_idx) && cols.contains(&c.position));
+            let style = match (is_cursor, is_marked) {
+                (true, _) => theme().selected().add_modifier(Modifier::UNDERLINED),
+                (false, true) => theme().selected(),
+                _ => row_style,
+            };
+            let width = c.width.min(right - c.x);
 
-            let effective_width = width.saturating_sub(NUM_SPACES_BETWEEN_COLUMNS);
-            let truncated = if cell_data.chars().count() > effective_width as usize {
+            let effective_width = c.width.saturating_sub(NUM_SPACES_BETWEEN_COLUMNS);
+            let cell_lines = if lines > 1 {
+                wrap_text(cell_data, effective_width as usize)
+            } else if cell_data.chars().count() > effective_width as usize {
                 let truncated_chars: String = cell_data
                     .chars()
                     .take(effective_width.saturating_sub(1) as usize)
                     .collect();
-                format!("{}…", truncated_chars)
+                vec![format!("{}…", truncated_chars)]
             } else {
-                cell_data.clone()
+                vec![cell_data.clone()]
             };
 
-            // Pad with spaces to fill the column width
-            let padded = format!("{:width$}", truncated, width = width as usize);
-            let span = Span::styled(padded, style);
+            let is_current = self
+                .find
+                .is_some_and(|find| find.current_cell() == Some((row_idx, c.position)));
+            for (line_y, line_idx) in (y..y + lines).zip(0..) {
+                // Pad with spaces to fill the column width
+                let text = cell_lines.get(line_idx).map_or("", |l| l.as_str());
+                let padded = format!("{:width$}", text, width = c.width as usize);
+                match self.find {
+                    Some(find) => {
+                        let line = highlight_matches(find, &padded, style, is_current);
+                        buf.set_line(c.x, line_y, &line, width);
+                    }
+                    None => {
+                        buf.set_span(c.x, line_y, &Span::styled(padded, style), width);
+                    }
+                }
+            }
+        }
+    }
 
-            buf.set_span(x_offset, y, &span, width);
-            x_offset += width;
+    /// Vertical line in the spacing after the last pinned column.
+    fn render_pinned_separator(&self, buf: &mut Buffer, layout: &TableLayout, bottom: u16) {
+        let Some(last) = layout.pinned.checked_sub(1).map(|i| layout.columns[i]) else {
+            return;
+        };
+        if layout.columns.len() == layout.pinned {
+            return;
+        }
+        let x = last.x + last.width - 1;
+        let border_style = ratatui::style::Style::default().fg(self.border_color);
+        for y in layout.area.y..bottom {
+            let symbol = if y == layout.area.y + 1 {
+                line::CROSS
+            } else {
+                line::VERTICAL
+            };
+            if let Some(cell) = buf.cell_mut(Position::new(x, y)) {
+                cell.set_symbol(symbol).set_style(border_style);
+            }
         }
     }
 
@@ -264,55 +441,116 @@ impl<'a> DataTable<'a> {
     }
 }
 
-impl<'a> Widget for DataTable<'a> {
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        if area.area() == 0 {
-            return;
-        }
+impl<'a> DataTable<'a> {
+    fn row_number_width(&self) -> u16 {
+        let max_row_num = self.data.rows.len().saturating_sub(self.vertical_scroll);
+        format!("{}", max_row_num).len().max(4) as u16
+    }
 
+    /// Where the table is drawn in `area`: the first row shown and the visible columns.
+    pub fn layout(&self, area: Rect) -> TableLayout {
         // Calculate row number section width
-        let max_row_num = self.data.rows.len().saturating_sub(self.vertical_scroll);
-        let max_row_num_length = format!("{}", max_row_num).len().max(4) as u16;
-        let row_num_section_width = max_row_num_length + 2 * NUM_SPACES_AFTER_LINE_NUMBER + 1;
-        let x_row_separator = max_row_num_length + NUM_SPACES_AFTER_LINE_NUMBER + 1;
+        let row_num_section_width = self.row_number_width() + 2 * NUM_SPACES_AFTER_LINE_NUMBER + 1;
 
         // Calculate available width for data columns
         let available_width = area.width.saturating_sub(row_num_section_width);
 
-        let min_column_width = 12;
-        let max_visible_columns = (available_width / min_column_width).max(1) as usize;
-
-        // Clamp scroll offset to valid range
-        let max_scroll = self.data.total_columns.saturating_sub(max_visible_columns);
-        let horizontal_scroll = self.horizontal_scroll.min(max_scroll);
-
-        // Get visible columns
-        let visible_headers: Vec<String> = self
-            .data
-            .flattened_columns
+        // Fit columns to the rows on screen
+        let row_lines = area.height.saturating_sub(2);
+        let rows_from_scroll = self.data.rows.get(self.vertical_scroll..).unwrap_or(&[]);
+        let rows_in_view = &rows_from_scroll[..rows_from_scroll.len().min(row_lines as usize)];
+        let max_width = MAX_FITTED_COLUMN_WIDTH.min(available_width / 2);
+        let view = self.view_columns();
+        let widths: Vec<u16> = view
             .iter()
-            .skip(horizontal_scroll)
-            .take(max_visible_columns)
-            .cloned()
+            .map(|&column| self.column_width(column, rows_in_view, max_width))
             .collect();
 
-        // Get visible data for each row (apply vertical scroll)
-        let visible_rows: Vec<Vec<String>> = self
-            .data
-            .rows
-            .iter()
-            .skip(self.vertical_scroll)
-            .map(|row| {
-                row.iter()
-                    .skip(horizontal_scroll)
-                    .take(max_visible_columns)
-                    .cloned()
-                    .collect()
+        // Pinned columns come first and stay put; the rest scroll in the remaining width
+        let pinned = self.pinned_columns.min(view.len());
+        let scroll_width = available_width.saturating_sub(widths[..pinned].iter().sum());
+
+        // Scroll no further than the current column and far enough for it to fit, then
+        // clamp so the last columns still fill the width
+        let mut first_scrolled = self.horizontal_scroll.max(pinned);
+        if let Some(current) = self
+            .current_column
+            .filter(|&c| c >= pinned && c < view.len())
+        {
+            first_scrolled = first_scrolled.min(current);
+            while first_scrolled < current
+                && widths[first_scrolled..=current].iter().sum::<u16>() > scroll_width
+            {
+                first_scrolled += 1;
+            }
+        }
+        let scrollable = &widths[pinned..];
+        let max_scroll = (0..scrollable.len())
+            .find(|&start| scrollable[start..].iter().sum::<u16>() <= scroll_width)
+            .unwrap_or(scrollable.len().saturating_sub(1));
+        let first_scrolled = first_scrolled.min(pinned + max_scroll);
+
+        let mut x = area.x + row_num_section_width;
+        let columns: Vec<LayoutColumn> = (0..pinned)
+            .chain(first_scrolled..view.len())
+            .map_while(|position| {
+                let column = LayoutColumn {
+                    position,
+                    column: view[position],
+                    x,
+                    width: widths[position],
+                };
+                x = x.saturating_add(column.width);
+                (column.x < area.right()).then_some(column)
             })
             .collect();
 
-        // Calculate column widths
-        let column_widths = self.calculate_column_widths(&visible_headers, &visible_rows);
+        // One line per row, more for the selected row when it is wrapped
+        let mut rows = Vec::new();
+        let mut y = area.y + 2;
+        for (i, row) in rows_in_view.iter().enumerate() {
+            if y >= area.bottom() {
+                break;
+            }
+            let row_idx = self.vertical_scroll + i;
+            let lines = if self.wrap_selected_row && self.selected_row == Some(row_idx) {
+                columns
+                    .iter()
+                    .filter_map(|c| {
+                        let text = row.get(c.column)?;
+                        let width = c.width.saturating_sub(NUM_SPACES_BETWEEN_COLUMNS);
+                        Some(wrap_text(text, width as usize).len() as u16)
+                    })
+                    .max()
+                    .unwrap_or(1)
+                    .clamp(1, area.bottom() - y)
+            } else {
+                1
+            };
+            rows.push((row_idx, y, lines));
+            y += lines;
+        }
+
+        TableLayout {
+            area,
+            rows,
+            pinned: columns.iter().filter(|c| c.position < pinned).count(),
+            columns,
+            first_scrolled,
+        }
+    }
+}
+
+impl<'a> Widget for DataTable<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.area() == 0 {
+            return;
+        }
+
+        let max_row_num_length = self.row_number_width();
+        let x_row_separator = max_row_num_length + NUM_SPACES_AFTER_LINE_NUMBER + 1;
+
+        let layout = self.layout(area);
 
         // Header area: 2 lines (header text + separator)
         let header_height = 2;
@@ -328,47 +566,49 @@ impl<'a> Widget for DataTable<'a> {
         );
 
         // Render row numbers
-        self.render_row_numbers(buf, rows_area, &visible_rows);
+        self.render_row_numbers(buf, &layout);
 
         // Render header
-        self.render_header(
-            buf,
-            row_num_section_width,
-            y_header,
-            &visible_headers,
-            &column_widths,
-            area.width,
-        );
+        self.render_header(buf, y_header, &layout.columns, area.right());
 
         // Render header separator (horizontal line below headers)
         self.render_header_separator(buf, area, x_row_separator, y_first_record);
 
         // Render data rows
-        let mut y_offset = y_first_record;
-        for (row_idx, row_data) in visible_rows.iter().enumerate() {
-            if y_offset >= rows_area.bottom() {
-                break;
-            }
-            let actual_row_num = row_idx + self.vertical_scroll;
-            let is_selected = self
-                .selected_row
-                .is_some_and(|selected| actual_row_num == selected);
-
+        for &row in &layout.rows {
             self.render_data_row(
                 buf,
-                row_num_section_width,
-                y_offset,
-                row_data,
-                &column_widths,
-                is_selected,
-                area.width,
+                row,
+                &self.data.rows[row.0],
+                &layout.columns,
+                area.right(),
             );
-            y_offset += 1;
         }
 
         // Render vertical separator after row numbers
         self.render_row_number_separator(buf, x_row_separator, y_first_record, rows_area.height);
+        self.render_pinned_separator(buf, &layout, rows_area.bottom());
+    }
+}
+
+/// Split `text` into spans, highlighting occurrences of the find query.
+/// The current match is drawn in a stronger color than the others.
+pub fn highlight_matches(
+    find: &FindMatches,
+    text: &str,
+    style: Style,
+    is_current: bool,
+) -> Line<'static> {
+    let highlight = theme().find_match(is_current);
+    let mut spans = Vec::new();
+    let mut last = 0;
+    for (start, end) in find.ranges(text) {
+        spans.push(Span::styled(text[last..start].to_string(), style));
+        spans.push(Span::styled(text[start..end].to_string(), highlight));
+        last = end;
     }
+    spans.push(Span::styled(text[last..].to_string(), style));
+    Line::from(spans)
 }
 
 impl Renderable for ParquetSampleData {
@@ -377,3 +617,71 @@ impl Renderable for ParquetSampleData {
         table_component.render(area, buf);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use polars::prelude::DataFrame;
+
+    fn data() -> ParquetSampleData {
+        ParquetSampleData {
+            flattened_columns: vec!["id".into(), "name".into(), "note".into()],
+            rows: vec![
+                vec!["1".into(), "alice".into(), "x".into()],
+                vec!["22".into(), "bob".into(), "longer text".into()],
+            ],
+            total_columns: 3,
+            total_rows: 2,
+            frame: DataFrame::empty(),
+        }
+    }
+
+    #[test]
+    fn test_layout_maps_positions_to_cells() {
+        let data = data();
+        let layout = DataTable::new(&data).layout(Rect::new(0, 0, 60, 10));
+
+        // Rows start below the header and its separator
+        assert_eq!(None, layout.row_at(1));
+        assert_eq!(Some(0), layout.row_at(2));
+        assert_eq!(Some(1), layout.row_at(3));
+        assert_eq!(None, layout.row_at(4));
+
+        // Columns fit their values, after the row numbers
+        assert_eq!(None, layout.column_at(8));
+        assert_eq!(Some(0), layout.column_at(9));
+        assert_eq!(Some(0), layout.column_at(14));
+        assert_eq!(Some(1), layout.column_at(15));
+        assert_eq!(Some(2), layout.column_at(34));
+        assert_eq!(None, layout.column_at(35));
+
+        // The spacing after a column in the header line resizes it
+        assert_eq!(None, layout.separator_at(12, 0));
+        assert_eq!(Some((0, 4)), layout.separator_at(13, 0));
+        assert_eq!(Some((0, 4)), layout.separator_at(14, 0));
+        assert_eq!(None, layout.separator_at(13, 2));
+    }
+
+    #[test]
+    fn test_layout_scrolls_past_pinned_columns() {
+        let data = data();
+        let order = [0, 1, 2];
+        let layout = DataTable::new(&data)
+            .with_column_order(&order, 1)
+            .with_current_column(Some(2))
+            .with_selected_row(Some(1))
+            .with_wrapped_row(true)
+            .layout(Rect::new(0, 0, 25, 10));
+
+        // The pinned column stays, the current one scrolls into view next to it
+        assert_eq!(1, layout.pinned);
+        assert_eq!(2, layout.first_scrolled);
+        assert_eq!(Some(0), layout.column_at(9));
+        assert_eq!(Some(2), layout.column_at(15));
+        assert_eq!(None, layout.column_at(25));
+
+        // The wrapped row takes a line per wrapped line of its widest value
+        assert_eq!(vec![(0, 2, 1), (1, 3, 2)], layout.rows);
+        assert_eq!(Some(1), layout.row_at(4));
+    }
+}
@@ -0,0 +1,114 @@
+use crate::file::group_by::GroupBy;
+use crate::file::utils::{commas, truncate_str};
+use crate::theme::theme;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style, Stylize},
+    symbols::border,
+    text::{Line, Span},
+    widgets::{Block, Widget},
+};
+
+const COUNT_WIDTH: usize = 14;
+const PERCENT_WIDTH: usize = 8;
+const EIGHTHS: [&str; 8] = ["", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+
+/// Value counts of a column: value, count, percent of rows and a bar per group.
+pub struct GroupByTable<'a> {
+    pub group_by: &'a GroupBy,
+    pub selected: usize,
+    pub border_style: border::Set,
+}
+
+impl<'a> GroupByTable<'a> {
+    pub fn new(group_by: &'a GroupBy, selected: usize) -> Self {
+        Self {
+            group_by,
+            selected,
+            border_style: border::ROUNDED,
+        }
+    }
+
+    /// A bar `fraction` of `width` cells long, in eighths of a cell.
+    fn bar(fraction: f64, width: usize) -> String {
+        let eighths = (fraction.clamp(0.0, 1.0) * width as f64 * 8.0).round() as usize;
+        format!("{}{}", "█".repeat(eighths / 8), EIGHTHS[eighths % 8])
+    }
+}
+
+impl<'a> Widget for GroupByTable<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let g = self.group_by;
+        let mut title = format!(
+            " {}: {} distinct values in {} rows",
+            g.column,
+            commas(g.distinct as u64),
+            commas(g.rows)
+        );
+        if g.distinct > g.groups.len() {
+            title.push_str(&format!(", top {}", commas(g.groups.len() as u64)));
+        }
+        title.push_str(" (Enter: filter, Esc: close) ");
+        let block = Block::bordered()
+            .border_set(self.border_style)
+            .border_style(Style::default().fg(theme().border))
+            .title(Span::styled(title, Style::default().fg(theme().title)));
+        let inner = block.inner(area);
+        block.render(area, buf);
+        if inner.height < 2 {
+            return;
+        }
+
+        let width = inner.width as usize;
+        let value_width = (width / 3).max(8);
+        let bar_width = width.saturating_sub(value_width + COUNT_WIDTH + PERCENT_WIDTH + 3);
+        let header = format!(
+            "{:<value_width$} {:>COUNT_WIDTH$} {:>PERCENT_WIDTH$}",
+            "Value", "Count", "%"
+        );
+        Line::from(header.fg(theme().header).bold()).render(Rect { height: 1, ..inner }, buf);
+
+        let max_count = g.groups.first().map_or(0, |group| group.count).max(1);
+        let visible = (inner.height - 1) as usize;
+        let scroll = (self.selected + 1).saturating_sub(visible);
+        for (row, (i, group)) in g
+            .groups
+            .iter()
+            .enumerate()
+            .skip(scroll)
+            .take(visible)
+            .enumerate()
+        {
+            let value = match &group.value {
+                Some(value) => Span::raw(format!(
+                    "{:<value_width$}",
+                    truncate_str(value, value_width)
+                )),
+                None => format!("{:<value_width$}", "NULL").fg(theme().muted),
+            };
+            let mut line = Line::from(vec![
+                value,
+                format!(
+                    " {:>COUNT_WIDTH$} {:>PERCENT_WIDTH$} ",
+                    commas(group.count),
+                    format!("{:.1}%", g.percent(group))
+                )
+                .into(),
+                Self::bar(group.count as f64 / max_count as f64, bar_width).fg(theme().title),
+            ]);
+            if i == self.selected {
+                line = line.style(Style::default().add_modifier(Modifier::REVERSED));
+            }
+            let y = inner.y + 1 + row as u16;
+            line.render(
+                Rect {
+                    y,
+                    height: 1,
+                    ..inner
+                },
+                buf,
+            );
+        }
+    }
+}
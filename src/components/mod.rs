@@ -1,12 +1,22 @@
+pub mod column_summary;
 pub mod data_table;
+pub mod group_by;
+pub mod result_chart;
 pub mod row_group;
 pub mod schema;
 pub mod scrollbar;
+pub mod storage;
 
+pub use column_summary::ColumnSummaryStrip;
 pub use data_table::DataTable;
+pub use group_by::GroupByTable;
+pub use result_chart::ResultChart;
 pub use row_group::RowGroupColumnMetadataComponent;
 pub use row_group::RowGroupMetadata;
 pub use row_group::RowGroupProgressBar;
+pub use schema::ColumnProfileComponent;
+pub use schema::CompressionSimulationComponent;
 pub use schema::FileSchemaTable;
 pub use schema::SchemaTreeComponent;
 pub use scrollbar::ScrollbarComponent;
+pub use storage::StorageBreakdownComponent;
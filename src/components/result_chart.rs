@@ -0,0 +1,194 @@
+use crate::file::chart::{ChartData, ChartKind, XAxis};
+use crate::file::utils::{commas, format_number};
+use crate::theme::theme;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Style, Stylize},
+    symbols::Marker,
+    text::{Line, Span},
+    widgets::{Axis, Bar, BarChart, BarGroup, Block, Borders, Chart, Dataset, GraphType, Widget},
+};
+
+const NUM_AXIS_LABELS: usize = 4;
+/// Bar heights are scaled to this range since bars only take integers.
+const BAR_SCALE: f64 = 10_000.0;
+
+/// Line, bar or scatter chart of a query result.
+pub struct ResultChart<'a> {
+    pub data: &'a ChartData,
+    pub kind: ChartKind,
+}
+
+impl<'a> ResultChart<'a> {
+    pub fn new(data: &'a ChartData, kind: ChartKind) -> Self {
+        Self { data, kind }
+    }
+
+    fn title(&self) -> Line<'static> {
+        let mut title: Vec<Span<'static>> = Vec::new();
+        for (i, series) in self.data.series.iter().enumerate() {
+            if i > 0 {
+                title.push(", ".into());
+            }
+            title.push(series.name.clone().fg(theme().series_color(i)).bold());
+        }
+        title.push(format!(" by {}", self.data.x_name).into());
+        Line::from(title).centered()
+    }
+
+    fn footnote(&self) -> Line<'static> {
+        let text = match (self.data.buckets, self.data.x_axis) {
+            (Some(buckets), _) => format!(
+                "{} rows averaged into {buckets} buckets",
+                commas(self.data.rows as u64)
+            ),
+            (None, XAxis::Category) if self.data.categories.len() < self.data.rows => format!(
+                "first {} of {} rows",
+                self.data.categories.len(),
+                commas(self.data.rows as u64)
+            ),
+            _ => format!("{} rows", commas(self.data.rows as u64)),
+        };
+        Line::from(text.fg(theme().muted))
+    }
+
+    fn axis_labels(bounds: [f64; 2], label: impl Fn(f64) -> String) -> Vec<String> {
+        (0..NUM_AXIS_LABELS)
+            .map(|i| {
+                label(bounds[0] + (bounds[1] - bounds[0]) * i as f64 / (NUM_AXIS_LABELS - 1) as f64)
+            })
+            .collect()
+    }
+
+    /// Categories sit at whole positions, so label those rather than even steps.
+    fn x_labels(&self, bounds: [f64; 2]) -> Vec<String> {
+        match self.data.x_axis {
+            XAxis::Category => {
+                let n = self.data.categories.len();
+                let positions: Vec<usize> = if n <= NUM_AXIS_LABELS {
+                    (0..n).collect()
+                } else {
+                    (0..NUM_AXIS_LABELS)
+                        .map(|i| i * (n - 1) / (NUM_AXIS_LABELS - 1))
+                        .collect()
+                };
+                positions
+                    .into_iter()
+                    .map(|i| self.data.categories[i].clone())
+                    .collect()
+            }
+            XAxis::Number | XAxis::Time => Self::axis_labels(bounds, |x| self.data.x_label(x)),
+        }
+    }
+
+    fn render_chart(&self, area: Rect, buf: &mut Buffer) {
+        let (graph_type, marker) = match self.kind {
+            ChartKind::Scatter => (GraphType::Scatter, Marker::Dot),
+            _ => (GraphType::Line, Marker::Braille),
+        };
+        let datasets = self
+            .data
+            .series
+            .iter()
+            .enumerate()
+            .map(|(i, series)| {
+                Dataset::default()
+                    .name(series.name.clone())
+                    .marker(marker)
+                    .graph_type(graph_type)
+                    .style(Style::default().fg(theme().series_color(i)))
+                    .data(&series.points)
+            })
+            .collect();
+
+        let x_bounds = self.data.x_bounds();
+        let y_bounds = self.data.y_bounds();
+        let chart = Chart::new(datasets)
+            .block(
+                Block::default()
+                    .title(self.title())
+                    .title_bottom(self.footnote())
+                    .borders(Borders::NONE),
+            )
+            .x_axis(
+                Axis::default()
+                    .style(Style::default().fg(theme().text))
+                    .bounds(x_bounds)
+                    .labels(self.x_labels(x_bounds)),
+            )
+            .y_axis(
+                Axis::default()
+                    .style(Style::default().fg(theme().text))
+                    .bounds(y_bounds)
+                    .labels(Self::axis_labels(y_bounds, format_number)),
+            );
+
+        chart.render(area, buf);
+    }
+
+    fn render_bars(&self, area: Rect, buf: &mut Buffer) {
+        let block = Block::default()
+            .title(self.title())
+            .title_bottom(self.footnote())
+            .borders(Borders::NONE);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let series = &self.data.series;
+        let max = series
+            .iter()
+            .flat_map(|s| s.points.iter().map(|p| p.1))
+            .fold(0.0, f64::max);
+        if max <= 0.0 {
+            return;
+        }
+
+        // One group per X value with a bar per series; bars shrink to fit the width
+        let mut xs: Vec<f64> = series
+            .iter()
+            .flat_map(|s| s.points.iter().map(|p| p.0))
+            .collect();
+        xs.sort_by(|a, b| a.total_cmp(b));
+        xs.dedup();
+        let group_width = inner.width as usize / xs.len().max(1);
+        let bar_width = (group_width.saturating_sub(1) / series.len().max(1)).max(1) as u16;
+        let group_gap = u16::from(group_width > series.len());
+
+        let mut chart = BarChart::default()
+            .bar_width(bar_width)
+            .bar_gap(0)
+            .group_gap(group_gap)
+            .max(BAR_SCALE as u64);
+        for x in xs {
+            let bars: Vec<Bar> = series
+                .iter()
+                .enumerate()
+                .filter_map(|(i, s)| {
+                    let y = s.points.iter().find(|p| p.0 == x)?.1;
+                    Some(
+                        Bar::default()
+                            .value((y.max(0.0) / max * BAR_SCALE) as u64)
+                            .text_value(format_number(y))
+                            .style(Style::default().fg(theme().series_color(i))),
+                    )
+                })
+                .collect();
+            chart = chart.data(
+                BarGroup::default()
+                    .label(Line::from(self.data.x_label(x)))
+                    .bars(&bars),
+            );
+        }
+        chart.render(inner, buf);
+    }
+}
+
+impl<'a> Widget for ResultChart<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        match self.kind {
+            ChartKind::Bar => self.render_bars(area, buf),
+            ChartKind::Line | ChartKind::Scatter => self.render_chart(area, buf),
+        }
+    }
+}
@@ -3,7 +3,6 @@ use ratatui::style::Style;
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Layout, Rect},
-    prelude::Color,
     style::Stylize,
     symbols::Marker,
     text::{Line, Span},
@@ -11,6 +10,7 @@ use ratatui::{
 };
 
 use crate::file::utils::{commas, human_readable_bytes};
+use crate::theme::theme;
 
 /// Component to display row group level statistics
 pub struct RowGroupMetadata<'a> {
@@ -102,15 +102,15 @@ impl<'a> RowGroupMetadata<'a> {
         median: String,
     ) {
         let title_bottom: Vec<Span> = vec![
-            average.light_cyan().bold(),
-            " / ".white().bold(),
-            median.light_magenta().bold(),
+            average.fg(theme().title).bold(),
+            " / ".fg(theme().text).bold(),
+            median.fg(theme().accent).bold(),
         ];
 
         let block = Block::bordered()
-            .title(title.light_blue().bold())
+            .title(title.fg(theme().key_alt).bold())
             .title_bottom(Line::from(title_bottom).centered())
-            .border_style(ratatui::style::Style::default().fg(Color::Blue));
+            .border_style(ratatui::style::Style::default().fg(theme().key_alt));
 
         let inner = block.inner(area);
         block.render(area, buf);
@@ -126,7 +126,7 @@ impl<'a> RowGroupMetadata<'a> {
                     let x = inner.x + (inner.width.saturating_sub(line.len() as u16)) / 2;
                     if x < inner.x + inner.width {
                         line.bold()
-                            .yellow()
+                            .fg(theme().header)
                             .render(Rect::new(x, y, line.len() as u16, 1), buf);
                     }
                 }
@@ -208,12 +208,12 @@ impl<'a> RowGroupMetadata<'a> {
             Dataset::default()
                 .name("Compressed")
                 .marker(Marker::Dot)
-                .style(Style::default().fg(Color::Blue))
+                .style(Style::default().fg(theme().key_alt))
                 .data(&compressed_data),
             Dataset::default()
                 .name("Uncompressed")
                 .marker(Marker::Dot)
-                .style(Style::default().fg(Color::Red))
+                .style(Style::default().fg(theme().bad))
                 .data(&uncompressed_data),
         ];
 
@@ -234,9 +234,9 @@ impl<'a> RowGroupMetadata<'a> {
             .collect();
 
         let title = vec![
-            "Compressed".light_blue().bold(),
+            "Compressed".fg(theme().key_alt).bold(),
             " vs ".into(),
-            "Uncompressed".light_red().bold(),
+            "Uncompressed".fg(theme().bad).bold(),
             " (B)".into(),
         ];
 
@@ -244,18 +244,18 @@ impl<'a> RowGroupMetadata<'a> {
             .block(
                 Block::default()
                     .title(Line::from(title).centered())
-                    .title_bottom("Row Group".dark_gray())
+                    .title_bottom("Row Group".fg(theme().muted))
                     .borders(Borders::NONE),
             )
             .x_axis(
                 Axis::default()
-                    .style(Style::default().fg(Color::White))
+                    .style(Style::default().fg(theme().text))
                     .bounds([0.0, 1.0])
                     .labels(x_labels),
             )
             .y_axis(
                 Axis::default()
-                    .style(Style::default().fg(Color::White))
+                    .style(Style::default().fg(theme().text))
                     .bounds([0.0, max_size * 1.5])
                     .labels(y_labels),
             );
@@ -293,7 +293,7 @@ impl<'a> RowGroupMetadata<'a> {
             Dataset::default()
                 .name("Compression Ratio")
                 .marker(Marker::Dot)
-                .style(Style::default().fg(Color::Yellow))
+                .style(Style::default().fg(theme().header))
                 .data(&ratio_data),
         ];
 
@@ -312,19 +312,19 @@ impl<'a> RowGroupMetadata<'a> {
         let chart = Chart::new(datasets)
             .block(
                 Block::default()
-                    .title("Compression Ratio".yellow())
-                    .title_bottom("Row Group".dark_gray())
+                    .title("Compression Ratio".fg(theme().header))
+                    .title_bottom("Row Group".fg(theme().muted))
                     .borders(Borders::NONE),
             )
             .x_axis(
                 Axis::default()
-                    .style(Style::default().fg(Color::White))
+                    .style(Style::default().fg(theme().text))
                     .bounds([0.0, 1.0])
                     .labels(x_labels),
             )
             .y_axis(
                 Axis::default()
-                    .style(Style::default().fg(Color::White))
+                    .style(Style::default().fg(theme().text))
                     .bounds([1.0, max_ratio * 1.1])
                     .labels(y_labels),
             );
@@ -1,4 +1,7 @@
+use crate::file::pruning::PruningReport;
 use crate::file::row_groups::RowGroupStats;
+use crate::file::utils::{commas, format_size};
+use crate::theme::theme;
 use ratatui::{
     buffer::Buffer,
     layout::{Position, Rect},
@@ -11,6 +14,7 @@ use ratatui::{
 pub struct RowGroupProgressBar<'a> {
     pub row_group_stats: &'a [RowGroupStats],
     pub selected_idx: usize,
+    pub pruning: Option<&'a PruningReport>,
 }
 
 impl<'a> RowGroupProgressBar<'a> {
@@ -18,25 +22,118 @@ impl<'a> RowGroupProgressBar<'a> {
         Self {
             row_group_stats,
             selected_idx,
+            pruning: None,
         }
     }
-}
 
-impl<'a> Widget for RowGroupProgressBar<'a> {
-    fn render(self, area: Rect, buf: &mut Buffer) {
+    /// Color row groups by what a pushdown filter would skip, and add a second line
+    /// showing which rows of the selected row group are still read.
+    pub fn with_pruning(mut self, pruning: Option<&'a PruningReport>) -> Self {
+        self.pruning = pruning;
+        self
+    }
+
+    /// Row group under screen column `x` of a bar of `row_groups` drawn in `area`.
+    pub fn segment_at(area: Rect, row_groups: usize, x: u16) -> Option<usize> {
+        let inner = area.inner(ratatui::layout::Margin::new(1, 1));
+        if row_groups == 0 || !(inner.x..inner.right()).contains(&x) {
+            return None;
+        }
+        let segment_width = inner.width as f64 / row_groups as f64;
+        Some((((x - inner.x) as f64 / segment_width) as usize).min(row_groups - 1))
+    }
+
+    /// Line color of a row group: red when pruned, yellow when pages are skipped.
+    fn segment_color(&self, idx: usize) -> Color {
+        let Some(rg) = self.pruning.and_then(|p| p.row_groups.get(idx)) else {
+            return theme().text;
+        };
+        if rg.pruned_by.is_some() {
+            theme().bad
+        } else if rg.rows_read() < rg.rows {
+            theme().header
+        } else {
+            theme().good
+        }
+    }
+
+    fn title(&self) -> Line<'static> {
         let total_row_groups = self.row_group_stats.len();
-        let title: Vec<Span<'static>> = vec![
+        let mut title: Vec<Span<'static>> = vec![
             " Row Group: ".into(),
             format!("{}", self.selected_idx + 1).into(),
             " / ".into(),
             format!("{total_row_groups}").into(),
             " ".into(),
         ];
+        if let Some(rg) = self
+            .pruning
+            .and_then(|p| p.row_groups.get(self.selected_idx))
+        {
+            let status = match rg.pruned_by {
+                Some(reason) => format!("skipped by {} ", reason.describe()).fg(theme().bad),
+                None if rg.pages_total > 0 => format!(
+                    "reads {} of {} rows, {} of {} pages ",
+                    commas(rg.rows_read() as u64),
+                    commas(rg.rows as u64),
+                    rg.pages_read,
+                    rg.pages_total
+                )
+                .into(),
+                None => "read ".fg(theme().good),
+            };
+            title.extend(vec!["- ".into(), status]);
+        }
+        Line::from(title)
+    }
+
+    fn pruning_summary(report: &PruningReport) -> Line<'static> {
+        let mut summary: Vec<Span<'static>> = vec![
+            " Pushdown ".into(),
+            report.filter.clone().fg(theme().key),
+            format!(
+                ": {} / {} row groups pruned",
+                report.row_groups_pruned(),
+                report.row_groups.len()
+            )
+            .into(),
+        ];
+        if report.has_page_index {
+            summary.push(
+                format!(
+                    ", {} / {} pages read",
+                    report.pages_read(),
+                    report.pages_total()
+                )
+                .into(),
+            );
+        } else {
+            summary.push(", no page index".into());
+        }
+        summary.push(
+            format!(
+                ", {} of {} read ({:.1}%) ",
+                format_size(report.bytes_read()),
+                format_size(report.bytes_total()),
+                report.read_fraction() * 100.0
+            )
+            .bold(),
+        );
+        Line::from(summary)
+    }
+}
+
+impl<'a> Widget for RowGroupProgressBar<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let total_row_groups = self.row_group_stats.len();
 
-        let block = Block::bordered()
-            .title(Line::from(title))
-            .border_style(ratatui::style::Style::default().fg(Color::Gray))
-            .title_style(ratatui::style::Style::default().fg(Color::Gray).bold());
+        let mut block = Block::bordered()
+            .title(self.title())
+            .border_style(ratatui::style::Style::default().fg(theme().border))
+            .title_style(ratatui::style::Style::default().fg(theme().muted).bold());
+        if let Some(report) = self.pruning {
+            block = block.title_bottom(Self::pruning_summary(report));
+        }
 
         let inner = block.inner(area);
         block.render(area, buf);
@@ -45,28 +142,82 @@ impl<'a> Widget for RowGroupProgressBar<'a> {
             // Calculate the width of each segment
             let segment_width = inner.width as f64 / total_row_groups as f64;
 
-            // Find the center line to draw the thin progress bar
-            let center_y = inner.y + inner.height / 2;
+            // Find the center line to draw the thin progress bar; with a pushdown
+            // preview the row line of the selected row group goes below it
+            let center_y = if self.pruning.is_some() {
+                inner.y
+            } else {
+                inner.y + inner.height / 2
+            };
 
-            // First, draw the thin white line across the entire width
+            // First, draw the thin line across the entire width, colored per row group
             for x in inner.x..inner.x + inner.width {
+                let idx =
+                    (((x - inner.x) as f64 / segment_width) as usize).min(total_row_groups - 1);
                 if let Some(cell) = buf.cell_mut(Position::new(x, center_y)) {
                     cell.set_symbol("─")
-                        .set_style(ratatui::style::Style::default().fg(Color::White));
+                        .set_style(ratatui::style::Style::default().fg(self.segment_color(idx)));
                 }
             }
 
             // Then, draw the thick filled section for the selected row group
             let selected_start_x = inner.x + (self.selected_idx as f64 * segment_width) as u16;
             let selected_end_x = inner.x + ((self.selected_idx + 1) as f64 * segment_width) as u16;
+            let selected_color = match self.pruning {
+                Some(_) => self.segment_color(self.selected_idx),
+                None => theme().key_alt,
+            };
 
             // Fill the selected section with solid blocks (single line, centered)
             for x in selected_start_x..selected_end_x.min(inner.x + inner.width) {
                 if let Some(cell) = buf.cell_mut(Position::new(x, center_y)) {
                     cell.set_symbol("█")
-                        .set_style(ratatui::style::Style::default().fg(Color::Blue));
+                        .set_style(ratatui::style::Style::default().fg(selected_color));
+                }
+            }
+
+            // Rows of the selected row group: read ranges solid, skipped pages dotted
+            if let Some(rg) = self
+                .pruning
+                .and_then(|p| p.row_groups.get(self.selected_idx))
+                && inner.height > 1
+                && rg.rows > 0
+            {
+                let rows_y = center_y + 1;
+                for x in inner.x..inner.x + inner.width {
+                    let start = (x - inner.x) as usize * rg.rows / inner.width as usize;
+                    let end = ((x - inner.x + 1) as usize * rg.rows / inner.width as usize)
+                        .max(start + 1);
+                    let read = rg.selected_rows.iter().any(|(s, e)| *s < end && start < *e);
+                    let (symbol, color) = if read {
+                        ("▀", theme().good)
+                    } else {
+                        ("·", theme().muted)
+                    };
+                    if let Some(cell) = buf.cell_mut(Position::new(x, rows_y)) {
+                        cell.set_symbol(symbol)
+                            .set_style(ratatui::style::Style::default().fg(color));
+                    }
                 }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_segment_at() {
+        // Five row groups share the ten columns inside the border
+        let area = Rect::new(0, 0, 12, 3);
+        assert_eq!(None, RowGroupProgressBar::segment_at(area, 5, 0));
+        assert_eq!(Some(0), RowGroupProgressBar::segment_at(area, 5, 1));
+        assert_eq!(Some(0), RowGroupProgressBar::segment_at(area, 5, 2));
+        assert_eq!(Some(1), RowGroupProgressBar::segment_at(area, 5, 3));
+        assert_eq!(Some(4), RowGroupProgressBar::segment_at(area, 5, 10));
+        assert_eq!(None, RowGroupProgressBar::segment_at(area, 5, 11));
+        assert_eq!(None, RowGroupProgressBar::segment_at(area, 0, 1));
+    }
+}
@@ -1,9 +1,9 @@
 use crate::file::utils::human_readable_bytes;
 use crate::file::{row_groups::RowGroupColumnMetadata, utils::commas};
+use crate::theme::theme;
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Layout, Position, Rect},
-    prelude::Color,
     style::Stylize,
     text::Line,
     widgets::{Block, Borders, Cell, Row, Table, Widget},
@@ -24,14 +24,18 @@ impl<'a> Widget for RowGroupColumnMetadataComponent<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let title = vec![
             " Column: ".into(),
-            self.column_metadata.column_path.clone().yellow().bold(),
+            self.column_metadata
+                .column_path
+                .clone()
+                .fg(theme().header)
+                .bold(),
             " ".into(),
         ];
 
         let block = Block::bordered()
             .title(Line::from(title).centered())
             .borders(Borders::TOP)
-            .border_style(ratatui::style::Style::default().fg(Color::Blue));
+            .border_style(ratatui::style::Style::default().fg(theme().key_alt));
 
         let inner_area = block.inner(area);
         block.render(area, buf);
@@ -107,8 +111,8 @@ impl<'a> RowGroupColumnMetadataComponent<'a> {
             .into_iter()
             .map(|(k, v)| {
                 Row::new(vec![
-                    Cell::from(k).bold().fg(Color::Cyan),
-                    Cell::from(v).fg(Color::White),
+                    Cell::from(k).bold().fg(theme().title),
+                    Cell::from(v).fg(theme().text),
                 ])
             })
             .collect();
@@ -116,7 +120,7 @@ impl<'a> RowGroupColumnMetadataComponent<'a> {
         let table = Table::new(rows, vec![Constraint::Length(18), Constraint::Fill(1)]).block(
             Block::bordered()
                 .title("Metadata")
-                .border_style(ratatui::style::Style::default().fg(Color::Blue)),
+                .border_style(ratatui::style::Style::default().fg(theme().key_alt)),
         );
 
         table.render(area, buf);
@@ -160,9 +164,9 @@ impl<'a> RowGroupColumnMetadataComponent<'a> {
 
     fn render_indicator_box(&self, title: &str, has_feature: bool, area: Rect, buf: &mut Buffer) {
         let (symbol, color) = if has_feature {
-            ("✓", Color::Green)
+            ("✓", theme().good)
         } else {
-            ("✗", Color::Red)
+            ("✗", theme().bad)
         };
 
         let block = Block::bordered()
@@ -211,8 +215,8 @@ impl<'a> RowGroupColumnMetadataComponent<'a> {
                 .into_iter()
                 .map(|(k, v)| {
                     Row::new(vec![
-                        Cell::from(k).bold().fg(Color::Magenta),
-                        Cell::from(v).fg(Color::White),
+                        Cell::from(k).bold().fg(theme().accent),
+                        Cell::from(v).fg(theme().text),
                     ])
                 })
                 .collect();
@@ -220,7 +224,7 @@ impl<'a> RowGroupColumnMetadataComponent<'a> {
             let table = Table::new(rows, vec![Constraint::Length(18), Constraint::Fill(1)]).block(
                 Block::bordered()
                     .title("Statistics")
-                    .border_style(ratatui::style::Style::default().fg(Color::Magenta)),
+                    .border_style(ratatui::style::Style::default().fg(theme().accent)),
             );
 
             table.render(area, buf);
@@ -232,11 +236,11 @@ impl<'a> RowGroupColumnMetadataComponent<'a> {
 
         // Create header
         let header = Row::new(vec![
-            Cell::from("#").bold().fg(Color::Yellow),
-            Cell::from("Page Type").bold().fg(Color::Yellow),
-            Cell::from("Size").bold().fg(Color::Yellow),
-            Cell::from("Rows").bold().fg(Color::Yellow),
-            Cell::from("Encoding").bold().fg(Color::Yellow),
+            Cell::from("#").bold().fg(theme().header),
+            Cell::from("Page Type").bold().fg(theme().header),
+            Cell::from("Size").bold().fg(theme().header),
+            Cell::from("Rows").bold().fg(theme().header),
+            Cell::from("Encoding").bold().fg(theme().header),
         ]);
 
         // Create rows from page info
@@ -248,11 +252,11 @@ impl<'a> RowGroupColumnMetadataComponent<'a> {
             .enumerate()
             .map(|(idx, page)| {
                 Row::new(vec![
-                    Cell::from((idx + 1).to_string()).fg(Color::White),
-                    Cell::from(page.page_type.clone()).fg(Color::Cyan),
-                    Cell::from(human_readable_bytes(page.size as u64)).fg(Color::White),
-                    Cell::from(commas(page.rows as u64)).fg(Color::White),
-                    Cell::from(page.encoding.clone()).fg(Color::Green),
+                    Cell::from((idx + 1).to_string()).fg(theme().text),
+                    Cell::from(page.page_type.clone()).fg(theme().title),
+                    Cell::from(human_readable_bytes(page.size as u64)).fg(theme().text),
+                    Cell::from(commas(page.rows as u64)).fg(theme().text),
+                    Cell::from(page.encoding.clone()).fg(theme().key),
                 ])
             })
             .collect();
@@ -271,7 +275,7 @@ impl<'a> RowGroupColumnMetadataComponent<'a> {
         .block(
             Block::bordered()
                 .title("Pages")
-                .border_style(ratatui::style::Style::default().fg(Color::DarkGray)),
+                .border_style(ratatui::style::Style::default().fg(theme().border)),
         );
 
         table.render(area, buf);
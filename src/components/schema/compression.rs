@@ -0,0 +1,122 @@
+use crate::file::compression::CompressionSimulation;
+use crate::file::utils::{commas, format_size};
+use crate::theme::theme;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::{Style, Stylize},
+    symbols::border,
+    text::Line,
+    widgets::{Block, Cell, Row, Table, Widget},
+};
+
+/// Table of re-encoding trials for a single column, compared to the current encoding.
+pub struct CompressionSimulationComponent<'a> {
+    pub simulation: &'a CompressionSimulation,
+    pub vertical_scroll: usize,
+    pub border_style: border::Set,
+}
+
+impl<'a> CompressionSimulationComponent<'a> {
+    pub fn new(simulation: &'a CompressionSimulation) -> Self {
+        Self {
+            simulation,
+            vertical_scroll: 0,
+            border_style: border::ROUNDED,
+        }
+    }
+
+    pub fn with_vertical_scroll(mut self, offset: usize) -> Self {
+        self.vertical_scroll = offset;
+        self
+    }
+}
+
+impl<'a> Widget for CompressionSimulationComponent<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let sim = self.simulation;
+        let headers = [
+            "Codec",
+            "Encoding",
+            "Sample",
+            "vs current",
+            "Est. total",
+            "Encode",
+            "Decode",
+        ];
+
+        let mut rows = vec![
+            Row::new([
+                Cell::from("current"),
+                Cell::from("-"),
+                Cell::from(format_size(sim.sample_compressed_size)),
+                Cell::from("1.00x"),
+                Cell::from(format_size(sim.current_total_compressed_size)),
+                Cell::from(""),
+                Cell::from(""),
+            ])
+            .style(Style::default().fg(theme().title).bold()),
+        ];
+
+        let visible_rows = area.height.saturating_sub(4) as usize;
+        rows.extend(
+            sim.trials
+                .iter()
+                .skip(self.vertical_scroll)
+                .take(visible_rows)
+                .map(|trial| {
+                    let relative = sim.relative_size(trial);
+                    let color = if relative < 0.95 {
+                        theme().good
+                    } else if relative > 1.05 {
+                        theme().bad
+                    } else {
+                        theme().text
+                    };
+                    Row::new([
+                        Cell::from(trial.codec.clone()),
+                        Cell::from(trial.encoding.clone()),
+                        Cell::from(format_size(trial.compressed_size)),
+                        Cell::from(format!("{relative:.2}x")),
+                        Cell::from(format_size(sim.estimated_total_size(trial))),
+                        Cell::from(format!("{:.1} ms", trial.encode_time.as_secs_f64() * 1e3)),
+                        Cell::from(format!("{:.1} ms", trial.decode_time.as_secs_f64() * 1e3)),
+                    ])
+                    .fg(color)
+                }),
+        );
+
+        let title = format!(
+            " Compression what-if: {} ({} sampled rows) ",
+            sim.column,
+            commas(sim.sample_rows as u64)
+        );
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(12),
+                Constraint::Length(24),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+            ],
+        )
+        .header(Row::new(
+            headers
+                .into_iter()
+                .map(|h| Cell::from(h).bold().fg(theme().header)),
+        ))
+        .column_spacing(1)
+        .block(
+            Block::bordered()
+                .title(Line::from(title).centered().bold().fg(theme().key))
+                .title_bottom(Line::from(" Esc close, ↑↓ scroll ").centered())
+                .border_set(self.border_style),
+        );
+
+        table.render(area, buf);
+    }
+}
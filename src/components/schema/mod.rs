@@ -1,5 +1,9 @@
+pub mod compression;
+pub mod profile;
 pub mod table;
 pub mod tree;
 
+pub use compression::CompressionSimulationComponent;
+pub use profile::ColumnProfileComponent;
 pub use table::FileSchemaTable;
 pub use tree::SchemaTreeComponent;
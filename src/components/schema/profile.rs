@@ -0,0 +1,160 @@
+use crate::file::profile::ColumnProfile;
+use crate::file::utils::{commas, format_number, human_readable_count, truncate_str};
+use crate::theme::theme;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Style, Stylize},
+    symbols::border,
+    text::{Line, Span},
+    widgets::{Bar, BarChart, BarGroup, Block, Paragraph, Widget},
+};
+
+/// Column profile panel: summary statistics, top-k values and a histogram.
+pub struct ColumnProfileComponent<'a> {
+    pub profile: &'a ColumnProfile,
+    pub border_style: border::Set,
+}
+
+impl<'a> ColumnProfileComponent<'a> {
+    pub fn new(profile: &'a ColumnProfile) -> Self {
+        Self {
+            profile,
+            border_style: border::ROUNDED,
+        }
+    }
+
+    fn summary_lines(&self) -> Vec<Line<'static>> {
+        let p = self.profile;
+        let null_pct = if p.rows > 0 {
+            p.null_count as f64 * 100.0 / p.rows as f64
+        } else {
+            0.0
+        };
+        let mut lines = vec![
+            Line::from(vec![
+                "Type: ".fg(theme().key_alt).bold(),
+                Span::raw(p.dtype.clone()),
+                "  Rows: ".fg(theme().key_alt).bold(),
+                Span::raw(commas(p.rows)),
+            ]),
+            Line::from(vec![
+                "Nulls: ".fg(theme().key_alt).bold(),
+                Span::raw(format!("{} ({null_pct:.1}%)", commas(p.null_count))),
+                "  Distinct (approx): ".fg(theme().key_alt).bold(),
+                Span::raw(commas(p.approx_distinct)),
+            ]),
+        ];
+        if let (Some(min), Some(max), Some(mean)) = (p.min, p.max, p.mean) {
+            let label = if p.quantiles.is_empty() {
+                "Length "
+            } else {
+                ""
+            };
+            lines.push(Line::from(vec![
+                format!("{label}Min: ").fg(theme().key_alt).bold(),
+                Span::raw(format_number(min)),
+                "  Max: ".fg(theme().key_alt).bold(),
+                Span::raw(format_number(max)),
+                "  Mean: ".fg(theme().key_alt).bold(),
+                Span::raw(format_number(mean)),
+            ]));
+        }
+        if !p.quantiles.is_empty() {
+            let mut spans = vec!["Quantiles: ".fg(theme().key_alt).bold()];
+            for (q, v) in &p.quantiles {
+                spans.push(format!("p{:.0}=", q * 100.0).fg(theme().muted));
+                spans.push(Span::raw(format!("{} ", format_number(*v))));
+            }
+            lines.push(Line::from(spans));
+        }
+        lines
+    }
+
+    fn render_top_values(&self, area: Rect, buf: &mut Buffer) {
+        let label_width = area.width.saturating_sub(14).clamp(4, 24) as usize;
+        let bars: Vec<Bar> = self
+            .profile
+            .top_values
+            .iter()
+            .map(|(value, count)| {
+                Bar::default()
+                    .value(*count)
+                    .label(Line::from(format!(
+                        "{:<label_width$}",
+                        truncate_str(value, label_width)
+                    )))
+                    .text_value(human_readable_count(*count))
+            })
+            .collect();
+
+        BarChart::default()
+            .block(Block::bordered().title(" Top values ".fg(theme().header).bold()))
+            .direction(Direction::Horizontal)
+            .bar_width(1)
+            .bar_gap(0)
+            .bar_style(Style::default().fg(theme().title))
+            .value_style(Style::default().fg(theme().selected_fg).bg(theme().title))
+            .data(BarGroup::default().bars(&bars))
+            .render(area, buf);
+    }
+
+    fn render_histogram(&self, area: Rect, buf: &mut Buffer) {
+        let bins = &self.profile.histogram;
+        let inner_width = area.width.saturating_sub(2) as usize;
+        let bar_width = (inner_width / bins.len().max(1)).saturating_sub(1).max(1) as u16;
+        let bars: Vec<Bar> = bins
+            .iter()
+            .map(|bin| Bar::default().value(bin.count).text_value(String::new()))
+            .collect();
+
+        let (lower, upper) = match (bins.first(), bins.last()) {
+            (Some(first), Some(last)) => (first.lower, last.upper),
+            _ => (0.0, 0.0),
+        };
+        let title = format!(
+            " {} histogram ({} – {}) ",
+            self.profile.histogram_label,
+            format_number(lower),
+            format_number(upper)
+        );
+        BarChart::default()
+            .block(Block::bordered().title(title.fg(theme().header).bold()))
+            .bar_width(bar_width)
+            .bar_gap(1)
+            .bar_style(Style::default().fg(theme().accent))
+            .data(BarGroup::default().bars(&bars))
+            .render(area, buf);
+    }
+}
+
+impl<'a> Widget for ColumnProfileComponent<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = Block::bordered()
+            .title(
+                Line::from(format!(" Profile: {} ", self.profile.column))
+                    .centered()
+                    .bold()
+                    .fg(theme().key),
+            )
+            .title_bottom(Line::from(" Esc close ").centered())
+            .border_set(self.border_style);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let summary = self.summary_lines();
+        let top_height = (self.profile.top_values.len() as u16 + 2).max(3);
+        let [summary_area, top_area, histogram_area] = Layout::vertical([
+            Constraint::Length(summary.len() as u16),
+            Constraint::Length(top_height),
+            Constraint::Fill(1),
+        ])
+        .areas(inner);
+
+        Paragraph::new(summary).render(summary_area, buf);
+        self.render_top_values(top_area, buf);
+        if !self.profile.histogram.is_empty() {
+            self.render_histogram(histogram_area, buf);
+        }
+    }
+}
@@ -11,6 +11,7 @@ use ratatui::{
 use std::cmp::min;
 
 use crate::file::Renderable;
+use crate::theme::theme;
 
 pub struct FileSchemaTable<'a> {
     pub schema: &'a FileSchema,
@@ -29,8 +30,8 @@ impl<'a> FileSchemaTable<'a> {
             schema,
             selected_index: 0,
             title: "Column Statistics".to_string(),
-            title_color: Color::Green,
-            selected_color: Color::Yellow,
+            title_color: theme().key,
+            selected_color: theme().selected_bg,
             border_style: border::ROUNDED,
             horizontal_scroll: 0,
             vertical_scroll: 0,
@@ -151,7 +152,7 @@ impl<'a> Widget for FileSchemaTable<'a> {
             .header(Row::new(
                 visible_headers
                     .into_iter()
-                    .map(|h| Cell::from(*h).bold().fg(Color::Yellow)),
+                    .map(|h| Cell::from(*h).bold().fg(theme().header)),
             ))
             .column_spacing(1)
             .block(
@@ -1,4 +1,5 @@
 use crate::file::schema::SchemaInfo;
+use crate::theme::theme;
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
@@ -29,11 +30,11 @@ impl<'a> SchemaTreeComponent<'a> {
             selected_index: 0,
             scroll_offset: 0,
             title: "Schema Tree".to_string(),
-            title_color: Color::Yellow,
-            root_color: Color::LightYellow,
-            primitive_color: Color::White,
-            group_color: Color::Green,
-            selected_color: Color::Yellow,
+            title_color: theme().header,
+            root_color: theme().header,
+            primitive_color: theme().text,
+            group_color: theme().key,
+            selected_color: theme().selected_bg,
             border_style: border::ROUNDED,
             show_legend: true,
         }
@@ -77,6 +78,13 @@ impl<'a> SchemaTreeComponent<'a> {
         self.show_legend = show;
         self
     }
+
+    /// Index into the schema columns of the line at screen row `y`, for a tree drawn in
+    /// `area` scrolled by `scroll_offset` lines.
+    pub fn item_at(area: Rect, scroll_offset: usize, y: u16) -> Option<usize> {
+        let line = y.checked_sub(area.y + 1)?;
+        (line + 2 < area.height).then_some(scroll_offset + line as usize)
+    }
 }
 
 impl<'a> Widget for SchemaTreeComponent<'a> {
@@ -117,7 +125,7 @@ impl<'a> Widget for SchemaTreeComponent<'a> {
                     SchemaInfo::Primitive { display: d, .. } => {
                         let mut item = ListItem::new(d.clone()).fg(self.primitive_color);
                         if is_selected {
-                            item = item.bg(self.selected_color).fg(Color::Black);
+                            item = item.style(theme().selected().bg(self.selected_color));
                         }
                         item
                     }
@@ -153,3 +161,20 @@ impl<'a> Widget for SchemaTreeComponent<'a> {
         list.render(area, buf);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_item_at() {
+        let area = Rect::new(0, 5, 20, 10);
+        assert_eq!(None, SchemaTreeComponent::item_at(area, 3, 4));
+        // The top border
+        assert_eq!(None, SchemaTreeComponent::item_at(area, 3, 5));
+        assert_eq!(Some(3), SchemaTreeComponent::item_at(area, 3, 6));
+        assert_eq!(Some(10), SchemaTreeComponent::item_at(area, 3, 13));
+        // The bottom border
+        assert_eq!(None, SchemaTreeComponent::item_at(area, 3, 14));
+    }
+}
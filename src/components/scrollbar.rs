@@ -1,3 +1,4 @@
+use crate::theme::theme;
 use ratatui::{buffer::Buffer, layout::Rect, style::Color, widgets::Widget};
 
 pub struct ScrollbarComponent {
@@ -24,8 +25,8 @@ impl ScrollbarComponent {
             total_items,
             visible_items,
             position,
-            track_color: Color::Yellow,
-            thumb_color: Color::Gray,
+            track_color: theme().header,
+            thumb_color: theme().muted,
             track_symbol: "│",
             thumb_symbol: "█",
         }
@@ -37,8 +38,8 @@ impl ScrollbarComponent {
             total_items,
             visible_items,
             position,
-            track_color: Color::DarkGray,
-            thumb_color: Color::Gray,
+            track_color: theme().muted,
+            thumb_color: theme().muted,
             track_symbol: "─",
             thumb_symbol: "█",
         }
@@ -63,11 +64,9 @@ impl ScrollbarComponent {
 
         let thumb_size = ((self.visible_items * track_length) / self.total_items).max(1);
         let max_position = self.total_items.saturating_sub(self.visible_items);
-        let thumb_position = if max_position == 0 {
-            0
-        } else {
-            (self.position * (track_length - thumb_size)) / max_position
-        };
+        let thumb_position = (self.position * (track_length - thumb_size))
+            .checked_div(max_position)
+            .unwrap_or(0);
 
         (thumb_size, thumb_position)
     }
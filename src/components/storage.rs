@@ -0,0 +1,158 @@
+use crate::file::storage::StorageBreakdown;
+use crate::file::utils::format_size;
+use crate::theme::theme;
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Rect},
+    style::Stylize,
+    symbols::border,
+    text::Line,
+    widgets::{Block, Cell, Row, Table, Widget},
+};
+
+const BAR_WIDTH: usize = 30;
+
+/// Ranked list of the children of one schema level, with a proportional size bar.
+pub struct StorageBreakdownComponent<'a> {
+    pub breakdown: &'a StorageBreakdown,
+    pub path: &'a [usize],
+    pub selected_index: usize,
+    pub vertical_scroll: usize,
+    pub border_style: border::Set,
+}
+
+impl<'a> StorageBreakdownComponent<'a> {
+    pub fn new(breakdown: &'a StorageBreakdown, path: &'a [usize]) -> Self {
+        Self {
+            breakdown,
+            path,
+            selected_index: 0,
+            vertical_scroll: 0,
+            border_style: border::ROUNDED,
+        }
+    }
+
+    pub fn with_selected_index(mut self, index: usize) -> Self {
+        self.selected_index = index;
+        self
+    }
+
+    pub fn with_vertical_scroll(mut self, offset: usize) -> Self {
+        self.vertical_scroll = offset;
+        self
+    }
+}
+
+impl<'a> Widget for StorageBreakdownComponent<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let current = self.path.last().copied().unwrap_or(0);
+        let children = self.breakdown.ranked_children(current);
+        let largest = children
+            .first()
+            .map(|idx| self.breakdown.nodes[*idx].compressed_size)
+            .unwrap_or(0)
+            .max(1);
+
+        // Keep the selection visible
+        let visible_rows = area.height.saturating_sub(3) as usize;
+        let scroll = if self.selected_index >= self.vertical_scroll + visible_rows {
+            self.selected_index + 1 - visible_rows
+        } else {
+            self.vertical_scroll.min(self.selected_index)
+        };
+
+        let rows: Vec<Row> = children
+            .iter()
+            .enumerate()
+            .skip(scroll)
+            .take(visible_rows)
+            .map(|(i, idx)| {
+                let node = &self.breakdown.nodes[*idx];
+                let filled = (node.compressed_size as f64 / largest as f64 * BAR_WIDTH as f64)
+                    .round() as usize;
+                let bar = format!("{}{}", "█".repeat(filled), "·".repeat(BAR_WIDTH - filled));
+                let ratio = if node.compressed_size > 0 {
+                    format!(
+                        "{:.2}x",
+                        node.uncompressed_size as f64 / node.compressed_size as f64
+                    )
+                } else {
+                    "N/A".to_string()
+                };
+                let name = if node.is_group {
+                    format!("▸ {}", node.name)
+                } else {
+                    format!("  {}", node.name)
+                };
+
+                let mut row = Row::new([
+                    Cell::from(name),
+                    Cell::from(bar).fg(if node.is_group {
+                        theme().key
+                    } else {
+                        theme().title
+                    }),
+                    Cell::from(format_size(node.compressed_size)),
+                    Cell::from(format!("{:.1}%", self.breakdown.percent_of_file(*idx))),
+                    Cell::from(format!("{:.2}", self.breakdown.bytes_per_row(*idx))),
+                    Cell::from(ratio),
+                    Cell::from(node.leaf_count.to_string()),
+                ]);
+                if node.is_group {
+                    row = row.fg(theme().key);
+                }
+                if i == self.selected_index {
+                    row = row.style(theme().selected());
+                }
+                row
+            })
+            .collect();
+
+        let headers = [
+            "Column",
+            "Size vs largest",
+            "Compressed size",
+            "% of file",
+            "Bytes/row",
+            "Ratio",
+            "Leaves",
+        ];
+        let name_width = children
+            .iter()
+            .map(|idx| self.breakdown.nodes[*idx].name.len() + 2)
+            .max()
+            .unwrap_or(0)
+            .clamp(8, 40) as u16;
+
+        let title = format!(
+            " Storage: {} ({}) ",
+            self.breakdown.breadcrumb(self.path),
+            format_size(self.breakdown.nodes[current].compressed_size)
+        );
+
+        Table::new(
+            rows,
+            [
+                Constraint::Length(name_width),
+                Constraint::Length(BAR_WIDTH as u16),
+                Constraint::Length(15),
+                Constraint::Length(9),
+                Constraint::Length(10),
+                Constraint::Length(8),
+                Constraint::Length(6),
+            ],
+        )
+        .header(Row::new(
+            headers
+                .into_iter()
+                .map(|h| Cell::from(h).bold().fg(theme().header)),
+        ))
+        .column_spacing(1)
+        .block(
+            Block::bordered()
+                .title(Line::from(title).centered().bold().fg(theme().key))
+                .border_set(self.border_style),
+        )
+        .render(area, buf);
+    }
+}
@@ -0,0 +1,193 @@
+//! User configuration from `$XDG_CONFIG_HOME/parqeye/config.toml`, falling back to
+//! `~/.config`:
+//!
+//! ```toml
+//! keymap = "vim"          # "default", "vim" or "emacs"
+//! theme = "light"         # "dark", "light", "solarized", "high-contrast" or "no-color"
+//! mouse = false           # leave the mouse to the terminal, e.g. to select text
+//!
+//! [keys]                  # replaces the preset's keys for an action
+//! quit = "q"
+//! down = ["j", "ctrl+n"]
+//! top = "g g"             # keys separated by spaces are pressed in order
+//!
+//! [colors]                # overrides colors of the theme by role
+//! header = "#b58900"
+//! ```
+//!
+//! A non-empty `NO_COLOR` environment variable always selects the "no-color" theme.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::keymap::{Action, KeyMap, Preset, parse_sequence};
+use crate::theme::Theme;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    keymap: Option<String>,
+    keys: HashMap<String, Binding>,
+    theme: Option<String>,
+    colors: HashMap<String, String>,
+    mouse: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Binding {
+    One(String),
+    Many(Vec<String>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub keymap: KeyMap,
+    pub theme: Theme,
+    /// Capture mouse clicks, wheel and drags
+    pub mouse: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            keymap: KeyMap::default(),
+            theme: Theme::default(),
+            mouse: true,
+        }
+    }
+}
+
+/// Directory of the files parqeye is configured with: `$XDG_CONFIG_HOME/parqeye`,
+/// falling back to `~/.config/parqeye`.
+pub fn config_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_CONFIG_HOME", ".config")
+}
+
+/// Directory of the files parqeye keeps between sessions: `$XDG_STATE_HOME/parqeye`,
+/// falling back to `~/.local/state/parqeye`.
+pub fn state_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_STATE_HOME", ".local/state")
+}
+
+fn xdg_dir(var: &str, home_fallback: &str) -> Option<PathBuf> {
+    std::env::var_os(var)
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(home_fallback)))
+        .map(|dir| dir.join("parqeye"))
+}
+
+impl Config {
+    pub fn path() -> Option<PathBuf> {
+        config_dir().map(|dir| dir.join("config.toml"))
+    }
+
+    /// Load the config file; defaults when there is none.
+    pub fn load() -> Result<Config, String> {
+        let mut config = match Self::path() {
+            Some(path) => match std::fs::read_to_string(&path) {
+                Ok(text) => Self::parse(&text).map_err(|e| format!("{}: {e}", path.display()))?,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Config::default(),
+                Err(e) => return Err(format!("{}: {e}", path.display())),
+            },
+            None => Config::default(),
+        };
+        if std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+            config.theme = Theme::no_color();
+        }
+        Ok(config)
+    }
+
+    pub fn parse(text: &str) -> Result<Config, String> {
+        let file: ConfigFile = toml::from_str(text).map_err(|e| e.message().to_string())?;
+        let preset = match file.keymap.as_deref() {
+            None => Preset::Default,
+            Some(name) => Preset::from_name(name).ok_or_else(|| {
+                format!("unknown keymap '{name}', expected default, vim or emacs")
+            })?,
+        };
+
+        let mut keymap = KeyMap::preset(preset);
+        for (name, binding) in file.keys {
+            let action =
+                Action::from_name(&name).ok_or_else(|| format!("unknown action '{name}'"))?;
+            let keys = match binding {
+                Binding::One(key) => vec![key],
+                Binding::Many(keys) => keys,
+            };
+            let sequences = keys
+                .iter()
+                .map(|k| parse_sequence(k).map_err(|e| format!("{name}: {e}")))
+                .collect::<Result<Vec<_>, String>>()?;
+            keymap.bind(action, sequences);
+        }
+
+        let mut theme = match file.theme.as_deref() {
+            None => Theme::default(),
+            Some(name) => Theme::from_name(name).ok_or_else(|| {
+                format!(
+                    "unknown theme '{name}', expected dark, light, solarized, high-contrast or no-color"
+                )
+            })?,
+        };
+        for (role, color) in &file.colors {
+            theme.set_color(role, color)?;
+        }
+        Ok(Config {
+            keymap,
+            theme,
+            mouse: file.mouse.unwrap_or(true),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Color;
+
+    #[test]
+    fn test_parse_config() {
+        let config = Config::parse(
+            r#"
+            keymap = "emacs"
+            [keys]
+            quit = "q"
+            down = ["j", "ctrl+n"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!("q", config.keymap.hint(Action::Quit));
+        assert_eq!(2, config.keymap.bindings(Action::Down).len());
+        assert_eq!("Ctrl+P", config.keymap.hint(Action::Up));
+
+        assert_eq!(Theme::dark(), Config::parse("").unwrap().theme);
+        assert!(Config::parse("").unwrap().mouse);
+        assert!(!Config::parse("mouse = false").unwrap().mouse);
+        assert!(Config::parse("keymap = \"nano\"").is_err());
+        assert!(Config::parse("[keys]\njump = \"j\"").is_err());
+        assert!(Config::parse("[keys]\nquit = \"hyper+q\"").is_err());
+        assert!(Config::parse("colours = 1").is_err());
+    }
+
+    #[test]
+    fn test_parse_theme() {
+        let config = Config::parse(
+            r##"
+            theme = "light"
+            [colors]
+            header = "#b58900"
+            bad = "lightred"
+            "##,
+        )
+        .unwrap();
+        assert_eq!(Theme::light().title, config.theme.title);
+        assert_eq!(Color::Rgb(181, 137, 0), config.theme.header);
+        assert_eq!(Color::LightRed, config.theme.bad);
+
+        assert!(Config::parse("theme = \"pastel\"").is_err());
+        assert!(Config::parse("[colors]\nheader = \"#b5\"").is_err());
+        assert!(Config::parse("[colors]\nheadline = \"red\"").is_err());
+    }
+}
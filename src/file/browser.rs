@@ -0,0 +1,117 @@
+//! File browser for opening more Parquet files in the session: the subdirectories and
+//! `*.parquet` files of a directory, narrowed by a fuzzy search on their names.
+
+use crate::file::column_layout::fuzzy_match;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrowserEntry {
+    pub name: String,
+    pub is_dir: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct FileBrowser {
+    pub dir: PathBuf,
+    /// `..` first, then directories and Parquet files, each sorted by name
+    pub entries: Vec<BrowserEntry>,
+    pub query: String,
+    /// Line selected among the matches
+    pub selected: usize,
+}
+
+impl FileBrowser {
+    /// Browse `dir`; a directory that cannot be read is shown empty.
+    pub fn new(dir: &Path) -> Self {
+        let dir = fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf());
+        let mut entries: Vec<BrowserEntry> = fs::read_dir(&dir)
+            .map(|read| {
+                read.flatten()
+                    .filter_map(|entry| {
+                        let name = entry.file_name().to_string_lossy().to_string();
+                        let is_dir = entry.path().is_dir();
+                        let shown = if is_dir {
+                            !name.starts_with('.')
+                        } else {
+                            is_parquet(&name)
+                        };
+                        shown.then_some(BrowserEntry { name, is_dir })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.cmp(&b.name)));
+        if dir.parent().is_some() {
+            entries.insert(
+                0,
+                BrowserEntry {
+                    name: "..".to_string(),
+                    is_dir: true,
+                },
+            );
+        }
+        Self {
+            dir,
+            entries,
+            query: String::new(),
+            selected: 0,
+        }
+    }
+
+    /// Entries whose name matches the query, with the positions of the matched characters.
+    pub fn matches(&self) -> Vec<(usize, Vec<usize>)> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter_map(|(i, entry)| fuzzy_match(&self.query, &entry.name).map(|chars| (i, chars)))
+            .collect()
+    }
+
+    pub fn selected_entry(&self) -> Option<&BrowserEntry> {
+        let (entry, _) = self.matches().into_iter().nth(self.selected)?;
+        self.entries.get(entry)
+    }
+
+    /// Path of an entry; `..` is the parent directory.
+    pub fn path_of(&self, entry: &BrowserEntry) -> PathBuf {
+        if entry.name == ".." {
+            self.dir.parent().unwrap_or(&self.dir).to_path_buf()
+        } else {
+            self.dir.join(&entry.name)
+        }
+    }
+}
+
+fn is_parquet(name: &str) -> bool {
+    Path::new(name)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("parquet"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::test_path;
+
+    #[test]
+    fn test_entries() {
+        let dir = test_path("browser");
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        fs::create_dir_all(dir.join(".hidden")).unwrap();
+        for name in ["b.parquet", "a.PARQUET", "notes.txt"] {
+            fs::write(dir.join(name), "").unwrap();
+        }
+
+        let mut browser = FileBrowser::new(&dir);
+        let names: Vec<&str> = browser.entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(vec!["..", "nested", "a.PARQUET", "b.parquet"], names);
+
+        browser.query = "bp".to_string();
+        let entry = browser.selected_entry().unwrap().clone();
+        assert_eq!("b.parquet", entry.name);
+        assert_eq!(browser.dir.join("b.parquet"), browser.path_of(&entry));
+        fs::remove_dir_all(dir).ok();
+    }
+}
@@ -0,0 +1,382 @@
+//! Chart of a query result: one X column against one or more numeric Y columns.
+//! When there are more points than the chart is wide, numeric and time X axes are
+//! cut into equal-width buckets and averaged with polars.
+
+use chrono::DateTime;
+use polars::prelude::*;
+
+use crate::file::utils::format_number;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartKind {
+    Line,
+    Bar,
+    Scatter,
+}
+
+impl ChartKind {
+    pub fn next(self) -> ChartKind {
+        match self {
+            ChartKind::Line => ChartKind::Bar,
+            ChartKind::Bar => ChartKind::Scatter,
+            ChartKind::Scatter => ChartKind::Line,
+        }
+    }
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            ChartKind::Line => "line",
+            ChartKind::Bar => "bar",
+            ChartKind::Scatter => "scatter",
+        }
+    }
+}
+
+/// What to plot: column indices into the result.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartSpec {
+    pub kind: ChartKind,
+    pub x: usize,
+    pub ys: Vec<usize>,
+}
+
+impl ChartSpec {
+    /// First column as X and the first numeric column after it as Y; a line chart
+    /// for numeric and time X axes, bars for categories.
+    pub fn for_frame(df: &DataFrame) -> ChartSpec {
+        let dtypes: Vec<&DataType> = df.get_columns().iter().map(|c| c.dtype()).collect();
+        let kind = match dtypes.first().map(|d| XAxis::for_dtype(d)) {
+            Some(XAxis::Category) => ChartKind::Bar,
+            _ => ChartKind::Line,
+        };
+        let ys = dtypes
+            .iter()
+            .enumerate()
+            .skip(1)
+            .find(|(_, d)| is_plottable(d))
+            .map(|(i, _)| vec![i])
+            .unwrap_or_default();
+        ChartSpec { kind, x: 0, ys }
+    }
+
+    /// Add the column as a Y series, or remove it if it already is one.
+    pub fn toggle_y(&mut self, column: usize) {
+        match self.ys.iter().position(|&y| y == column) {
+            Some(i) => {
+                self.ys.remove(i);
+            }
+            None => self.ys.push(column),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XAxis {
+    Number,
+    /// Milliseconds since the epoch
+    Time,
+    /// Values in row order, plotted at 0, 1, 2, ...
+    Category,
+}
+
+impl XAxis {
+    fn for_dtype(dtype: &DataType) -> XAxis {
+        if matches!(dtype, DataType::Date | DataType::Datetime(_, _)) {
+            XAxis::Time
+        } else if is_plottable(dtype) {
+            XAxis::Number
+        } else {
+            XAxis::Category
+        }
+    }
+}
+
+fn is_plottable(dtype: &DataType) -> bool {
+    dtype.is_primitive_numeric() || dtype.is_decimal() || dtype.is_bool()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartSeries {
+    pub name: String,
+    pub points: Vec<(f64, f64)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ChartData {
+    pub x_name: String,
+    pub x_axis: XAxis,
+    pub series: Vec<ChartSeries>,
+    /// Labels of a category axis, by position
+    pub categories: Vec<String>,
+    /// Result rows with a non-null X value
+    pub rows: usize,
+    /// Number of buckets when the rows were averaged into buckets
+    pub buckets: Option<usize>,
+}
+
+impl ChartData {
+    pub fn from_frame(
+        df: &DataFrame,
+        spec: &ChartSpec,
+        max_points: usize,
+    ) -> Result<ChartData, String> {
+        let columns = df.get_columns();
+        let x_column = columns
+            .get(spec.x)
+            .ok_or_else(|| "No X column".to_string())?;
+        if spec.ys.is_empty() {
+            return Err("Pick one or more Y columns (space)".to_string());
+        }
+        let mut y_exprs = Vec::new();
+        let mut y_names = Vec::new();
+        for (i, &y) in spec.ys.iter().enumerate() {
+            let column = columns
+                .get(y)
+                .ok_or_else(|| format!("No column {}", y + 1))?;
+            if !is_plottable(column.dtype()) {
+                return Err(format!(
+                    "Y column {} is {}, not numeric",
+                    column.name(),
+                    column.dtype()
+                ));
+            }
+            y_exprs.push(
+                col(column.name().clone())
+                    .cast(DataType::Float64)
+                    .alias(format!("y{i}")),
+            );
+            y_names.push(column.name().to_string());
+        }
+
+        let x_name = x_column.name().to_string();
+        let x_axis = XAxis::for_dtype(x_column.dtype());
+        let x_expr = match x_axis {
+            XAxis::Time => col(x_column.name().clone())
+                .dt()
+                .timestamp(TimeUnit::Milliseconds)
+                .cast(DataType::Float64),
+            XAxis::Number => col(x_column.name().clone()).cast(DataType::Float64),
+            XAxis::Category => col(x_column.name().clone()).cast(DataType::String),
+        };
+        let mut exprs = vec![x_expr.alias("x")];
+        exprs.extend(y_exprs);
+        let mut lf = df
+            .clone()
+            .lazy()
+            .select(exprs)
+            .filter(col("x").is_not_null());
+        if x_axis == XAxis::Category {
+            lf = lf.limit(max_points as IdxSize);
+        } else {
+            lf = lf.sort(["x"], Default::default());
+        }
+        let frame = lf.collect().map_err(|e| e.to_string())?;
+        let rows = match x_axis {
+            XAxis::Category => x_column.len() - x_column.null_count(),
+            _ => frame.height(),
+        };
+
+        let mut buckets = None;
+        let frame = if x_axis != XAxis::Category && frame.height() > max_points.max(1) {
+            let n = max_points.max(1);
+            let x = frame
+                .column("x")
+                .and_then(|c| c.f64())
+                .map_err(|e| e.to_string())?;
+            let (min, max) = (x.min().unwrap_or(0.0), x.max().unwrap_or(0.0));
+            let width = ((max - min) / n as f64).max(f64::MIN_POSITIVE);
+            let bucket = ((col("x") - lit(min)) / lit(width)).cast(DataType::Int64);
+            let mut aggs = vec![col("x").mean()];
+            aggs.extend((0..y_names.len()).map(|i| col(format!("y{i}")).mean()));
+            buckets = Some(n);
+            frame
+                .lazy()
+                .with_column(
+                    when(bucket.clone().gt_eq(lit(n as i64)))
+                        .then(lit(n as i64 - 1))
+                        .otherwise(bucket)
+                        .alias("bucket"),
+                )
+                .group_by([col("bucket")])
+                .agg(aggs)
+                .sort(["bucket"], Default::default())
+                .collect()
+                .map_err(|e| e.to_string())?
+        } else {
+            frame
+        };
+
+        let (xs, categories): (Vec<f64>, Vec<String>) = match x_axis {
+            XAxis::Category => {
+                let labels = frame
+                    .column("x")
+                    .and_then(|c| c.str().cloned())
+                    .map_err(|e| e.to_string())?;
+                labels
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, s)| (i as f64, s.unwrap_or_default().to_string()))
+                    .unzip()
+            }
+            _ => (
+                frame
+                    .column("x")
+                    .and_then(|c| c.f64().cloned())
+                    .map_err(|e| e.to_string())?
+                    .into_iter()
+                    .map(|x| x.unwrap_or_default())
+                    .collect(),
+                Vec::new(),
+            ),
+        };
+        let series = y_names
+            .into_iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let ys = frame
+                    .column(&format!("y{i}"))
+                    .and_then(|c| c.f64().cloned())
+                    .map_err(|e| e.to_string())?;
+                let points = xs
+                    .iter()
+                    .zip(&ys)
+                    .filter_map(|(&x, y)| y.map(|y| (x, y)))
+                    .collect();
+                Ok(ChartSeries { name, points })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(ChartData {
+            x_name,
+            x_axis,
+            series,
+            categories,
+            rows,
+            buckets,
+        })
+    }
+
+    pub fn x_bounds(&self) -> [f64; 2] {
+        bounds(
+            self.series
+                .iter()
+                .flat_map(|s| s.points.iter().map(|p| p.0)),
+        )
+    }
+
+    /// Y range, always including zero.
+    pub fn y_bounds(&self) -> [f64; 2] {
+        let [min, max] = bounds(
+            self.series
+                .iter()
+                .flat_map(|s| s.points.iter().map(|p| p.1))
+                .chain([0.0]),
+        );
+        [min, max + (max - min) * 0.05]
+    }
+
+    /// Axis label of an X value: a date or time, a number, or the category at that position.
+    pub fn x_label(&self, x: f64) -> String {
+        match self.x_axis {
+            XAxis::Number => format_number(x),
+            XAxis::Category => self
+                .categories
+                .get(x.round().max(0.0) as usize)
+                .cloned()
+                .unwrap_or_default(),
+            XAxis::Time => {
+                let [min, max] = self.x_bounds();
+                let format = if max - min >= 2.0 * 86_400_000.0 {
+                    "%Y-%m-%d"
+                } else if max - min >= 60_000.0 {
+                    "%m-%d %H:%M"
+                } else {
+                    "%H:%M:%S%.3f"
+                };
+                DateTime::from_timestamp_millis(x as i64)
+                    .map(|t| t.format(format).to_string())
+                    .unwrap_or_default()
+            }
+        }
+    }
+}
+
+fn bounds(values: impl Iterator<Item = f64>) -> [f64; 2] {
+    let (min, max) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| {
+        (lo.min(v), hi.max(v))
+    });
+    if min > max {
+        [0.0, 1.0]
+    } else if min == max {
+        [min - 0.5, max + 0.5]
+    } else {
+        [min, max]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucketed_line_chart() {
+        let df = df!(
+            "x" => (0..100i64).rev().collect::<Vec<_>>(),
+            "y" => (0..100i64).rev().map(|v| v * 2).collect::<Vec<_>>(),
+            "name" => (0..100).map(|v| format!("n{v}")).collect::<Vec<_>>(),
+        )
+        .unwrap();
+        let spec = ChartSpec::for_frame(&df);
+        assert_eq!(ChartKind::Line, spec.kind);
+        assert_eq!(vec![1], spec.ys);
+
+        let data = ChartData::from_frame(&df, &spec, 200).unwrap();
+        assert_eq!(XAxis::Number, data.x_axis);
+        assert_eq!(None, data.buckets);
+        assert_eq!((0.0, 0.0), data.series[0].points[0]);
+        assert_eq!(100, data.series[0].points.len());
+
+        let data = ChartData::from_frame(&df, &spec, 10).unwrap();
+        assert_eq!(Some(10), data.buckets);
+        assert_eq!(100, data.rows);
+        let points = &data.series[0].points;
+        assert_eq!(10, points.len());
+        assert_eq!((4.5, 9.0), points[0]);
+        assert!(points.windows(2).all(|w| w[0].0 < w[1].0));
+
+        let spec = ChartSpec {
+            kind: ChartKind::Line,
+            x: 0,
+            ys: vec![2],
+        };
+        assert!(ChartData::from_frame(&df, &spec, 10).is_err());
+    }
+
+    #[test]
+    fn test_category_and_time_axes() {
+        let df = df!(
+            "country" => ["DE", "FR", "NL"],
+            "n" => [3u32, 1, 2],
+        )
+        .unwrap();
+        let spec = ChartSpec::for_frame(&df);
+        assert_eq!(ChartKind::Bar, spec.kind);
+        let data = ChartData::from_frame(&df, &spec, 2).unwrap();
+        assert_eq!(XAxis::Category, data.x_axis);
+        assert_eq!(3, data.rows);
+        assert_eq!(vec![(0.0, 3.0), (1.0, 1.0)], data.series[0].points);
+        assert_eq!("FR", data.x_label(1.0));
+
+        let df = df!(
+            "day" => [
+                chrono::NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+                chrono::NaiveDate::from_ymd_opt(2024, 1, 5).unwrap(),
+            ],
+            "v" => [1.5f64, 2.5],
+        )
+        .unwrap();
+        let data = ChartData::from_frame(&df, &ChartSpec::for_frame(&df), 10).unwrap();
+        assert_eq!(XAxis::Time, data.x_axis);
+        assert_eq!("2024-01-05", data.x_label(data.series[0].points[1].0));
+    }
+}
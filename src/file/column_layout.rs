@@ -0,0 +1,318 @@
+//! Order, visibility and pinning of the Visualize columns, chosen in the column
+//! chooser ("c") and saved per file in `$XDG_STATE_HOME/parqeye/column_layouts.json`,
+//! falling back to `~/.local/state`:
+//!
+//! ```text
+//! { "/data/events.parquet": { "order": ["id", "ts", ...], "hidden": ["raw"], "pinned": ["id"] } }
+//! ```
+//!
+//! Columns are saved by name, so a layout still applies after columns are added to or
+//! removed from the file.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::state_dir;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnLayout {
+    /// Data column indices in display order, hidden ones included
+    order: Vec<usize>,
+    /// Hidden columns, by data column index
+    hidden: Vec<bool>,
+    /// Number of columns at the start of `order` frozen at the left of the table
+    pinned: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SavedLayout {
+    #[serde(default)]
+    order: Vec<String>,
+    #[serde(default)]
+    hidden: Vec<String>,
+    #[serde(default)]
+    pinned: Vec<String>,
+}
+
+impl ColumnLayout {
+    /// All columns shown in file order.
+    pub fn new(columns: usize) -> Self {
+        Self {
+            order: (0..columns).collect(),
+            hidden: vec![false; columns],
+            pinned: 0,
+        }
+    }
+
+    /// Every column in display order, hidden ones included.
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// Shown columns in display order, and how many of them are pinned.
+    pub fn visible(&self) -> (Vec<usize>, usize) {
+        let shown = |c: &&usize| !self.hidden[**c];
+        let pinned = self.order[..self.pinned].iter().filter(shown).count();
+        (self.order.iter().filter(shown).copied().collect(), pinned)
+    }
+
+    pub fn visible_count(&self) -> usize {
+        self.hidden.iter().filter(|&&hidden| !hidden).count()
+    }
+
+    pub fn is_hidden(&self, column: usize) -> bool {
+        self.hidden[column]
+    }
+
+    pub fn is_pinned(&self, column: usize) -> bool {
+        self.order[..self.pinned].contains(&column)
+    }
+
+    pub fn toggle_hidden(&mut self, column: usize) {
+        self.hidden[column] = !self.hidden[column];
+    }
+
+    /// Pin a column after the pinned ones, or unpin it to the start of the others.
+    pub fn toggle_pinned(&mut self, column: usize) {
+        let Some(at) = self.order.iter().position(|&c| c == column) else {
+            return;
+        };
+        self.order.remove(at);
+        if at < self.pinned {
+            self.pinned -= 1;
+            self.order.insert(self.pinned, column);
+        } else {
+            self.order.insert(self.pinned, column);
+            self.pinned += 1;
+        }
+    }
+
+    /// Swap a column with its neighbour, staying among the pinned or the other columns.
+    pub fn move_column(&mut self, column: usize, later: bool) {
+        let Some(at) = self.order.iter().position(|&c| c == column) else {
+            return;
+        };
+        let block = if at < self.pinned {
+            0..self.pinned
+        } else {
+            self.pinned..self.order.len()
+        };
+        let to = if later { at + 1 } else { at.wrapping_sub(1) };
+        if block.contains(&to) {
+            self.order.swap(at, to);
+        }
+    }
+
+    fn from_saved(saved: &SavedLayout, names: &[String]) -> Self {
+        let index = |name: &String| names.iter().position(|n| n == name);
+        let mut layout = Self::new(names.len());
+        let mut order: Vec<usize> = Vec::new();
+        // Columns new to the file go at the end
+        for column in saved.order.iter().filter_map(index).chain(0..names.len()) {
+            if !order.contains(&column) {
+                order.push(column);
+            }
+        }
+        layout.order = order;
+        for column in saved.hidden.iter().filter_map(index) {
+            layout.hidden[column] = true;
+        }
+        for column in saved.pinned.iter().filter_map(index) {
+            if !layout.is_pinned(column) {
+                layout.toggle_pinned(column);
+            }
+        }
+        layout
+    }
+
+    fn to_saved(&self, names: &[String]) -> SavedLayout {
+        let name = |&c: &usize| names[c].clone();
+        SavedLayout {
+            order: self.order.iter().map(name).collect(),
+            hidden: self
+                .order
+                .iter()
+                .filter(|&&c| self.hidden[c])
+                .map(name)
+                .collect(),
+            pinned: self.order[..self.pinned].iter().map(name).collect(),
+        }
+    }
+
+    /// The saved layout of `dataset_path`, or all columns in file order when there is
+    /// none or the layouts file cannot be read.
+    pub fn load(dataset_path: &str, names: &[String]) -> Self {
+        match store_path() {
+            Some(store) => Self::load_from(&store, dataset_path, names),
+            None => Self::new(names.len()),
+        }
+    }
+
+    pub fn load_from(store: &Path, dataset_path: &str, names: &[String]) -> Self {
+        read_store(store)
+            .ok()
+            .and_then(|layouts| layouts.get(&store_key(dataset_path)).cloned())
+            .map(|saved| Self::from_saved(&saved, names))
+            .unwrap_or_else(|| Self::new(names.len()))
+    }
+
+    /// Save the layout of `dataset_path`; the default layout removes its entry.
+    pub fn save(&self, dataset_path: &str, names: &[String]) -> Result<(), String> {
+        let store = store_path().ok_or("No location for saved column layouts")?;
+        self.save_to(&store, dataset_path, names)
+    }
+
+    pub fn save_to(
+        &self,
+        store: &Path,
+        dataset_path: &str,
+        names: &[String],
+    ) -> Result<(), String> {
+        let mut layouts = read_store(store)?;
+        let key = store_key(dataset_path);
+        if *self == Self::new(names.len()) {
+            if layouts.remove(&key).is_none() {
+                return Ok(());
+            }
+        } else {
+            layouts.insert(key, self.to_saved(names));
+        }
+        if let Some(dir) = store.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("Cannot create {}: {e}", dir.display()))?;
+        }
+        let content = serde_json::to_string_pretty(&layouts).map_err(|e| e.to_string())?;
+        fs::write(store, content + "\n")
+            .map_err(|e| format!("Cannot write {}: {e}", store.display()))
+    }
+}
+
+fn store_path() -> Option<PathBuf> {
+    state_dir().map(|dir| dir.join("column_layouts.json"))
+}
+
+/// Layouts are keyed by absolute path, so opening a file from another directory finds
+/// its layout.
+fn store_key(dataset_path: &str) -> String {
+    fs::canonicalize(dataset_path)
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|_| dataset_path.to_string())
+}
+
+fn read_store(store: &Path) -> Result<BTreeMap<String, SavedLayout>, String> {
+    match fs::read_to_string(store) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("Cannot parse {}: {e}", store.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+        Err(e) => Err(format!("Cannot read {}: {e}", store.display())),
+    }
+}
+
+/// Column chooser overlay: a fuzzy search over the column names and the selected line.
+#[derive(Debug, Clone, Default)]
+pub struct ColumnChooser {
+    pub query: String,
+    pub selected: usize,
+}
+
+impl ColumnChooser {
+    /// Columns matching the query in display order, with the positions of the matched
+    /// characters of their names.
+    pub fn matches(&self, layout: &ColumnLayout, names: &[String]) -> Vec<(usize, Vec<usize>)> {
+        layout
+            .order()
+            .iter()
+            .filter_map(|&c| fuzzy_match(&self.query, &names[c]).map(|chars| (c, chars)))
+            .collect()
+    }
+}
+
+/// Char positions in `text` of the characters of `query`, in order but not necessarily
+/// adjacent, or `None` when they do not all appear. Smart-case like find: a query
+/// without uppercase letters ignores case.
+pub fn fuzzy_match(query: &str, text: &str) -> Option<Vec<usize>> {
+    let ignore_case = !query.chars().any(|c| c.is_uppercase());
+    let fold = |c: char| {
+        if ignore_case {
+            c.to_ascii_lowercase()
+        } else {
+            c
+        }
+    };
+    let mut positions = Vec::new();
+    let mut text = text.chars().enumerate();
+    for q in query.chars().map(fold) {
+        let (at, _) = text.by_ref().find(|&(_, t)| fold(t) == q)?;
+        positions.push(at);
+    }
+    Some(positions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::test_path;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn test_pin_hide_and_move() {
+        let mut layout = ColumnLayout::new(4);
+        layout.toggle_pinned(2);
+        layout.toggle_pinned(3);
+        assert_eq!(&[2, 3, 0, 1], layout.order());
+        layout.move_column(3, false);
+        assert_eq!(&[3, 2, 0, 1], layout.order());
+        // Moves stay among the pinned columns
+        layout.move_column(2, true);
+        assert_eq!(&[3, 2, 0, 1], layout.order());
+        layout.toggle_hidden(3);
+        assert_eq!((vec![2, 0, 1], 1), layout.visible());
+        layout.toggle_pinned(3);
+        assert_eq!(&[2, 3, 0, 1], layout.order());
+        assert!(!layout.is_pinned(3));
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = test_path("layouts");
+        fs::remove_dir_all(&dir).ok();
+        let store = dir.join("column_layouts.json");
+
+        let columns = names(&["id", "ts", "raw"]);
+        let mut layout = ColumnLayout::new(3);
+        layout.toggle_pinned(1);
+        layout.toggle_hidden(2);
+        layout.save_to(&store, "data.parquet", &columns).unwrap();
+        assert_eq!(
+            layout,
+            ColumnLayout::load_from(&store, "data.parquet", &columns)
+        );
+
+        // A column added to the file shows up at the end, a removed one is dropped
+        let columns = names(&["ts", "id", "country"]);
+        let reloaded = ColumnLayout::load_from(&store, "data.parquet", &columns);
+        assert_eq!(&[0, 1, 2], reloaded.order());
+        assert!(reloaded.is_pinned(0));
+
+        ColumnLayout::new(3)
+            .save_to(&store, "data.parquet", &columns)
+            .unwrap();
+        assert_eq!(
+            ColumnLayout::new(3),
+            ColumnLayout::load_from(&store, "data.parquet", &columns)
+        );
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_fuzzy_match() {
+        assert_eq!(Some(vec![0, 1, 6]), fuzzy_match("ust", "user_status"));
+        assert_eq!(Some(vec![]), fuzzy_match("", "id"));
+        assert!(fuzzy_match("sut", "status").is_none());
+        assert!(fuzzy_match("ID", "id").is_none());
+    }
+}
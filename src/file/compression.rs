@@ -0,0 +1,282 @@
+//! Compression "what-if" simulation for a single leaf column.
+//!
+//! A sample of the column is read back into Arrow, re-encoded with every
+//! codec / encoding combination and the resulting column chunk size and
+//! encode / decode times are compared against what the file currently uses.
+
+use bytes::Bytes;
+use parquet::arrow::ArrowWriter;
+use parquet::arrow::ProjectionMask;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::basic::{
+    BrotliLevel, Compression, Encoding, GzipLevel, Type as PhysicalType, ZstdLevel,
+};
+use parquet::file::properties::WriterProperties;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use std::fs::File;
+use std::time::{Duration, Instant};
+
+use crate::file::schema::ColumnStats;
+
+/// Maximum number of rows re-encoded per trial.
+const SAMPLE_ROWS: usize = 65_536;
+
+/// Result of re-encoding the sample with one codec / encoding combination.
+#[derive(Debug, Clone)]
+pub struct CodecTrial {
+    pub codec: String,
+    pub encoding: String,
+    pub compressed_size: u64,
+    pub encode_time: Duration,
+    pub decode_time: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct CompressionSimulation {
+    pub column: String,
+    pub sample_rows: usize,
+    /// Compressed size of the sampled rows as currently stored in the file.
+    pub sample_compressed_size: u64,
+    /// `ColumnStats::total_compressed_size` for the whole column.
+    pub current_total_compressed_size: u64,
+    /// Trials sorted by ascending compressed size.
+    pub trials: Vec<CodecTrial>,
+}
+
+impl CompressionSimulation {
+    pub fn run(
+        file_path: &str,
+        leaf_idx: usize,
+        current: &ColumnStats,
+    ) -> Result<CompressionSimulation, Box<dyn std::error::Error>> {
+        let reader = SerializedFileReader::new(File::open(file_path)?)?;
+        let md = reader.metadata();
+        let schema_descr = md.file_metadata().schema_descr();
+        let column_descr = schema_descr.column(leaf_idx);
+        let physical = column_descr.physical_type();
+
+        // Pick leading row groups until the sample is large enough
+        let mut row_groups = Vec::new();
+        let mut sample_rows = 0usize;
+        let mut sample_compressed_size = 0f64;
+        for (rg_idx, rg) in md.row_groups().iter().enumerate() {
+            if sample_rows >= SAMPLE_ROWS {
+                break;
+            }
+            let rg_rows = rg.num_rows().max(0) as usize;
+            let taken = rg_rows.min(SAMPLE_ROWS - sample_rows);
+            if rg_rows > 0 {
+                sample_compressed_size +=
+                    rg.column(leaf_idx).compressed_size() as f64 * taken as f64 / rg_rows as f64;
+            }
+            sample_rows += taken;
+            row_groups.push(rg_idx);
+        }
+
+        let batches = ParquetRecordBatchReaderBuilder::try_new(File::open(file_path)?)?
+            .with_projection(ProjectionMask::leaves(schema_descr, [leaf_idx]))
+            .with_row_groups(row_groups)
+            .with_limit(SAMPLE_ROWS)
+            .build()?
+            .collect::<Result<Vec<_>, _>>()?;
+        let arrow_schema = match batches.first() {
+            Some(batch) => batch.schema(),
+            None => return Err("column has no rows to sample".into()),
+        };
+
+        let mut trials = Vec::new();
+        for encoding in candidate_encodings(physical) {
+            for codec in candidate_codecs() {
+                let mut props = WriterProperties::builder()
+                    .set_compression(codec)
+                    .set_dictionary_enabled(encoding == Encoding::RLE_DICTIONARY);
+                if encoding != Encoding::RLE_DICTIONARY {
+                    props = props.set_encoding(encoding);
+                }
+
+                // Combinations the writer rejects for this type are skipped
+                let start = Instant::now();
+                let mut buffer = Vec::new();
+                let written =
+                    ArrowWriter::try_new(&mut buffer, arrow_schema.clone(), Some(props.build()))
+                        .and_then(|mut writer| {
+                            for batch in &batches {
+                                writer.write(batch)?;
+                            }
+                            writer.close()
+                        });
+                let encode_time = start.elapsed();
+                let written = match written {
+                    Ok(written) => written,
+                    Err(_) => continue,
+                };
+                let compressed_size = written
+                    .row_groups
+                    .iter()
+                    .flat_map(|rg| rg.columns.iter())
+                    .filter_map(|c| c.meta_data.as_ref())
+                    .map(|m| m.total_compressed_size as u64)
+                    .sum();
+
+                // Combinations the reader cannot read back are skipped too
+                let start = Instant::now();
+                let decoded = ParquetRecordBatchReaderBuilder::try_new(Bytes::from(buffer))
+                    .and_then(|builder| builder.build())
+                    .map(|mut reader| reader.all(|batch| batch.is_ok()));
+                let decode_time = start.elapsed();
+                if !matches!(decoded, Ok(true)) {
+                    continue;
+                }
+
+                trials.push(CodecTrial {
+                    codec: codec_name(codec),
+                    encoding: format!("{encoding:?}"),
+                    compressed_size,
+                    encode_time,
+                    decode_time,
+                });
+            }
+        }
+        trials.sort_by_key(|t| t.compressed_size);
+
+        Ok(CompressionSimulation {
+            column: column_descr.path().to_string(),
+            sample_rows: batches.iter().map(|b| b.num_rows()).sum(),
+            sample_compressed_size: sample_compressed_size as u64,
+            current_total_compressed_size: current.total_compressed_size,
+            trials,
+        })
+    }
+
+    /// Extrapolate a trial's sample size to the whole column.
+    pub fn estimated_total_size(&self, trial: &CodecTrial) -> u64 {
+        if self.sample_compressed_size == 0 {
+            return trial.compressed_size;
+        }
+        (trial.compressed_size as f64 / self.sample_compressed_size as f64
+            * self.current_total_compressed_size as f64) as u64
+    }
+
+    /// Size of a trial relative to the current encoding of the same sample (1.0 = unchanged).
+    pub fn relative_size(&self, trial: &CodecTrial) -> f64 {
+        if self.sample_compressed_size == 0 {
+            return 1.0;
+        }
+        trial.compressed_size as f64 / self.sample_compressed_size as f64
+    }
+}
+
+fn candidate_codecs() -> Vec<Compression> {
+    let mut codecs = vec![
+        Compression::UNCOMPRESSED,
+        Compression::SNAPPY,
+        Compression::LZ4_RAW,
+    ];
+    codecs.extend(
+        [1, 6, 9]
+            .into_iter()
+            .filter_map(|l| GzipLevel::try_new(l).ok())
+            .map(Compression::GZIP),
+    );
+    codecs.extend(
+        [1, 6, 9]
+            .into_iter()
+            .filter_map(|l| BrotliLevel::try_new(l).ok())
+            .map(Compression::BROTLI),
+    );
+    codecs.extend(
+        [1, 3, 9, 19]
+            .into_iter()
+            .filter_map(|l| ZstdLevel::try_new(l).ok())
+            .map(Compression::ZSTD),
+    );
+    codecs
+}
+
+fn candidate_encodings(physical: PhysicalType) -> Vec<Encoding> {
+    let mut encodings = vec![Encoding::PLAIN];
+    if physical != PhysicalType::BOOLEAN {
+        encodings.push(Encoding::RLE_DICTIONARY);
+    }
+    match physical {
+        PhysicalType::INT32 | PhysicalType::INT64 => {
+            encodings.push(Encoding::DELTA_BINARY_PACKED);
+            encodings.push(Encoding::BYTE_STREAM_SPLIT);
+        }
+        PhysicalType::FLOAT | PhysicalType::DOUBLE => encodings.push(Encoding::BYTE_STREAM_SPLIT),
+        PhysicalType::BYTE_ARRAY => {
+            encodings.push(Encoding::DELTA_LENGTH_BYTE_ARRAY);
+            encodings.push(Encoding::DELTA_BYTE_ARRAY);
+        }
+        PhysicalType::FIXED_LEN_BYTE_ARRAY => {
+            encodings.push(Encoding::DELTA_BYTE_ARRAY);
+            encodings.push(Encoding::BYTE_STREAM_SPLIT);
+        }
+        _ => {}
+    }
+    encodings
+}
+
+fn codec_name(codec: Compression) -> String {
+    match codec {
+        Compression::GZIP(level) => format!("GZIP({})", level.compression_level()),
+        Compression::BROTLI(level) => format!("BROTLI({})", level.compression_level()),
+        Compression::ZSTD(level) => format!("ZSTD({})", level.compression_level()),
+        other => format!("{other:?}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_candidate_encodings() {
+        assert_eq!(
+            candidate_encodings(PhysicalType::BOOLEAN),
+            vec![Encoding::PLAIN]
+        );
+        assert!(candidate_encodings(PhysicalType::INT64).contains(&Encoding::DELTA_BINARY_PACKED));
+        assert!(candidate_encodings(PhysicalType::DOUBLE).contains(&Encoding::BYTE_STREAM_SPLIT));
+        assert!(
+            candidate_encodings(PhysicalType::BYTE_ARRAY)
+                .contains(&Encoding::DELTA_LENGTH_BYTE_ARRAY)
+        );
+    }
+
+    #[test]
+    fn test_codec_name() {
+        assert_eq!(codec_name(Compression::SNAPPY), "SNAPPY");
+        assert_eq!(
+            codec_name(Compression::ZSTD(ZstdLevel::try_new(3).unwrap())),
+            "ZSTD(3)"
+        );
+        assert_eq!(candidate_codecs().len(), 13);
+    }
+
+    #[test]
+    fn test_simulation_on_alltypes_plain() {
+        let path = format!(
+            "{}/alltypes_plain.parquet",
+            crate::file::parquet_test_data(),
+        );
+        let stats = ColumnStats {
+            min: None,
+            max: None,
+            nulls: 0,
+            distinct: None,
+            total_compressed_size: 100,
+            total_uncompressed_size: 100,
+        };
+        let sim = CompressionSimulation::run(&path, 0, &stats).unwrap();
+
+        assert_eq!(sim.column, "id");
+        assert_eq!(sim.sample_rows, 8);
+        assert!(!sim.trials.is_empty());
+        assert!(
+            sim.trials
+                .windows(2)
+                .all(|w| w[0].compressed_size <= w[1].compressed_size)
+        );
+    }
+}
@@ -0,0 +1,598 @@
+//! Filter language used by `/` search in the Visualize tab.
+//!
+//! ```text
+//! country:DE                 column contains "DE"
+//! note~^\{"k"                column matches a regex
+//! price>10 AND price<=20     typed comparisons: = != > >= < <=
+//! note is null               null checks, also `is not null`
+//! country:"de"i              `i` after a quoted value ignores case
+//! ts>='2024-01-01 10:00'     single or double quotes for values with spaces
+//! "unit price">10            and for column names with spaces or operators
+//! (a:x OR b:y) NOT c:z       grouping, negation; adjacent terms are ANDed
+//! hello                      bare term: any column contains "hello"
+//! ```
+//!
+//! A parsed [`FilterExpr`] is compiled against the file's polars schema so it
+//! runs over every row of the file, not just the in-memory sample.
+
+use crate::file::profile::leaf_expr;
+use polars::prelude::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterOp {
+    Contains,
+    Regex,
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    IsNull,
+    IsNotNull,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Predicate {
+    /// `None` for a bare term, which searches every column.
+    pub column: Option<String>,
+    pub op: FilterOp,
+    pub value: String,
+    pub case_insensitive: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterExpr {
+    And(Box<FilterExpr>, Box<FilterExpr>),
+    Or(Box<FilterExpr>, Box<FilterExpr>),
+    Not(Box<FilterExpr>),
+    Predicate(Predicate),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    Is,
+    Null,
+    Op(FilterOp),
+    Word(String),
+    Quoted(String, bool),
+}
+
+const OP_CHARS: [char; 5] = [':', '~', '=', '<', '>'];
+
+fn is_word_char(c: char) -> bool {
+    !c.is_whitespace() && !matches!(c, '(' | ')' | '"') && !OP_CHARS.contains(&c) && c != '!'
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut depth = 0usize;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '(' {
+            depth += 1;
+            tokens.push(Token::LParen);
+            i += 1;
+        } else if c == ')' {
+            depth = depth.saturating_sub(1);
+            tokens.push(Token::RParen);
+            i += 1;
+        } else if c == '"' || c == '\'' {
+            let (token, next) = quoted(&chars, i)?;
+            tokens.push(token);
+            i = next;
+        } else if let Some((op, len)) = operator(&chars[i..]) {
+            tokens.push(Token::Op(op));
+            i += len;
+            while i < chars.len() && chars[i].is_whitespace() {
+                i += 1;
+            }
+            // The value runs to the next whitespace so regexes and timestamps need no quoting
+            if matches!(chars.get(i), Some('"') | Some('\'')) {
+                let (token, next) = quoted(&chars, i)?;
+                tokens.push(token);
+                i = next;
+            } else {
+                let start = i;
+                while i < chars.len() && !chars[i].is_whitespace() {
+                    i += 1;
+                }
+                let mut value: String = chars[start..i].iter().collect();
+                let mut closing = 0;
+                while depth > 0
+                    && value.ends_with(')')
+                    && value.matches(')').count() > value.matches('(').count()
+                {
+                    value.pop();
+                    closing += 1;
+                    depth -= 1;
+                }
+                if value.is_empty() {
+                    return Err("Expected a value after operator".to_string());
+                }
+                tokens.push(Token::Word(value));
+                tokens.extend(std::iter::repeat_n(Token::RParen, closing));
+            }
+        } else if c == '!' {
+            return Err("Unexpected '!'; use NOT or !=".to_string());
+        } else {
+            let start = i;
+            while i < chars.len() && is_word_char(chars[i]) {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            tokens.push(match word.to_ascii_lowercase().as_str() {
+                "and" | "&&" => Token::And,
+                "or" | "||" => Token::Or,
+                "not" => Token::Not,
+                "is" => Token::Is,
+                "null" => Token::Null,
+                _ => Token::Word(word),
+            });
+        }
+    }
+    Ok(tokens)
+}
+
+fn operator(chars: &[char]) -> Option<(FilterOp, usize)> {
+    let two: String = chars.iter().take(2).collect();
+    match two.as_str() {
+        "==" => return Some((FilterOp::Eq, 2)),
+        "!=" => return Some((FilterOp::Ne, 2)),
+        ">=" => return Some((FilterOp::Ge, 2)),
+        "<=" => return Some((FilterOp::Le, 2)),
+        _ => {}
+    }
+    match chars.first()? {
+        ':' => Some((FilterOp::Contains, 1)),
+        '~' => Some((FilterOp::Regex, 1)),
+        '=' => Some((FilterOp::Eq, 1)),
+        '>' => Some((FilterOp::Gt, 1)),
+        '<' => Some((FilterOp::Lt, 1)),
+        _ => None,
+    }
+}
+
+/// Read a single- or double-quoted string starting at `start`; a backslash escapes
+/// the quote character or a backslash and a trailing `i` marks the value as
+/// case-insensitive.
+fn quoted(chars: &[char], start: usize) -> Result<(Token, usize), String> {
+    let quote = chars[start];
+    let mut value = String::new();
+    let mut i = start + 1;
+    loop {
+        match chars.get(i) {
+            None => return Err("Unterminated quoted string".to_string()),
+            Some(c) if *c == quote => break,
+            Some('\\') if matches!(chars.get(i + 1), Some(c) if *c == quote || *c == '\\') => {
+                value.push(chars[i + 1]);
+                i += 2;
+            }
+            Some(c) => {
+                value.push(*c);
+                i += 1;
+            }
+        }
+    }
+    i += 1;
+    let case_insensitive = chars.get(i) == Some(&'i')
+        && chars
+            .get(i + 1)
+            .is_none_or(|c| !is_word_char(*c) || *c == ')');
+    if case_insensitive {
+        i += 1;
+    }
+    Ok((Token::Quoted(value, case_insensitive), i))
+}
+
+/// Double-quote `text` so that it reads back as one value.
+pub fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Write a column name so that it reads back as that column: bare when it is one word,
+/// otherwise quoted.
+pub fn column_term(name: &str) -> String {
+    let word = !name.is_empty()
+        && name.chars().all(is_word_char)
+        && !name.starts_with('\'')
+        && !matches!(
+            name.to_ascii_lowercase().as_str(),
+            "and" | "or" | "not" | "is" | "null" | "&&" | "||"
+        );
+    if word { name.to_string() } else { quote(name) }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> Result<FilterExpr, String> {
+        let mut expr = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            expr = FilterExpr::Or(Box::new(expr), Box::new(self.parse_and()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<FilterExpr, String> {
+        let mut expr = self.parse_unary()?;
+        loop {
+            match self.peek() {
+                Some(Token::And) => self.pos += 1,
+                None | Some(Token::Or) | Some(Token::RParen) => return Ok(expr),
+                // Adjacent terms are implicitly ANDed
+                Some(_) => {}
+            }
+            expr = FilterExpr::And(Box::new(expr), Box::new(self.parse_unary()?));
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<FilterExpr, String> {
+        match self.next() {
+            Some(Token::Not) => Ok(FilterExpr::Not(Box::new(self.parse_unary()?))),
+            Some(Token::LParen) => {
+                let expr = self.parse_or()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(expr),
+                    _ => Err("Missing closing parenthesis".to_string()),
+                }
+            }
+            Some(Token::Word(word)) => self.parse_predicate(word, false),
+            Some(Token::Quoted(value, case_insensitive)) => {
+                self.parse_predicate(value, case_insensitive)
+            }
+            Some(token) => Err(format!("Unexpected {}", describe(&token))),
+            None => Err("Unexpected end of filter".to_string()),
+        }
+    }
+
+    /// `first` is either a column name (followed by an operator or `is`) or a bare term.
+    fn parse_predicate(&mut self, first: String, quoted_ci: bool) -> Result<FilterExpr, String> {
+        let predicate = match self.peek() {
+            Some(Token::Op(op)) => {
+                let op = *op;
+                self.pos += 1;
+                let (value, case_insensitive) = match self.next() {
+                    Some(Token::Word(value)) => (value, false),
+                    Some(Token::Quoted(value, ci)) => (value, ci),
+                    _ => return Err(format!("Expected a value after {first}")),
+                };
+                Predicate {
+                    column: Some(first),
+                    op,
+                    value,
+                    case_insensitive,
+                }
+            }
+            Some(Token::Is) => {
+                self.pos += 1;
+                let negated = self.peek() == Some(&Token::Not);
+                if negated {
+                    self.pos += 1;
+                }
+                if self.next() != Some(Token::Null) {
+                    return Err(format!("Expected NULL after {first} IS"));
+                }
+                Predicate {
+                    column: Some(first),
+                    op: if negated {
+                        FilterOp::IsNotNull
+                    } else {
+                        FilterOp::IsNull
+                    },
+                    value: String::new(),
+                    case_insensitive: false,
+                }
+            }
+            _ => Predicate {
+                column: None,
+                op: FilterOp::Contains,
+                value: first,
+                case_insensitive: quoted_ci,
+            },
+        };
+        Ok(FilterExpr::Predicate(predicate))
+    }
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::LParen => "'('".to_string(),
+        Token::RParen => "')'".to_string(),
+        Token::And => "AND".to_string(),
+        Token::Or => "OR".to_string(),
+        Token::Not => "NOT".to_string(),
+        Token::Is => "IS".to_string(),
+        Token::Null => "NULL".to_string(),
+        Token::Op(op) => format!("operator {op:?}"),
+        Token::Word(w) | Token::Quoted(w, _) => format!("'{w}'"),
+    }
+}
+
+impl FilterExpr {
+    pub fn parse(input: &str) -> Result<FilterExpr, String> {
+        let tokens = tokenize(input)?;
+        if tokens.is_empty() {
+            return Err("Empty filter".to_string());
+        }
+        let mut parser = Parser { tokens, pos: 0 };
+        let expr = parser.parse_or()?;
+        match parser.peek() {
+            None => Ok(expr),
+            Some(token) => Err(format!("Unexpected {}", describe(token))),
+        }
+    }
+
+    /// Compile to a polars boolean expression against the file `schema`.
+    pub fn to_expr(&self, schema: &Schema) -> Result<Expr, String> {
+        match self {
+            FilterExpr::And(a, b) => Ok(a.to_expr(schema)?.and(b.to_expr(schema)?)),
+            FilterExpr::Or(a, b) => Ok(a.to_expr(schema)?.or(b.to_expr(schema)?)),
+            FilterExpr::Not(e) => Ok(e.to_expr(schema)?.not()),
+            FilterExpr::Predicate(p) => p.to_expr(schema),
+        }
+    }
+}
+
+impl Predicate {
+    fn to_expr(&self, schema: &Schema) -> Result<Expr, String> {
+        let Some(column) = &self.column else {
+            // Bare term: any scalar column contains the value
+            return schema
+                .iter()
+                .filter(|(_, dtype)| !dtype.is_nested())
+                .map(|(name, dtype)| self.text_match(col(name.clone()), dtype))
+                .reduce(|a, b| a.or(b))
+                .ok_or_else(|| "No searchable columns".to_string());
+        };
+        let (expr, dtype) = resolve_column(schema, column)?;
+        match self.op {
+            FilterOp::Contains | FilterOp::Regex => Ok(self.text_match(expr, &dtype)),
+            FilterOp::IsNull => Ok(expr.is_null()),
+            FilterOp::IsNotNull => Ok(expr.is_not_null()),
+            op => {
+                let (lhs, rhs) = if self.case_insensitive && dtype.is_string() {
+                    (expr.str().to_lowercase(), lit(self.value.to_lowercase()))
+                } else {
+                    (expr, typed_literal(column, &self.value, &dtype)?)
+                };
+                Ok(match op {
+                    FilterOp::Eq => lhs.eq(rhs),
+                    FilterOp::Ne => lhs.neq(rhs),
+                    FilterOp::Gt => lhs.gt(rhs),
+                    FilterOp::Ge => lhs.gt_eq(rhs),
+                    FilterOp::Lt => lhs.lt(rhs),
+                    _ => lhs.lt_eq(rhs),
+                })
+            }
+        }
+    }
+
+    /// Substring or regex match on the column's display text.
+    fn text_match(&self, expr: Expr, dtype: &DataType) -> Expr {
+        let text = if dtype.is_string() {
+            expr
+        } else {
+            expr.cast(DataType::String)
+        };
+        match (self.op, self.case_insensitive) {
+            (FilterOp::Regex, false) => text.str().contains(lit(self.value.clone()), true),
+            (FilterOp::Regex, true) => text
+                .str()
+                .contains(lit(format!("(?i){}", self.value)), true),
+            (_, false) => text.str().contains_literal(lit(self.value.clone())),
+            (_, true) => text
+                .str()
+                .to_lowercase()
+                .str()
+                .contains_literal(lit(self.value.to_lowercase())),
+        }
+    }
+}
+
+/// Find `name` in the schema, descending into struct fields for dotted names like `a.b`.
+pub(crate) fn resolve_column(schema: &Schema, name: &str) -> Result<(Expr, DataType), String> {
+    if let Some(dtype) = schema.get(name) {
+        return Ok((col(name), dtype.clone()));
+    }
+    let path: Vec<String> = name.split('.').map(str::to_string).collect();
+    let mut dtype = schema.get(path[0].as_str()).cloned();
+    for part in &path[1..] {
+        dtype = match dtype {
+            Some(DataType::Struct(fields)) => fields
+                .iter()
+                .find(|f| f.name().as_str() == part)
+                .map(|f| f.dtype().clone()),
+            _ => None,
+        };
+    }
+    match dtype {
+        Some(dtype) => Ok((leaf_expr(&path), dtype)),
+        None => Err(format!("Unknown column '{name}'")),
+    }
+}
+
+fn typed_literal(column: &str, value: &str, dtype: &DataType) -> Result<Expr, String> {
+    if dtype.is_integer()
+        && let Ok(v) = value.parse::<i64>()
+    {
+        Ok(lit(v))
+    } else if dtype.is_primitive_numeric() {
+        value
+            .parse::<f64>()
+            .map(lit)
+            .map_err(|_| format!("'{value}' is not a number (column {column})"))
+    } else if dtype.is_bool() {
+        value
+            .to_ascii_lowercase()
+            .parse::<bool>()
+            .map(lit)
+            .map_err(|_| format!("'{value}' is not a boolean (column {column})"))
+    } else if dtype.is_string() {
+        Ok(lit(value.to_string()))
+    } else {
+        // Dates, timestamps, decimals: let polars parse the text into the column type
+        Ok(lit(value.to_string()).strict_cast(dtype.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn predicate(column: Option<&str>, op: FilterOp, value: &str) -> FilterExpr {
+        FilterExpr::Predicate(Predicate {
+            column: column.map(str::to_string),
+            op,
+            value: value.to_string(),
+            case_insensitive: false,
+        })
+    }
+
+    fn matching_ids(query: &str) -> Vec<i64> {
+        let df = df!(
+            "id" => [1i64, 2, 3, 4],
+            "price" => [Some(5.0f64), Some(12.5), None, Some(30.0)],
+            "country" => ["DE", "us", "US", "FR"],
+        )
+        .unwrap();
+        let expr = FilterExpr::parse(query)
+            .unwrap()
+            .to_expr(df.schema())
+            .unwrap();
+        let out = df.lazy().filter(expr).collect().unwrap();
+        out.column("id")
+            .unwrap()
+            .i64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_column_predicates() {
+        assert_eq!(
+            predicate(Some("country"), FilterOp::Contains, "DE"),
+            FilterExpr::parse("country:DE").unwrap()
+        );
+        assert_eq!(
+            predicate(Some("price"), FilterOp::Ge, "10"),
+            FilterExpr::parse("price >= 10").unwrap()
+        );
+        assert_eq!(
+            predicate(Some("note"), FilterOp::IsNotNull, ""),
+            FilterExpr::parse("note IS NOT NULL").unwrap()
+        );
+        assert_eq!(
+            predicate(Some("note"), FilterOp::Regex, "^a(b|c)$"),
+            FilterExpr::parse("note~^a(b|c)$").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_bare_term_and_precedence() {
+        let expr = FilterExpr::parse("a:1 OR b:2 c:3").unwrap();
+        assert_eq!(
+            FilterExpr::Or(
+                Box::new(predicate(Some("a"), FilterOp::Contains, "1")),
+                Box::new(FilterExpr::And(
+                    Box::new(predicate(Some("b"), FilterOp::Contains, "2")),
+                    Box::new(predicate(Some("c"), FilterOp::Contains, "3")),
+                )),
+            ),
+            expr
+        );
+        assert_eq!(
+            predicate(None, FilterOp::Contains, "hello"),
+            FilterExpr::parse("hello").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_quoted_and_parenthesised() {
+        let FilterExpr::Predicate(p) = FilterExpr::parse(r#"name:"van der"i"#).unwrap() else {
+            panic!("expected a predicate");
+        };
+        assert_eq!("van der", p.value);
+        assert!(p.case_insensitive);
+        assert_eq!(
+            predicate(Some("ts"), FilterOp::Ge, "2024-01-01 10:00"),
+            FilterExpr::parse("ts >= '2024-01-01 10:00'").unwrap()
+        );
+
+        let expr = FilterExpr::parse("(a~x+) AND NOT b=1").unwrap();
+        assert_eq!(
+            FilterExpr::And(
+                Box::new(predicate(Some("a"), FilterOp::Regex, "x+")),
+                Box::new(FilterExpr::Not(Box::new(predicate(
+                    Some("b"),
+                    FilterOp::Eq,
+                    "1"
+                )))),
+            ),
+            expr
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(FilterExpr::parse("").is_err());
+        assert!(FilterExpr::parse("(a:1").is_err());
+        assert!(FilterExpr::parse("a:1 OR").is_err());
+        assert!(FilterExpr::parse("a is 1").is_err());
+        assert!(FilterExpr::parse(r#"a:"open"#).is_err());
+    }
+
+    #[test]
+    fn test_filter_evaluation() {
+        assert_eq!(vec![2, 4], matching_ids("price > 10"));
+        assert_eq!(vec![3], matching_ids("price is null"));
+        assert_eq!(vec![3], matching_ids("country:US"));
+        assert_eq!(vec![2, 3], matching_ids(r#"country:"us"i"#));
+        assert_eq!(vec![1, 4], matching_ids("country~^(DE|FR)$"));
+        assert_eq!(vec![1, 2], matching_ids("id<=2 OR NOT price>0"));
+        // Bare terms search every column, including numbers
+        assert_eq!(vec![2], matching_ids("12.5"));
+    }
+
+    #[test]
+    fn test_unknown_column_and_bad_literal() {
+        let schema = Schema::from_iter([Field::new("id".into(), DataType::Int64)]);
+        assert!(
+            FilterExpr::parse("missing:1")
+                .unwrap()
+                .to_expr(&schema)
+                .is_err()
+        );
+        assert!(
+            FilterExpr::parse("id>abc")
+                .unwrap()
+                .to_expr(&schema)
+                .is_err()
+        );
+    }
+}
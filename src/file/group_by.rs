@@ -0,0 +1,153 @@
+//! Value counts of one column over the whole file ("g" in the Visualize tab).
+
+use crate::file::filter::{column_term, quote, resolve_column};
+use polars::prelude::*;
+
+/// Most frequent values kept for display.
+const MAX_GROUPS: usize = 1_000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupCount {
+    /// `None` for the null group
+    pub value: Option<String>,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct GroupBy {
+    pub column: String,
+    /// Descending by count, at most [`MAX_GROUPS`]
+    pub groups: Vec<GroupCount>,
+    pub rows: u64,
+    pub distinct: usize,
+}
+
+impl GroupBy {
+    pub fn from_file(file_path: &str, column: &str) -> Result<GroupBy, String> {
+        let mut lf = LazyFrame::scan_parquet(PlPath::new(file_path), Default::default())
+            .map_err(|e| e.to_string())?;
+        let schema = lf.collect_schema().map_err(|e| e.to_string())?;
+        let (expr, _) = resolve_column(&schema, column)?;
+        let counts = lf
+            .group_by([expr.alias("v")])
+            .agg([len().alias("count")])
+            .collect()
+            .map_err(|e| e.to_string())?;
+        let distinct = counts.height();
+        let top = counts
+            .sort(
+                ["count"],
+                SortMultipleOptions::default()
+                    .with_order_descending(true)
+                    .with_maintain_order(true),
+            )
+            .map_err(|e| e.to_string())?
+            .head(Some(MAX_GROUPS));
+
+        let values = top.column("v").map_err(|e| e.to_string())?;
+        let counts = top
+            .column("count")
+            .and_then(|c| c.cast(&DataType::UInt64))
+            .map_err(|e| e.to_string())?;
+        let counts = counts.u64().map_err(|e| e.to_string())?;
+        let groups: Vec<GroupCount> = (0..top.height())
+            .map(|i| GroupCount {
+                value: match values.get(i) {
+                    Ok(AnyValue::Null) | Err(_) => None,
+                    Ok(value) => Some(
+                        value
+                            .get_str()
+                            .map(str::to_string)
+                            .unwrap_or_else(|| format!("{value}")),
+                    ),
+                },
+                count: counts.get(i).unwrap_or(0),
+            })
+            .collect();
+        let rows = if distinct > groups.len() {
+            let total = LazyFrame::scan_parquet(PlPath::new(file_path), Default::default())
+                .and_then(|lf| lf.select([len()]).collect())
+                .map_err(|e| e.to_string())?;
+            total
+                .get_columns()
+                .first()
+                .and_then(|c| c.get(0).ok())
+                .and_then(|v| v.extract::<u64>())
+                .unwrap_or(0)
+        } else {
+            groups.iter().map(|g| g.count).sum()
+        };
+
+        Ok(GroupBy {
+            column: column.to_string(),
+            groups,
+            rows,
+            distinct,
+        })
+    }
+
+    pub fn percent(&self, group: &GroupCount) -> f64 {
+        if self.rows == 0 {
+            0.0
+        } else {
+            group.count as f64 * 100.0 / self.rows as f64
+        }
+    }
+
+    /// Search filter matching the rows of a group, e.g. `country="DE"`.
+    pub fn filter_query(&self, group: &GroupCount) -> String {
+        match &group.value {
+            Some(value) => format!("{}={}", column_term(&self.column), quote(value)),
+            None => format!("{} is null", column_term(&self.column)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::filter::FilterExpr;
+    use crate::file::write_test_parquet;
+
+    #[test]
+    fn test_group_by_counts_and_filter() {
+        let mut df = df!(
+            "country" => [Some("DE"), Some("FR"), None, Some("DE"), Some("say \"hi\""), Some("DE")],
+            "n" => [1i64, 2, 3, 4, 5, 6],
+            "ship to: (city)" => [Some("C:\\"), None, Some("Köln"), Some("and"), Some("C:\\"), Some("C:\\")],
+        )
+        .unwrap();
+        let path = write_test_parquet("group-by", &mut df);
+
+        let group_by = GroupBy::from_file(&path, "country").unwrap();
+        assert_eq!(6, group_by.rows);
+        assert_eq!(4, group_by.distinct);
+        assert_eq!(
+            GroupCount {
+                value: Some("DE".to_string()),
+                count: 3
+            },
+            group_by.groups[0]
+        );
+        assert_eq!(50.0, group_by.percent(&group_by.groups[0]));
+
+        // Each group's filter matches exactly its rows, also for column names that need quoting
+        let schema = df.schema();
+        let city = GroupBy::from_file(&path, "ship to: (city)").unwrap();
+        assert_eq!(4, city.distinct);
+        for group_by in [&group_by, &city] {
+            for group in &group_by.groups {
+                let query = group_by.filter_query(group);
+                let expr = FilterExpr::parse(&query).unwrap().to_expr(schema).unwrap();
+                let matched = df.clone().lazy().filter(expr).collect().unwrap().height();
+                assert_eq!(group.count as usize, matched, "{query}");
+            }
+        }
+        assert_eq!(
+            r#""ship to: (city)"="C:\\""#,
+            city.filter_query(&city.groups[0])
+        );
+        assert!(GroupBy::from_file(&path, "missing").is_err());
+        std::fs::remove_file(path).ok();
+    }
+}
@@ -0,0 +1,62 @@
+//! Work that reads the whole file, run on a thread so the interface keeps
+//! answering keys, and picked up by the event loop once done.
+
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Work running in the background. Dropping the job discards its result.
+pub struct Job<T> {
+    started: Instant,
+    receiver: Receiver<Result<T, String>>,
+}
+
+impl<T: Send + 'static> Job<T> {
+    pub fn spawn(work: impl FnOnce() -> Result<T, String> + Send + 'static) -> Job<T> {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            // Nobody waits for the result once the job was dropped
+            let _ = sender.send(work());
+        });
+        Job {
+            started: Instant::now(),
+            receiver,
+        }
+    }
+}
+
+impl<T> Job<T> {
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// The result once the work has finished, without blocking.
+    pub fn poll(&self) -> Option<Result<T, String>> {
+        match self.receiver.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err("the work stopped unexpectedly".into())),
+        }
+    }
+
+    /// Block until the work has finished.
+    pub fn wait(&self) -> Result<T, String> {
+        self.receiver
+            .recv()
+            .unwrap_or_else(|_| Err("the work stopped unexpectedly".into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_job() {
+        let job = Job::spawn(|| Ok(6 * 7));
+        assert_eq!(Ok(42), job.wait());
+
+        let job: Job<()> = Job::spawn(|| panic!("lost"));
+        assert!(job.wait().is_err());
+    }
+}
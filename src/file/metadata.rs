@@ -4,7 +4,6 @@ use ratatui::widgets::Widget;
 use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Rect},
-    prelude::Color,
     style::Stylize,
     symbols::border,
     text::Line,
@@ -15,6 +14,7 @@ use std::collections::{HashMap, HashSet};
 use crate::file::Renderable;
 use crate::file::utils::commas;
 use crate::file::utils::human_readable_bytes;
+use crate::theme::theme;
 
 #[derive(Debug)]
 pub struct FileMetadata {
@@ -129,7 +129,7 @@ impl Renderable for FileMetadata {
             .into_iter()
             .map(|(k, v)| {
                 Row::new(vec![
-                    Cell::from(format!("{k:>18}")).bold().fg(Color::Blue),
+                    Cell::from(format!("{k:>18}")).bold().fg(theme().key_alt),
                     Cell::from(format!("{v:<}")),
                 ])
             })
@@ -159,7 +159,7 @@ impl Renderable for FileMetadata {
         )
         .block(
             Block::bordered()
-                .title(Line::from("File Metadata".yellow().bold()).centered())
+                .title(Line::from("File Metadata".fg(theme().header).bold()).centered())
                 .border_set(border::ROUNDED),
         );
         table.render(centered_area, buf);
@@ -1,10 +1,25 @@
+pub mod browser;
+pub mod chart;
+pub mod column_layout;
+pub mod compression;
+pub mod filter;
+pub mod group_by;
+pub mod job;
 pub mod metadata;
 pub mod parquet_ctx;
+pub mod profile;
+pub mod pruning;
+pub mod query_library;
+pub mod record;
 pub mod row_groups;
 pub mod sample_data;
 pub mod schema;
 pub mod sql;
+pub mod sql_editor;
+pub mod storage;
+pub mod summary;
 pub mod utils;
+pub mod watch;
 
 use std::{env, error::Error, path::PathBuf};
 
@@ -22,6 +37,44 @@ pub fn parquet_test_data() -> String {
     }
 }
 
+/// Path `name` in the temp directory, unique to the test process.
+#[cfg(test)]
+pub(crate) fn test_path(name: &str) -> PathBuf {
+    env::temp_dir().join(format!("parqeye-{}-{name}", std::process::id()))
+}
+
+/// Write `df` to the Parquet file `<name>.parquet` in the temp directory.
+#[cfg(test)]
+pub(crate) fn write_test_parquet(name: &str, df: &mut polars::prelude::DataFrame) -> String {
+    let path = test_path(&format!("{name}.parquet"));
+    polars::prelude::ParquetWriter::new(std::fs::File::create(&path).unwrap())
+        .finish(df)
+        .unwrap();
+    path.display().to_string()
+}
+
+/// Write Arrow `batches` to the Parquet file `<name>.parquet` in the temp directory,
+/// for files that need writer properties or types polars does not write.
+#[cfg(test)]
+pub(crate) fn write_test_batches(
+    name: &str,
+    batches: &[arrow::record_batch::RecordBatch],
+    props: Option<parquet::file::properties::WriterProperties>,
+) -> String {
+    let path = test_path(&format!("{name}.parquet"));
+    let mut writer = parquet::arrow::ArrowWriter::try_new(
+        std::fs::File::create(&path).unwrap(),
+        batches[0].schema(),
+        props,
+    )
+    .unwrap();
+    for batch in batches {
+        writer.write(batch).unwrap();
+    }
+    writer.close().unwrap();
+    path.display().to_string()
+}
+
 /// From Arrow Crate
 /// Returns a directory path for finding test data.
 ///
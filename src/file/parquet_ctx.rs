@@ -5,12 +5,14 @@ use crate::file::metadata::FileMetadata;
 use crate::file::row_groups::RowGroups;
 use crate::file::sample_data::ParquetSampleData;
 use crate::file::schema::FileSchema;
+use crate::file::storage::StorageBreakdown;
 pub struct ParquetCtx {
     pub file_path: String,
     pub metadata: FileMetadata,
     pub row_groups: RowGroups,
     pub schema: FileSchema,
     pub sample_data: ParquetSampleData,
+    pub storage: StorageBreakdown,
 }
 
 impl ParquetCtx {
@@ -23,6 +25,7 @@ impl ParquetCtx {
         // TODO: async calls?
         let metadata = FileMetadata::from_metadata(md)?;
         let schema = FileSchema::from_metadata(md)?;
+        let storage = StorageBreakdown::from_schema(&schema, metadata.num_rows);
 
         // Read sample data
         let sample_data = ParquetSampleData::read_sample_data(file_path)?;
@@ -33,6 +36,7 @@ impl ParquetCtx {
             row_groups,
             schema,
             sample_data,
+            storage,
         })
     }
 
@@ -0,0 +1,344 @@
+//! On-demand profile of a single leaf column, computed over the whole file
+//! with polars lazy scans (not the in-memory sample).
+
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use polars::prelude::*;
+use std::fs::File;
+
+const TOP_K: usize = 10;
+const HISTOGRAM_BINS: usize = 20;
+const QUANTILES: [f64; 5] = [0.01, 0.25, 0.5, 0.75, 0.99];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistogramBin {
+    pub lower: f64,
+    pub upper: f64,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ColumnProfile {
+    pub column: String,
+    pub dtype: String,
+    /// Values of the column: rows, or elements for a column in a list or map
+    pub rows: u64,
+    pub null_count: u64,
+    /// HyperLogLog estimate of the number of distinct non-null values.
+    pub approx_distinct: u64,
+    pub top_values: Vec<(String, u64)>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub mean: Option<f64>,
+    /// `(quantile, value)` pairs, only for numeric columns.
+    pub quantiles: Vec<(f64, f64)>,
+    /// Value histogram for numeric columns, string length histogram for string columns.
+    pub histogram: Vec<HistogramBin>,
+    pub histogram_label: String,
+}
+
+impl ColumnProfile {
+    pub fn from_file(
+        file_path: &str,
+        leaf_idx: usize,
+    ) -> Result<ColumnProfile, Box<dyn std::error::Error>> {
+        let reader = SerializedFileReader::new(File::open(file_path)?)?;
+        let schema_descr = reader.metadata().file_metadata().schema_descr_ptr();
+        let column_descr = schema_descr.column(leaf_idx);
+        let path = column_descr.path().parts().to_vec();
+
+        let lf = LazyFrame::scan_parquet(PlPath::new(file_path), Default::default())?;
+        let schema = lf.clone().collect_schema()?;
+        let values = leaf_values(&schema, &path)?;
+        let lf = lf.select([values.alias("v")]);
+        let dtype = lf
+            .clone()
+            .collect_schema()?
+            .get("v")
+            .cloned()
+            .unwrap_or(DataType::Null);
+        let numeric = dtype.is_primitive_numeric();
+        let string = dtype.is_string();
+
+        // Scalar statistics in a single pass
+        let mut exprs = vec![
+            len().alias("rows"),
+            col("v").null_count().alias("nulls"),
+            col("v").approx_n_unique().alias("distinct"),
+        ];
+        let measured = if string {
+            col("v").str().len_chars().cast(DataType::Float64)
+        } else {
+            col("v").cast(DataType::Float64)
+        };
+        if numeric || string {
+            exprs.push(measured.clone().min().alias("min"));
+            exprs.push(measured.clone().max().alias("max"));
+            exprs.push(measured.clone().mean().alias("mean"));
+        }
+        if numeric {
+            exprs.extend(QUANTILES.iter().enumerate().map(|(i, q)| {
+                measured
+                    .clone()
+                    .quantile(lit(*q), QuantileMethod::Linear)
+                    .alias(format!("q{i}"))
+            }));
+        }
+        let scalars = lf.clone().select(exprs).collect()?;
+        let scalar = |name: &str| -> Option<f64> {
+            scalars
+                .column(name)
+                .ok()
+                .and_then(|c| c.get(0).ok())
+                .and_then(|v| v.extract::<f64>())
+        };
+
+        let rows = scalar("rows").unwrap_or(0.0) as u64;
+        let null_count = scalar("nulls").unwrap_or(0.0) as u64;
+        // approx_n_unique counts null as a value
+        let approx_distinct =
+            (scalar("distinct").unwrap_or(0.0) as u64).saturating_sub((null_count > 0) as u64);
+        let (min, max, mean) = (scalar("min"), scalar("max"), scalar("mean"));
+        let quantiles = if numeric {
+            QUANTILES
+                .iter()
+                .enumerate()
+                .filter_map(|(i, q)| scalar(&format!("q{i}")).map(|v| (*q, v)))
+                .collect()
+        } else {
+            vec![]
+        };
+
+        let top_values = top_k(lf.clone(), TOP_K)?;
+
+        let histogram = match (min, max) {
+            (Some(min), Some(max)) if numeric || string => {
+                histogram(lf.select([measured.alias("v")]), min, max, HISTOGRAM_BINS)?
+            }
+            _ => vec![],
+        };
+
+        Ok(ColumnProfile {
+            column: column_descr.path().string(),
+            dtype: dtype.to_string(),
+            rows,
+            null_count,
+            approx_distinct,
+            top_values,
+            min,
+            max,
+            mean,
+            quantiles,
+            histogram,
+            histogram_label: if string { "String length" } else { "Value" }.to_string(),
+        })
+    }
+}
+
+/// Build a polars expression selecting a leaf column nested in structs.
+pub(crate) fn leaf_expr(path: &[String]) -> Expr {
+    let mut parts = path.iter();
+    let mut expr = col(parts.next().map(String::as_str).unwrap_or_default());
+    for part in parts {
+        expr = expr.struct_().field_by_name(part);
+    }
+    expr
+}
+
+/// Select the values of the leaf column at the Parquet `path`, one row per value: lists
+/// and maps are exploded, so a repeated leaf is profiled over all its elements.
+fn leaf_values(schema: &Schema, path: &[String]) -> Result<Expr, String> {
+    let unknown = || format!("Cannot find column {} in the file", path.join("."));
+    let root = path.first().ok_or_else(unknown)?;
+    let mut dtype = schema.get(root).ok_or_else(unknown)?.clone();
+    let mut expr = col(root.as_str());
+    let mut parts = path[1..].iter();
+    loop {
+        match dtype {
+            // Maps read as lists of key/value structs
+            DataType::List(inner) => {
+                expr = expr.explode();
+                dtype = *inner;
+            }
+            DataType::Struct(fields) => {
+                // Inside a list, the parts before the field name the list's repeated groups
+                let field = parts
+                    .by_ref()
+                    .find_map(|part| fields.iter().find(|f| f.name().as_str() == part))
+                    .ok_or_else(unknown)?;
+                expr = expr.struct_().field_by_name(field.name());
+                dtype = field.dtype().clone();
+            }
+            _ => return Ok(expr),
+        }
+    }
+}
+
+/// Most frequent non-null values of column `v`, descending by count.
+fn top_k(lf: LazyFrame, k: usize) -> PolarsResult<Vec<(String, u64)>> {
+    let df = lf
+        .filter(col("v").is_not_null())
+        .group_by([col("v")])
+        .agg([len().alias("count")])
+        .sort(
+            ["count"],
+            SortMultipleOptions::default().with_order_descending(true),
+        )
+        .limit(k as IdxSize)
+        .collect()?;
+
+    let values = df.column("v")?;
+    let counts = df.column("count")?;
+    Ok((0..df.height())
+        .map(|i| {
+            let value = values
+                .get(i)
+                .map(|v| v.str_value().to_string())
+                .unwrap_or_default();
+            let count = counts
+                .get(i)
+                .ok()
+                .and_then(|c| c.extract::<u64>())
+                .unwrap_or(0);
+            (value, count)
+        })
+        .collect())
+}
+
+/// Equal-width histogram of the float column `v` between `min` and `max`.
+fn histogram(lf: LazyFrame, min: f64, max: f64, bins: usize) -> PolarsResult<Vec<HistogramBin>> {
+    let bins = if max > min { bins } else { 1 };
+    let width = if max > min {
+        (max - min) / bins as f64
+    } else {
+        1.0
+    };
+
+    // (v - min) is never negative, so the integer cast floors
+    let bucket = ((col("v") - lit(min)) / lit(width)).cast(DataType::Int64);
+    let last = lit(bins as i64 - 1);
+    let df = lf
+        .filter(col("v").is_not_null())
+        .select([when(bucket.clone().gt(last.clone()))
+            .then(last)
+            .otherwise(bucket)
+            .alias("bucket")])
+        .group_by([col("bucket")])
+        .agg([len().alias("count")])
+        .collect()?;
+
+    let mut out: Vec<HistogramBin> = (0..bins)
+        .map(|i| HistogramBin {
+            lower: min + i as f64 * width,
+            upper: min + (i + 1) as f64 * width,
+            count: 0,
+        })
+        .collect();
+    let buckets = df.column("bucket")?;
+    let counts = df.column("count")?;
+    for i in 0..df.height() {
+        let bucket = buckets.get(i).ok().and_then(|b| b.extract::<usize>());
+        let count = counts.get(i).ok().and_then(|c| c.extract::<u64>());
+        if let (Some(bucket), Some(count)) = (bucket, count)
+            && let Some(bin) = out.get_mut(bucket)
+        {
+            bin.count = count;
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_bins() {
+        let df = df!("v" => [0.0f64, 1.0, 2.0, 3.0, 4.0, 10.0]).unwrap();
+        let bins = histogram(df.lazy(), 0.0, 10.0, 5).unwrap();
+
+        assert_eq!(5, bins.len());
+        assert_eq!(
+            vec![2, 2, 1, 0, 1],
+            bins.iter().map(|b| b.count).collect::<Vec<_>>()
+        );
+        assert_eq!(2.0, bins[0].upper);
+    }
+
+    #[test]
+    fn test_histogram_constant_column() {
+        let df = df!("v" => [5.0f64, 5.0, 5.0]).unwrap();
+        let bins = histogram(df.lazy(), 5.0, 5.0, 20).unwrap();
+
+        assert_eq!(1, bins.len());
+        assert_eq!(3, bins[0].count);
+    }
+
+    #[test]
+    fn test_top_k() {
+        let df = df!("v" => [Some("a"), Some("b"), Some("a"), None, Some("c"), Some("a")]).unwrap();
+        let top = top_k(df.lazy(), 2).unwrap();
+
+        assert_eq!(2, top.len());
+        assert_eq!(("a".to_string(), 3), top[0]);
+    }
+
+    #[test]
+    fn test_profile_alltypes_plain() {
+        let path = format!(
+            "{}/alltypes_plain.parquet",
+            crate::file::parquet_test_data(),
+        );
+        let profile = ColumnProfile::from_file(&path, 0).unwrap();
+
+        assert_eq!("id", profile.column);
+        assert_eq!(8, profile.rows);
+        assert_eq!(0, profile.null_count);
+        assert_eq!(Some(0.0), profile.min);
+        assert_eq!(Some(7.0), profile.max);
+    }
+    #[test]
+    fn test_profile_list_and_map() {
+        use arrow::array::{Int32Builder, ListBuilder, MapBuilder, StringBuilder};
+        use arrow::record_batch::RecordBatch;
+        use std::sync::Arc;
+
+        let mut list = ListBuilder::new(Int32Builder::new());
+        list.append_value([Some(1), Some(2)]);
+        list.append_value([Some(3), None, Some(4)]);
+        let mut map = MapBuilder::new(None, StringBuilder::new(), Int32Builder::new());
+        map.keys().append_value("a");
+        map.values().append_value(10);
+        map.append(true).unwrap();
+        map.keys().append_value("a");
+        map.values().append_value(20);
+        map.keys().append_value("b");
+        map.values().append_null();
+        map.append(true).unwrap();
+        let batch = RecordBatch::try_from_iter([
+            ("numbers", Arc::new(list.finish()) as _),
+            ("tags", Arc::new(map.finish()) as _),
+        ])
+        .unwrap();
+
+        let path = crate::file::write_test_batches("profile", &[batch], None);
+
+        let numbers = ColumnProfile::from_file(&path, 0).unwrap();
+        assert_eq!("numbers.list.item", numbers.column);
+        assert_eq!(5, numbers.rows);
+        assert_eq!(1, numbers.null_count);
+        assert_eq!(Some(4.0), numbers.max);
+
+        let keys = ColumnProfile::from_file(&path, 1).unwrap();
+        assert_eq!(3, keys.rows);
+        assert_eq!(
+            vec![("a".to_string(), 2), ("b".to_string(), 1)],
+            keys.top_values
+        );
+
+        let values = ColumnProfile::from_file(&path, 2).unwrap();
+        assert_eq!(1, values.null_count);
+        assert_eq!(Some(10.0), values.min);
+        assert_eq!(Some(20.0), values.max);
+        std::fs::remove_file(&path).ok();
+    }
+}
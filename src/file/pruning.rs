@@ -0,0 +1,772 @@
+//! Predicate pushdown preview: which row groups and pages a reader could skip
+//! for a filter, judged from column chunk statistics, bloom filters and the
+//! page index (column index + offset index), the way query engines prune.
+
+use chrono::{NaiveDate, NaiveDateTime};
+use parquet::basic::{ConvertedType, LogicalType, TimeUnit, Type as PhysicalType};
+use parquet::bloom_filter::Sbbf;
+use parquet::file::metadata::{ParquetMetaData, RowGroupMetaData};
+use parquet::file::page_index::index::Index;
+use parquet::file::properties::ReaderProperties;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::file::serialized_reader::ReadOptionsBuilder;
+use parquet::file::statistics::Statistics;
+use parquet::schema::types::ColumnDescriptor;
+use std::cmp::Ordering;
+use std::fs::File;
+
+use crate::file::filter::{FilterExpr, FilterOp};
+
+/// Half-open row ranges `[start, end)` within a row group, sorted and disjoint.
+type RowRanges = Vec<(usize, usize)>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PruneReason {
+    Statistics,
+    BloomFilter,
+    PageIndex,
+}
+
+impl PruneReason {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            PruneReason::Statistics => "statistics",
+            PruneReason::BloomFilter => "bloom filter",
+            PruneReason::PageIndex => "page index",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RowGroupPruning {
+    pub idx: usize,
+    pub rows: usize,
+    pub pruned_by: Option<PruneReason>,
+    /// Rows that still have to be read after page pruning
+    pub selected_rows: RowRanges,
+    /// Data pages over all column chunks, only known with an offset index
+    pub pages_total: usize,
+    pub pages_read: usize,
+    pub bytes_total: u64,
+    pub bytes_read: u64,
+}
+
+impl RowGroupPruning {
+    pub fn rows_read(&self) -> usize {
+        self.selected_rows.iter().map(|(s, e)| e - s).sum()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PruningReport {
+    pub filter: String,
+    pub row_groups: Vec<RowGroupPruning>,
+    pub has_page_index: bool,
+}
+
+impl PruningReport {
+    pub fn analyze(
+        file_path: &str,
+        filter: &str,
+    ) -> Result<PruningReport, Box<dyn std::error::Error>> {
+        let expr = FilterExpr::parse(filter)?;
+        let options = ReadOptionsBuilder::new()
+            .with_page_index()
+            .with_reader_properties(
+                ReaderProperties::builder()
+                    .set_read_bloom_filter(true)
+                    .build(),
+            )
+            .build();
+        let reader = SerializedFileReader::new_with_options(File::open(file_path)?, options)?;
+        let metadata = reader.metadata();
+        let node = Node::build(&expr, false, metadata)?;
+        let has_page_index = metadata.offset_index().is_some();
+
+        let row_groups = (0..metadata.num_row_groups())
+            .map(|rg_idx| {
+                let row_group = reader.get_row_group(rg_idx)?;
+                let ctx = RowGroupCtx {
+                    metadata,
+                    rg_idx,
+                    rg: metadata.row_group(rg_idx),
+                    bloom: &|col| row_group.get_column_bloom_filter(col),
+                };
+                Ok(ctx.prune(&node))
+            })
+            .collect::<Result<Vec<_>, parquet::errors::ParquetError>>()?;
+
+        Ok(PruningReport {
+            filter: filter.to_string(),
+            row_groups,
+            has_page_index,
+        })
+    }
+
+    pub fn row_groups_pruned(&self) -> usize {
+        self.row_groups
+            .iter()
+            .filter(|rg| rg.pruned_by.is_some())
+            .count()
+    }
+
+    pub fn pages_total(&self) -> usize {
+        self.row_groups.iter().map(|rg| rg.pages_total).sum()
+    }
+
+    pub fn pages_read(&self) -> usize {
+        self.row_groups.iter().map(|rg| rg.pages_read).sum()
+    }
+
+    pub fn bytes_total(&self) -> u64 {
+        self.row_groups.iter().map(|rg| rg.bytes_total).sum()
+    }
+
+    pub fn bytes_read(&self) -> u64 {
+        self.row_groups.iter().map(|rg| rg.bytes_read).sum()
+    }
+
+    /// Share of the column chunk bytes that would be read, 0.0 - 1.0.
+    pub fn read_fraction(&self) -> f64 {
+        let total = self.bytes_total();
+        if total == 0 {
+            return 0.0;
+        }
+        self.bytes_read() as f64 / total as f64
+    }
+}
+
+/// A value from statistics or a filter literal, in the column's physical representation.
+#[derive(Debug, Clone, PartialEq)]
+enum StatValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Bytes(Vec<u8>),
+}
+
+impl StatValue {
+    fn compare(&self, other: &StatValue) -> Option<Ordering> {
+        match (self, other) {
+            (StatValue::Bool(a), StatValue::Bool(b)) => a.partial_cmp(b),
+            (StatValue::Int(a), StatValue::Int(b)) => a.partial_cmp(b),
+            (StatValue::Float(a), StatValue::Float(b)) => a.partial_cmp(b),
+            (StatValue::Int(a), StatValue::Float(b)) => (*a as f64).partial_cmp(b),
+            (StatValue::Float(a), StatValue::Int(b)) => a.partial_cmp(&(*b as f64)),
+            (StatValue::Bytes(a), StatValue::Bytes(b)) => a.partial_cmp(b),
+            _ => None,
+        }
+    }
+}
+
+/// Min / max / null count of a column chunk or page.
+struct Bounds {
+    min: Option<StatValue>,
+    max: Option<StatValue>,
+    null_count: Option<u64>,
+    rows: usize,
+}
+
+impl Bounds {
+    fn from_statistics(stats: &Statistics, rows: usize) -> Bounds {
+        let (min, max) = match stats {
+            Statistics::Boolean(s) => (
+                s.min_opt().map(|v| StatValue::Bool(*v)),
+                s.max_opt().map(|v| StatValue::Bool(*v)),
+            ),
+            Statistics::Int32(s) => (
+                s.min_opt().map(|v| StatValue::Int(*v as i64)),
+                s.max_opt().map(|v| StatValue::Int(*v as i64)),
+            ),
+            Statistics::Int64(s) => (
+                s.min_opt().map(|v| StatValue::Int(*v)),
+                s.max_opt().map(|v| StatValue::Int(*v)),
+            ),
+            Statistics::Float(s) => (
+                s.min_opt().map(|v| StatValue::Float(*v as f64)),
+                s.max_opt().map(|v| StatValue::Float(*v as f64)),
+            ),
+            Statistics::Double(s) => (
+                s.min_opt().map(|v| StatValue::Float(*v)),
+                s.max_opt().map(|v| StatValue::Float(*v)),
+            ),
+            Statistics::ByteArray(s) => (
+                s.min_opt().map(|v| StatValue::Bytes(v.data().to_vec())),
+                s.max_opt().map(|v| StatValue::Bytes(v.data().to_vec())),
+            ),
+            Statistics::FixedLenByteArray(s) => (
+                s.min_opt().map(|v| StatValue::Bytes(v.data().to_vec())),
+                s.max_opt().map(|v| StatValue::Bytes(v.data().to_vec())),
+            ),
+            Statistics::Int96(_) => (None, None),
+        };
+        Bounds {
+            min,
+            max,
+            null_count: stats.null_count_opt(),
+            rows,
+        }
+    }
+
+    fn from_page_index(index: &Index, page: usize, rows: usize) -> Option<Bounds> {
+        macro_rules! page_bounds {
+            ($native:expr, $convert:expr) => {
+                $native.indexes.get(page).map(|p| Bounds {
+                    min: p.min.as_ref().map($convert),
+                    max: p.max.as_ref().map($convert),
+                    null_count: p.null_count.map(|n| n as u64),
+                    rows,
+                })
+            };
+        }
+        match index {
+            Index::BOOLEAN(n) => page_bounds!(n, |v: &bool| StatValue::Bool(*v)),
+            Index::INT32(n) => page_bounds!(n, |v: &i32| StatValue::Int(*v as i64)),
+            Index::INT64(n) => page_bounds!(n, |v: &i64| StatValue::Int(*v)),
+            Index::FLOAT(n) => page_bounds!(n, |v: &f32| StatValue::Float(*v as f64)),
+            Index::DOUBLE(n) => page_bounds!(n, |v: &f64| StatValue::Float(*v)),
+            Index::BYTE_ARRAY(n) => page_bounds!(n, |v: &parquet::data_type::ByteArray| {
+                StatValue::Bytes(v.data().to_vec())
+            }),
+            Index::FIXED_LEN_BYTE_ARRAY(n) => {
+                page_bounds!(n, |v: &parquet::data_type::FixedLenByteArray| {
+                    StatValue::Bytes(v.data().to_vec())
+                })
+            }
+            Index::NONE | Index::INT96(_) => None,
+        }
+    }
+
+    /// Whether any row within these bounds could satisfy `op value`.
+    fn may_match(&self, op: FilterOp, value: Option<&StatValue>) -> bool {
+        let all_null = self.null_count == Some(self.rows as u64);
+        match op {
+            FilterOp::IsNull => self.null_count.is_none_or(|n| n > 0),
+            FilterOp::IsNotNull => !all_null,
+            // Comparisons never match nulls
+            _ if all_null => false,
+            _ => {
+                let (Some(v), Some(min), Some(max)) = (value, &self.min, &self.max) else {
+                    return true;
+                };
+                let (Some(v_min), Some(v_max)) = (v.compare(min), v.compare(max)) else {
+                    return true;
+                };
+                match op {
+                    FilterOp::Eq => v_min.is_ge() && v_max.is_le(),
+                    FilterOp::Ne => !(v_min.is_eq() && v_max.is_eq()),
+                    FilterOp::Gt => v_max.is_lt(),
+                    FilterOp::Ge => v_max.is_le(),
+                    FilterOp::Lt => v_min.is_gt(),
+                    FilterOp::Le => v_min.is_ge(),
+                    _ => true,
+                }
+            }
+        }
+    }
+}
+
+/// Filter in negation normal form with columns resolved to leaf indices.
+/// Anything statistics cannot decide (substring, regex, bare terms) is `Unknown`.
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    And(Vec<Node>),
+    Or(Vec<Node>),
+    Leaf {
+        column: usize,
+        op: FilterOp,
+        value: Option<StatValue>,
+    },
+    Unknown,
+}
+
+impl Node {
+    fn build(expr: &FilterExpr, negated: bool, metadata: &ParquetMetaData) -> Result<Node, String> {
+        match (expr, negated) {
+            (FilterExpr::And(a, b), false) | (FilterExpr::Or(a, b), true) => Ok(Node::And(vec![
+                Node::build(a, negated, metadata)?,
+                Node::build(b, negated, metadata)?,
+            ])),
+            (FilterExpr::Or(a, b), false) | (FilterExpr::And(a, b), true) => Ok(Node::Or(vec![
+                Node::build(a, negated, metadata)?,
+                Node::build(b, negated, metadata)?,
+            ])),
+            (FilterExpr::Not(e), _) => Node::build(e, !negated, metadata),
+            (FilterExpr::Predicate(p), _) => {
+                let Some(name) = &p.column else {
+                    return Ok(Node::Unknown);
+                };
+                let schema = metadata.file_metadata().schema_descr();
+                let column = (0..schema.num_columns())
+                    .find(|i| schema.column(*i).path().string() == *name)
+                    .ok_or_else(|| format!("Unknown column '{name}'"))?;
+                let op = if negated { negate(p.op) } else { Some(p.op) };
+                let op = match op {
+                    Some(op) if !matches!(op, FilterOp::Contains | FilterOp::Regex) => op,
+                    _ => return Ok(Node::Unknown),
+                };
+                if matches!(op, FilterOp::IsNull | FilterOp::IsNotNull) {
+                    return Ok(Node::Leaf {
+                        column,
+                        op,
+                        value: None,
+                    });
+                }
+                // Statistics are case-sensitive, as is a literal that cannot be typed
+                match literal(&schema.column(column), &p.value) {
+                    Some(value) if !p.case_insensitive => Ok(Node::Leaf {
+                        column,
+                        op,
+                        value: Some(value),
+                    }),
+                    _ => Ok(Node::Unknown),
+                }
+            }
+        }
+    }
+}
+
+fn negate(op: FilterOp) -> Option<FilterOp> {
+    match op {
+        FilterOp::Eq => Some(FilterOp::Ne),
+        FilterOp::Ne => Some(FilterOp::Eq),
+        FilterOp::Gt => Some(FilterOp::Le),
+        FilterOp::Ge => Some(FilterOp::Lt),
+        FilterOp::Lt => Some(FilterOp::Ge),
+        FilterOp::Le => Some(FilterOp::Gt),
+        FilterOp::IsNull => Some(FilterOp::IsNotNull),
+        FilterOp::IsNotNull => Some(FilterOp::IsNull),
+        FilterOp::Contains | FilterOp::Regex => None,
+    }
+}
+
+/// Convert a filter literal to the physical representation used by the column's statistics.
+fn literal(column: &ColumnDescriptor, value: &str) -> Option<StatValue> {
+    let logical = column.logical_type();
+    if matches!(
+        logical,
+        Some(LogicalType::Decimal { .. })
+            | Some(LogicalType::Integer {
+                is_signed: false,
+                ..
+            })
+    ) || column.converted_type() == ConvertedType::DECIMAL
+    {
+        // Different sort orders or encodings than the plain physical value
+        return None;
+    }
+    match column.physical_type() {
+        PhysicalType::BOOLEAN => value.to_ascii_lowercase().parse().ok().map(StatValue::Bool),
+        PhysicalType::INT32 if logical == Some(LogicalType::Date) => {
+            let epoch = NaiveDate::from_ymd_opt(1970, 1, 1)?;
+            let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+            Some(StatValue::Int((date - epoch).num_days()))
+        }
+        PhysicalType::INT64 => match logical {
+            Some(LogicalType::Timestamp { unit, .. }) => {
+                let ts = parse_datetime(value)?.and_utc();
+                match unit {
+                    TimeUnit::MILLIS(_) => Some(ts.timestamp_millis()),
+                    TimeUnit::MICROS(_) => Some(ts.timestamp_micros()),
+                    TimeUnit::NANOS(_) => ts.timestamp_nanos_opt(),
+                }
+                .map(StatValue::Int)
+            }
+            Some(LogicalType::Time { .. }) => None,
+            _ => value.parse().ok().map(StatValue::Int),
+        },
+        PhysicalType::INT32 => match logical {
+            Some(LogicalType::Time { .. }) => None,
+            _ => value.parse().ok().map(StatValue::Int),
+        },
+        PhysicalType::FLOAT | PhysicalType::DOUBLE => value.parse().ok().map(StatValue::Float),
+        PhysicalType::BYTE_ARRAY | PhysicalType::FIXED_LEN_BYTE_ARRAY => {
+            // Only text is stored as the bytes of the literal; binary, UUIDs and the
+            // like are not
+            let text = matches!(
+                logical,
+                Some(LogicalType::String | LogicalType::Enum | LogicalType::Json)
+            ) || column.converted_type() == ConvertedType::UTF8;
+            text.then(|| StatValue::Bytes(value.as_bytes().to_vec()))
+        }
+        PhysicalType::INT96 => None,
+    }
+}
+
+fn parse_datetime(value: &str) -> Option<NaiveDateTime> {
+    const FORMATS: [&str; 4] = [
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M",
+    ];
+    FORMATS
+        .iter()
+        .find_map(|f| NaiveDateTime::parse_from_str(value, f).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+        })
+}
+
+fn union(mut ranges: RowRanges) -> RowRanges {
+    ranges.retain(|(s, e)| s < e);
+    ranges.sort();
+    let mut out: RowRanges = Vec::with_capacity(ranges.len());
+    for (start, end) in ranges {
+        match out.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => out.push((start, end)),
+        }
+    }
+    out
+}
+
+fn intersect(a: &[(usize, usize)], b: &[(usize, usize)]) -> RowRanges {
+    let mut out = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        let start = a[i].0.max(b[j].0);
+        let end = a[i].1.min(b[j].1);
+        if start < end {
+            out.push((start, end));
+        }
+        if a[i].1 < b[j].1 {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    out
+}
+
+/// How much of the file's metadata a pruning pass may use; each level includes the previous.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Level {
+    Statistics,
+    BloomFilter,
+    PageIndex,
+}
+
+struct RowGroupCtx<'a> {
+    metadata: &'a ParquetMetaData,
+    rg_idx: usize,
+    rg: &'a RowGroupMetaData,
+    bloom: &'a dyn Fn(usize) -> Option<&'a Sbbf>,
+}
+
+impl RowGroupCtx<'_> {
+    fn rows(&self) -> usize {
+        self.rg.num_rows() as usize
+    }
+
+    fn prune(&self, node: &Node) -> RowGroupPruning {
+        let mut pruned_by = None;
+        let mut selected = vec![(0, self.rows())];
+        for (level, reason) in [
+            (Level::Statistics, PruneReason::Statistics),
+            (Level::BloomFilter, PruneReason::BloomFilter),
+            (Level::PageIndex, PruneReason::PageIndex),
+        ] {
+            selected = self.eval(node, level);
+            if selected.is_empty() {
+                pruned_by = Some(reason);
+                break;
+            }
+        }
+
+        let mut pruning = RowGroupPruning {
+            idx: self.rg_idx,
+            rows: self.rows(),
+            pruned_by,
+            selected_rows: vec![],
+            pages_total: 0,
+            pages_read: 0,
+            bytes_total: 0,
+            bytes_read: 0,
+        };
+        let full = selected == [(0, self.rows())];
+        for col in 0..self.rg.num_columns() {
+            let chunk = self.rg.column(col);
+            let chunk_size = chunk.compressed_size() as u64;
+            pruning.bytes_total += chunk_size;
+
+            let Some(pages) = self.page_rows(col) else {
+                if !selected.is_empty() {
+                    pruning.bytes_read += chunk_size;
+                }
+                continue;
+            };
+            pruning.pages_total += pages.len();
+            let locations = self.offset_index(col).unwrap_or_default();
+            let read: Vec<_> = pages
+                .iter()
+                .zip(locations)
+                .filter(|(range, _)| !intersect(&[**range], &selected).is_empty())
+                .collect();
+            pruning.pages_read += read.len();
+            if full {
+                pruning.bytes_read += chunk_size;
+            } else if !read.is_empty() {
+                // Dictionary page, if any, sits before the first data page
+                let chunk_start = chunk
+                    .dictionary_page_offset()
+                    .unwrap_or(chunk.data_page_offset());
+                let dictionary = locations
+                    .first()
+                    .map_or(0, |first| (first.offset - chunk_start).max(0) as u64);
+                pruning.bytes_read += dictionary
+                    + read
+                        .iter()
+                        .map(|(_, loc)| loc.compressed_page_size as u64)
+                        .sum::<u64>();
+            }
+        }
+        pruning.selected_rows = selected;
+        pruning
+    }
+
+    fn eval(&self, node: &Node, level: Level) -> RowRanges {
+        match node {
+            Node::And(children) => children
+                .iter()
+                .map(|c| self.eval(c, level))
+                .reduce(|a, b| intersect(&a, &b))
+                .unwrap_or_default(),
+            Node::Or(children) => {
+                union(children.iter().flat_map(|c| self.eval(c, level)).collect())
+            }
+            Node::Unknown => vec![(0, self.rows())],
+            Node::Leaf { column, op, value } => self.eval_leaf(*column, *op, value.as_ref(), level),
+        }
+    }
+
+    fn eval_leaf(
+        &self,
+        column: usize,
+        op: FilterOp,
+        value: Option<&StatValue>,
+        level: Level,
+    ) -> RowRanges {
+        let chunk = self.rg.column(column);
+        if let Some(stats) = chunk.statistics()
+            && !Bounds::from_statistics(stats, self.rows()).may_match(op, value)
+        {
+            return vec![];
+        }
+        if level >= Level::BloomFilter
+            && op == FilterOp::Eq
+            && let (Some(bloom), Some(value)) = ((self.bloom)(column), value)
+            && !bloom_contains(bloom, chunk.column_descr().physical_type(), value)
+        {
+            return vec![];
+        }
+        if level >= Level::PageIndex
+            && let (Some(pages), Some(index)) = (self.page_rows(column), self.column_index(column))
+        {
+            return union(
+                pages
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(page, (start, end))| {
+                        Bounds::from_page_index(index, *page, end - start)
+                            .is_none_or(|bounds| bounds.may_match(op, value))
+                    })
+                    .map(|(_, range)| range)
+                    .collect(),
+            );
+        }
+        vec![(0, self.rows())]
+    }
+
+    fn offset_index(&self, column: usize) -> Option<&[parquet::format::PageLocation]> {
+        self.metadata
+            .offset_index()?
+            .get(self.rg_idx)?
+            .get(column)
+            .map(|oi| oi.page_locations().as_slice())
+    }
+
+    fn column_index(&self, column: usize) -> Option<&Index> {
+        self.metadata
+            .column_index()?
+            .get(self.rg_idx)?
+            .get(column)
+            .filter(|index| !matches!(index, Index::NONE))
+    }
+
+    /// Row range of every data page in a column chunk, from the offset index.
+    fn page_rows(&self, column: usize) -> Option<RowRanges> {
+        let locations = self.offset_index(column)?;
+        Some(
+            locations
+                .iter()
+                .enumerate()
+                .map(|(i, loc)| {
+                    let end = locations
+                        .get(i + 1)
+                        .map_or(self.rows(), |next| next.first_row_index as usize);
+                    (loc.first_row_index as usize, end)
+                })
+                .collect(),
+        )
+    }
+}
+
+fn bloom_contains(bloom: &Sbbf, physical: PhysicalType, value: &StatValue) -> bool {
+    match (physical, value) {
+        (PhysicalType::INT32, StatValue::Int(v)) => bloom.check(&(*v as i32)),
+        (PhysicalType::INT64, StatValue::Int(v)) => bloom.check(v),
+        (PhysicalType::FLOAT, StatValue::Float(v)) => bloom.check(&(*v as f32)),
+        (PhysicalType::DOUBLE, StatValue::Float(v)) => bloom.check(v),
+        (PhysicalType::BYTE_ARRAY | PhysicalType::FIXED_LEN_BYTE_ARRAY, StatValue::Bytes(v)) => {
+            bloom.check(v)
+        }
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Int64Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::record_batch::RecordBatch;
+    use parquet::file::properties::{EnabledStatistics, WriterProperties};
+    use parquet::schema::types::{ColumnPath, Type};
+    use std::sync::Arc;
+
+    use crate::file::write_test_batches;
+
+    /// Two row groups of 100 rows with ids 0..100 and 100..200 and countries alternating
+    /// between "DE" and "US"; pages hold 10 rows.
+    fn write_row_groups() -> String {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("country", DataType::Utf8, false),
+        ]));
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(100)
+            .set_data_page_row_count_limit(10)
+            .set_write_batch_size(10)
+            .set_statistics_enabled(EnabledStatistics::Page)
+            .set_bloom_filter_enabled(true)
+            .build();
+        let batches: Vec<RecordBatch> = [0, 100]
+            .into_iter()
+            .map(|start| {
+                RecordBatch::try_new(
+                    schema.clone(),
+                    vec![
+                        Arc::new(Int64Array::from_iter_values(start..start + 100)),
+                        Arc::new(StringArray::from_iter_values(
+                            (0..100).map(|i| if i % 2 == 0 { "DE" } else { "US" }),
+                        )),
+                    ],
+                )
+                .unwrap()
+            })
+            .collect();
+        write_test_batches("pruning", &batches, Some(props))
+    }
+
+    #[test]
+    fn test_range_set_operations() {
+        assert_eq!(vec![(0, 5), (6, 9)], union(vec![(6, 9), (0, 3), (2, 5)]));
+        assert_eq!(
+            vec![(2, 3), (5, 7), (7, 8)],
+            intersect(&[(0, 3), (5, 8)], &[(2, 7), (7, 10)])
+        );
+    }
+
+    #[test]
+    fn test_bounds_may_match() {
+        let bounds = Bounds {
+            min: Some(StatValue::Int(10)),
+            max: Some(StatValue::Int(20)),
+            null_count: Some(0),
+            rows: 5,
+        };
+        assert!(bounds.may_match(FilterOp::Eq, Some(&StatValue::Int(15))));
+        assert!(!bounds.may_match(FilterOp::Eq, Some(&StatValue::Int(21))));
+        assert!(!bounds.may_match(FilterOp::Gt, Some(&StatValue::Int(20))));
+        assert!(bounds.may_match(FilterOp::Ge, Some(&StatValue::Int(20))));
+        assert!(!bounds.may_match(FilterOp::Lt, Some(&StatValue::Float(10.0))));
+        assert!(!bounds.may_match(FilterOp::IsNull, None));
+    }
+
+    #[test]
+    fn test_prune_row_groups_and_pages() {
+        let path = write_row_groups();
+
+        let report = PruningReport::analyze(&path, "id >= 150").unwrap();
+        assert_eq!(
+            Some(PruneReason::Statistics),
+            report.row_groups[0].pruned_by
+        );
+        assert_eq!(None, report.row_groups[1].pruned_by);
+        assert_eq!(vec![(50, 100)], report.row_groups[1].selected_rows);
+        assert!(report.read_fraction() < 0.5);
+
+        let report = PruningReport::analyze(&path, "country = 'FR'").unwrap();
+        assert_eq!(
+            Some(PruneReason::BloomFilter),
+            report.row_groups[0].pruned_by
+        );
+
+        let report = PruningReport::analyze(&path, "country = 'DE' AND id < 5").unwrap();
+        assert_eq!(vec![(0, 10)], report.row_groups[0].selected_rows);
+        assert_eq!(
+            Some(PruneReason::Statistics),
+            report.row_groups[1].pruned_by
+        );
+
+        let report = PruningReport::analyze(&path, "NOT id < 200").unwrap();
+        assert_eq!(2, report.row_groups_pruned());
+        assert_eq!(0, report.bytes_read());
+
+        // Substring matches cannot use statistics
+        let report = PruningReport::analyze(&path, "country:XX").unwrap();
+        assert_eq!(report.bytes_total(), report.bytes_read());
+
+        assert!(PruningReport::analyze(&path, "missing = 1").is_err());
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_byte_array_literal() {
+        let column = |name: &str, physical, logical| {
+            let field = Type::primitive_type_builder(name, physical)
+                .with_length(16)
+                .with_logical_type(logical)
+                .build()
+                .unwrap();
+            ColumnDescriptor::new(Arc::new(field), 0, 0, ColumnPath::from(name))
+        };
+        let uuid = column(
+            "uuid",
+            PhysicalType::FIXED_LEN_BYTE_ARRAY,
+            Some(LogicalType::Uuid),
+        );
+        let value = "00000000-0000-0000-0000-000000000000";
+        assert_eq!(None, literal(&uuid, value));
+        let blob = column("blob", PhysicalType::BYTE_ARRAY, None);
+        assert_eq!(None, literal(&blob, "abc"));
+        let text = column("text", PhysicalType::BYTE_ARRAY, Some(LogicalType::String));
+        assert_eq!(
+            Some(StatValue::Bytes(b"abc".to_vec())),
+            literal(&text, "abc")
+        );
+    }
+
+    #[test]
+    fn test_timestamp_literal() {
+        let dt = parse_datetime("2024-01-02T03:04:05").unwrap();
+        assert_eq!(1704164645000, dt.and_utc().timestamp_millis());
+        assert_eq!(
+            parse_datetime("2024-01-02 00:00"),
+            parse_datetime("2024-01-02")
+        );
+    }
+}
@@ -0,0 +1,335 @@
+//! Saved SQL queries, loaded from a per-user library and a project library kept
+//! next to the dataset so a team can share it.
+//!
+//! Both are JSON files of the form
+//!
+//! ```text
+//! { "queries": [ { "name": "dup keys", "sql": "SELECT ...", "description": "..." } ] }
+//! ```
+//!
+//! Queries may take parameters written as `:name`, filled in before the query runs.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::config_dir;
+
+/// File name of the project library, in the directory of the opened file.
+pub const PROJECT_LIBRARY_FILE: &str = "parqeye-queries.json";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedQuery {
+    pub name: String,
+    pub sql: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl SavedQuery {
+    /// Parameter names in order of first use, without the leading `:`.
+    pub fn parameters(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        for (_, name) in parameter_spans(&self.sql) {
+            if !names.iter().any(|n| n == name) {
+                names.push(name.to_string());
+            }
+        }
+        names
+    }
+
+    /// The SQL with every `:name` replaced by its value, inserted verbatim so
+    /// strings need their own quotes.
+    pub fn bind(&self, values: &[(String, String)]) -> String {
+        let mut sql = String::with_capacity(self.sql.len());
+        let mut last = 0;
+        for (start, name) in parameter_spans(&self.sql) {
+            if let Some((_, value)) = values.iter().find(|(n, _)| n == name) {
+                sql.push_str(&self.sql[last..start]);
+                sql.push_str(value);
+                last = start + 1 + name.len();
+            }
+        }
+        sql.push_str(&self.sql[last..]);
+        sql
+    }
+}
+
+/// Byte offset of the `:` and the name of every parameter, skipping quoted
+/// strings and identifiers and `::` casts.
+fn parameter_spans(sql: &str) -> Vec<(usize, &str)> {
+    let bytes = sql.as_bytes();
+    let mut spans = Vec::new();
+    let mut quote: Option<u8> = None;
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        match quote {
+            Some(q) if b == q => quote = None,
+            Some(_) => {}
+            None if b == b'\'' || b == b'"' => quote = Some(b),
+            None if b == b':' && bytes.get(i + 1) == Some(&b':') => i += 1,
+            None if b == b':' => {
+                let len = sql[i + 1..]
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(sql.len() - i - 1);
+                let name = &sql[i + 1..i + 1 + len];
+                if name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+                    spans.push((i, name));
+                    i += len;
+                }
+            }
+            None => {}
+        }
+        i += 1;
+    }
+    spans
+}
+
+/// Asks for the parameters of a saved query one at a time.
+#[derive(Debug, Clone)]
+pub struct ParameterPrompt {
+    pub query: SavedQuery,
+    pub names: Vec<String>,
+    pub values: Vec<(String, String)>,
+    pub input: String,
+}
+
+impl ParameterPrompt {
+    pub fn new(query: SavedQuery) -> Self {
+        Self {
+            names: query.parameters(),
+            query,
+            values: Vec::new(),
+            input: String::new(),
+        }
+    }
+
+    /// Name of the parameter being asked for.
+    pub fn current(&self) -> Option<&str> {
+        self.names.get(self.values.len()).map(String::as_str)
+    }
+
+    /// Take the typed value; once every parameter has one, returns the bound SQL.
+    pub fn submit(&mut self) -> Option<String> {
+        if let Some(name) = self.current() {
+            let name = name.to_string();
+            self.values.push((name, std::mem::take(&mut self.input)));
+        }
+        self.current()
+            .is_none()
+            .then(|| self.query.bind(&self.values))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LibraryScope {
+    Project,
+    User,
+}
+
+impl LibraryScope {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            LibraryScope::Project => "project",
+            LibraryScope::User => "user",
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct LibraryFile {
+    #[serde(default)]
+    queries: Vec<SavedQuery>,
+}
+
+/// Project queries followed by user queries.
+#[derive(Debug, Clone, Default)]
+pub struct QueryLibrary {
+    pub queries: Vec<(LibraryScope, SavedQuery)>,
+    project_path: Option<PathBuf>,
+    user_path: Option<PathBuf>,
+}
+
+impl QueryLibrary {
+    /// Load the project library next to `dataset_path` and the user library from
+    /// `$XDG_CONFIG_HOME/parqeye/queries.json`, falling back to `~/.config`.
+    pub fn load(dataset_path: &str) -> Result<QueryLibrary, String> {
+        let project_path = Path::new(dataset_path)
+            .parent()
+            .map(|dir| dir.join(PROJECT_LIBRARY_FILE));
+        let user_path = config_dir().map(|dir| dir.join("queries.json"));
+        Self::from_files(project_path, user_path)
+    }
+
+    pub fn from_files(
+        project_path: Option<PathBuf>,
+        user_path: Option<PathBuf>,
+    ) -> Result<QueryLibrary, String> {
+        let mut queries = Vec::new();
+        for (scope, path) in [
+            (LibraryScope::Project, &project_path),
+            (LibraryScope::User, &user_path),
+        ] {
+            if let Some(path) = path {
+                queries.extend(read_file(path)?.queries.into_iter().map(|q| (scope, q)));
+            }
+        }
+        Ok(QueryLibrary {
+            queries,
+            project_path,
+            user_path,
+        })
+    }
+
+    /// Add a query, replacing one of the same name in that library, and write the file.
+    pub fn save(&mut self, scope: LibraryScope, query: SavedQuery) -> Result<PathBuf, String> {
+        let path = self.path(scope)?;
+        let mut file = read_file(&path)?;
+        match file.queries.iter_mut().find(|q| q.name == query.name) {
+            Some(existing) => *existing = query.clone(),
+            None => file.queries.push(query.clone()),
+        }
+        write_file(&path, &file)?;
+
+        match self
+            .queries
+            .iter_mut()
+            .find(|(s, q)| *s == scope && q.name == query.name)
+        {
+            Some((_, existing)) => *existing = query,
+            None => {
+                // Keep project queries first
+                let at = match scope {
+                    LibraryScope::Project => self
+                        .queries
+                        .iter()
+                        .take_while(|(s, _)| *s == LibraryScope::Project)
+                        .count(),
+                    LibraryScope::User => self.queries.len(),
+                };
+                self.queries.insert(at, (scope, query));
+            }
+        }
+        Ok(path)
+    }
+
+    /// Remove the query at `index` from its library file.
+    pub fn remove(&mut self, index: usize) -> Result<(), String> {
+        let Some((scope, query)) = self.queries.get(index).cloned() else {
+            return Ok(());
+        };
+        let path = self.path(scope)?;
+        let mut file = read_file(&path)?;
+        file.queries.retain(|q| q.name != query.name);
+        write_file(&path, &file)?;
+        self.queries.remove(index);
+        Ok(())
+    }
+
+    fn path(&self, scope: LibraryScope) -> Result<PathBuf, String> {
+        match scope {
+            LibraryScope::Project => self.project_path.clone(),
+            LibraryScope::User => self.user_path.clone(),
+        }
+        .ok_or_else(|| format!("No location for the {} library", scope.describe()))
+    }
+}
+
+fn read_file(path: &Path) -> Result<LibraryFile, String> {
+    match fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("Cannot parse {}: {e}", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(LibraryFile::default()),
+        Err(e) => Err(format!("Cannot read {}: {e}", path.display())),
+    }
+}
+
+fn write_file(path: &Path, file: &LibraryFile) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| format!("Cannot create {}: {e}", dir.display()))?;
+    }
+    let content = serde_json::to_string_pretty(file).map_err(|e| e.to_string())?;
+    fs::write(path, content + "\n").map_err(|e| format!("Cannot write {}: {e}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::test_path;
+
+    fn query(name: &str, sql: &str) -> SavedQuery {
+        SavedQuery {
+            name: name.to_string(),
+            sql: sql.to_string(),
+            description: None,
+        }
+    }
+
+    #[test]
+    fn test_parameters() {
+        let q = query(
+            "range",
+            "SELECT ts::date, ':nope' FROM parquet WHERE ts >= :start AND ts < :end OR ts = :start",
+        );
+        assert_eq!(vec!["start", "end"], q.parameters());
+        assert_eq!(
+            "SELECT ts::date, ':nope' FROM parquet WHERE ts >= '2024-01-01' AND ts < :end OR ts = '2024-01-01'",
+            q.bind(&[("start".to_string(), "'2024-01-01'".to_string())])
+        );
+        assert!(query("none", "SELECT 1").parameters().is_empty());
+
+        let mut prompt = ParameterPrompt::new(q);
+        assert_eq!(Some("start"), prompt.current());
+        prompt.input = "1".to_string();
+        assert_eq!(None, prompt.submit());
+        assert_eq!(Some("end"), prompt.current());
+        prompt.input = "2".to_string();
+        assert_eq!(
+            Some(
+                "SELECT ts::date, ':nope' FROM parquet WHERE ts >= 1 AND ts < 2 OR ts = 1"
+                    .to_string()
+            ),
+            prompt.submit()
+        );
+    }
+
+    #[test]
+    fn test_save_load_and_remove() {
+        let dir = test_path("library");
+        fs::remove_dir_all(&dir).ok();
+        let project = dir.join("data").join(PROJECT_LIBRARY_FILE);
+        let user = dir.join("config").join("queries.json");
+
+        let mut library =
+            QueryLibrary::from_files(Some(project.clone()), Some(user.clone())).unwrap();
+        assert!(library.queries.is_empty());
+        library
+            .save(LibraryScope::User, query("mine", "SELECT 1"))
+            .unwrap();
+        library
+            .save(LibraryScope::Project, query("shared", "SELECT 2"))
+            .unwrap();
+        library
+            .save(LibraryScope::Project, query("shared", "SELECT 3"))
+            .unwrap();
+
+        let reloaded = QueryLibrary::from_files(Some(project.clone()), Some(user.clone())).unwrap();
+        assert_eq!(library.queries, reloaded.queries);
+        assert_eq!(
+            vec![
+                (LibraryScope::Project, query("shared", "SELECT 3")),
+                (LibraryScope::User, query("mine", "SELECT 1")),
+            ],
+            reloaded.queries
+        );
+
+        library.remove(0).unwrap();
+        let reloaded = QueryLibrary::from_files(Some(project), Some(user)).unwrap();
+        assert_eq!(
+            vec![(LibraryScope::User, query("mine", "SELECT 1"))],
+            reloaded.queries
+        );
+        fs::remove_dir_all(dir).ok();
+    }
+}
@@ -0,0 +1,316 @@
+//! One row as a tree of its values, for the row detail overlay: structs, lists and maps
+//! are nodes that expand and collapse, leaves carry their value and Arrow type.
+
+use crate::file::sample_data::FindMatches;
+use polars::prelude::*;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordNode {
+    pub depth: usize,
+    /// Column or field name, `[i]` for list items, the key for map entries
+    pub name: String,
+    /// Arrow type of leaves; structs, lists and maps describe their shape
+    pub dtype: String,
+    /// Value as shown in the table, `None` for nodes with children
+    pub value: Option<String>,
+    pub null: bool,
+    /// Number of nodes below this one, which follow it in `RecordTree::nodes`
+    pub descendants: usize,
+    json: serde_json::Value,
+}
+
+impl RecordNode {
+    pub fn is_group(&self) -> bool {
+        self.value.is_none()
+    }
+}
+
+/// Nodes of a row in depth-first order, with which of them are collapsed.
+#[derive(Debug, Clone)]
+pub struct RecordTree {
+    pub nodes: Vec<RecordNode>,
+    collapsed: Vec<bool>,
+}
+
+impl RecordTree {
+    /// The record of `row` of `frame`, every node expanded.
+    pub fn from_frame(frame: &DataFrame, row: usize) -> Result<Self, String> {
+        if row >= frame.height() {
+            return Err(format!("Row {} is out of range", row + 1));
+        }
+        let mut nodes = Vec::new();
+        for column in frame.get_columns() {
+            let series = column.as_materialized_series();
+            push_node(&mut nodes, series, row, series.name().to_string(), 0)
+                .map_err(|e| e.to_string())?;
+        }
+        Ok(Self {
+            collapsed: vec![false; nodes.len()],
+            nodes,
+        })
+    }
+
+    /// Indices of the nodes not hidden inside a collapsed node, in display order.
+    pub fn visible(&self) -> Vec<usize> {
+        let mut visible = Vec::new();
+        let mut idx = 0;
+        while idx < self.nodes.len() {
+            visible.push(idx);
+            idx += if self.collapsed[idx] {
+                self.nodes[idx].descendants + 1
+            } else {
+                1
+            };
+        }
+        visible
+    }
+
+    pub fn is_collapsed(&self, node: usize) -> bool {
+        self.collapsed[node]
+    }
+
+    pub fn set_collapsed(&mut self, node: usize, collapsed: bool) {
+        if self.nodes[node].is_group() {
+            self.collapsed[node] = collapsed;
+        }
+    }
+
+    pub fn set_all_collapsed(&mut self, collapsed: bool) {
+        for node in 0..self.nodes.len() {
+            self.set_collapsed(node, collapsed);
+        }
+    }
+
+    pub fn parent(&self, node: usize) -> Option<usize> {
+        let depth = self.nodes[node].depth;
+        (0..node).rev().find(|&n| self.nodes[n].depth < depth)
+    }
+
+    /// Expand the nodes above `node` so that it is visible.
+    pub fn reveal(&mut self, node: usize) {
+        let mut current = node;
+        while let Some(parent) = self.parent(current) {
+            self.collapsed[parent] = false;
+            current = parent;
+        }
+    }
+
+    /// Nodes whose name or value contains the query, smart-case like find.
+    pub fn find(&self, query: &str) -> Vec<usize> {
+        let find = FindMatches {
+            query: query.to_string(),
+            ..Default::default()
+        };
+        (0..self.nodes.len())
+            .filter(|&n| {
+                let node = &self.nodes[n];
+                !find.ranges(&node.name).is_empty()
+                    || node
+                        .value
+                        .as_ref()
+                        .is_some_and(|v| !find.ranges(v).is_empty())
+            })
+            .collect()
+    }
+
+    /// The record as a JSON object of column names to values.
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::Value::Object(
+            self.nodes
+                .iter()
+                .filter(|n| n.depth == 0)
+                .map(|n| (n.name.clone(), n.json.clone()))
+                .collect(),
+        )
+    }
+}
+
+/// Append the node of `series[row]` and, for nested values, its children.
+fn push_node(
+    nodes: &mut Vec<RecordNode>,
+    series: &Series,
+    row: usize,
+    name: String,
+    depth: usize,
+) -> PolarsResult<serde_json::Value> {
+    let at = nodes.len();
+    let value = series.get(row)?;
+    let mut node = RecordNode {
+        depth,
+        name,
+        dtype: arrow_type(series.dtype()),
+        value: None,
+        null: value.is_null(),
+        descendants: 0,
+        json: serde_json::Value::Null,
+    };
+    if node.null {
+        node.value = Some("NULL".to_string());
+        nodes.push(node);
+        return Ok(serde_json::Value::Null);
+    }
+    nodes.push(node);
+
+    let json = match series.dtype() {
+        DataType::Struct(fields) => {
+            nodes[at].dtype = format!("struct ({} fields)", fields.len());
+            let mut object = serde_json::Map::new();
+            for field in series.struct_()?.fields_as_series() {
+                let name = field.name().to_string();
+                let json = push_node(nodes, &field, row, name.clone(), depth + 1)?;
+                object.insert(name, json);
+            }
+            serde_json::Value::Object(object)
+        }
+        DataType::List(inner) if is_map_entry(inner) => {
+            let entries = series.list()?.get_as_series(row).unwrap_or_default();
+            nodes[at].dtype = format!("map ({} entries)", entries.len());
+            let fields = entries.struct_()?.fields_as_series();
+            let mut object = serde_json::Map::new();
+            for entry in 0..entries.len() {
+                let key = fields[0].get(entry)?;
+                let key = key
+                    .get_str()
+                    .map(str::to_string)
+                    .unwrap_or_else(|| key.to_string());
+                let json = push_node(nodes, &fields[1], entry, key.clone(), depth + 1)?;
+                object.insert(key, json);
+            }
+            serde_json::Value::Object(object)
+        }
+        DataType::List(_) | DataType::Array(..) => {
+            let items = match series.dtype() {
+                DataType::List(_) => series.list()?.get_as_series(row),
+                _ => series.array()?.get_as_series(row),
+            }
+            .unwrap_or_default();
+            nodes[at].dtype = format!("list ({} items)", items.len());
+            let mut array = Vec::with_capacity(items.len());
+            for item in 0..items.len() {
+                array.push(push_node(
+                    nodes,
+                    &items,
+                    item,
+                    format!("[{item}]"),
+                    depth + 1,
+                )?);
+            }
+            serde_json::Value::Array(array)
+        }
+        _ => {
+            nodes[at].value = Some(value.to_string());
+            leaf_json(&value)
+        }
+    };
+    nodes[at].descendants = nodes.len() - at - 1;
+    nodes[at].json = json.clone();
+    Ok(json)
+}
+
+/// Parquet maps are read as lists of `{key, value}` structs.
+fn is_map_entry(dtype: &DataType) -> bool {
+    matches!(dtype, DataType::Struct(fields)
+        if fields.len() == 2 && fields[0].name() == "key" && fields[1].name() == "value")
+}
+
+fn arrow_type(dtype: &DataType) -> String {
+    format!("{:?}", dtype.to_arrow(CompatLevel::newest()))
+}
+
+/// Numbers and booleans stay typed, other values are strings as displayed.
+fn leaf_json(value: &AnyValue) -> serde_json::Value {
+    let dtype = value.dtype();
+    if let AnyValue::Boolean(b) = value {
+        serde_json::Value::Bool(*b)
+    } else if dtype.is_unsigned_integer() {
+        value
+            .extract::<u64>()
+            .map_or(serde_json::Value::Null, Into::into)
+    } else if dtype.is_integer() {
+        value
+            .extract::<i64>()
+            .map_or(serde_json::Value::Null, Into::into)
+    } else if dtype.is_float() {
+        value
+            .extract::<f64>()
+            .and_then(serde_json::Number::from_f64)
+            .map_or_else(|| value.to_string().into(), serde_json::Value::Number)
+    } else if let Some(s) = value.get_str() {
+        s.into()
+    } else {
+        value.to_string().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record() -> RecordTree {
+        let address = StructChunked::from_series(
+            "address".into(),
+            2,
+            [
+                Series::new("city".into(), ["Paris", "Oslo"]),
+                Series::new("zip".into(), [Some(75001i32), None]),
+            ]
+            .iter(),
+        )
+        .unwrap()
+        .into_series();
+        let tags = Series::new(
+            "tags".into(),
+            [
+                Series::new("".into(), ["a", "b"]),
+                Series::new("".into(), ["c"]),
+            ],
+        );
+        let frame = DataFrame::new(vec![
+            Series::new("id".into(), [1i64, 2]).into(),
+            address.into(),
+            tags.into(),
+        ])
+        .unwrap();
+        RecordTree::from_frame(&frame, 0).unwrap()
+    }
+
+    #[test]
+    fn test_nested_nodes() {
+        let tree = record();
+        let names: Vec<&str> = tree.nodes.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(
+            vec!["id", "address", "city", "zip", "tags", "[0]", "[1]"],
+            names
+        );
+        assert_eq!("Int64", tree.nodes[0].dtype);
+        assert_eq!("struct (2 fields)", tree.nodes[1].dtype);
+        assert_eq!(2, tree.nodes[1].descendants);
+        assert_eq!("list (2 items)", tree.nodes[4].dtype);
+        assert_eq!(Some(4), tree.parent(6));
+        assert_eq!(
+            serde_json::json!({"id": 1, "address": {"city": "Paris", "zip": 75001}, "tags": ["a", "b"]}),
+            tree.to_json()
+        );
+
+        let frame = DataFrame::new(vec![Series::new("zip".into(), [None::<i32>]).into()]).unwrap();
+        let tree = RecordTree::from_frame(&frame, 0).unwrap();
+        assert!(tree.nodes[0].null);
+        assert_eq!(serde_json::json!({"zip": null}), tree.to_json());
+    }
+
+    #[test]
+    fn test_collapse_and_find() {
+        let mut tree = record();
+        tree.set_collapsed(1, true);
+        assert_eq!(vec![0, 1, 4, 5, 6], tree.visible());
+        tree.set_all_collapsed(true);
+        assert_eq!(vec![0, 1, 4], tree.visible());
+
+        let found = tree.find("PAR");
+        assert!(found.is_empty());
+        let found = tree.find("par");
+        assert_eq!(vec![2], found);
+        tree.reveal(2);
+        assert_eq!(vec![0, 1, 2, 3, 4], tree.visible());
+    }
+}
@@ -1,11 +1,125 @@
+use crate::file::filter::FilterExpr;
 use polars::prelude::*;
 
+const MAX_ROWS: usize = 200;
+
+/// Sort order applied to the data preview: column index into `flattened_columns`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SortSpec {
+    pub column: usize,
+    pub descending: bool,
+}
+
+impl SortSpec {
+    /// Cycle ascending -> descending -> unsorted for `column`; a new column starts ascending.
+    pub fn toggle(current: Option<SortSpec>, column: usize) -> Option<SortSpec> {
+        match current {
+            Some(spec) if spec.column == column && !spec.descending => Some(SortSpec {
+                column,
+                descending: true,
+            }),
+            Some(spec) if spec.column == column => None,
+            _ => Some(SortSpec {
+                column,
+                descending: false,
+            }),
+        }
+    }
+
+    pub fn indicator(&self) -> &'static str {
+        if self.descending { "▼" } else { "▲" }
+    }
+}
+
+/// Cells of the data preview containing a find query, in row-major order.
+/// Matching is smart-case: a query without uppercase letters ignores ASCII case.
+/// Columns are positions among the columns searched, which may be reordered.
+#[derive(Debug, Clone, Default)]
+pub struct FindMatches {
+    pub query: String,
+    /// `(row, column)` of every matching cell
+    pub cells: Vec<(usize, usize)>,
+    /// Index into `cells` of the match the cursor is on
+    pub current: Option<usize>,
+}
+
+impl FindMatches {
+    /// Search `columns`, data column indices in the order they are shown.
+    pub fn search_columns(data: &ParquetSampleData, query: &str, columns: &[usize]) -> Self {
+        let mut find = FindMatches {
+            query: query.to_string(),
+            cells: vec![],
+            current: None,
+        };
+        if query.is_empty() {
+            return find;
+        }
+        for (row_idx, row) in data.rows.iter().enumerate() {
+            for (position, cell) in columns.iter().filter_map(|&c| row.get(c)).enumerate() {
+                if !find.ranges(cell).is_empty() {
+                    find.cells.push((row_idx, position));
+                }
+            }
+        }
+        find
+    }
+
+    fn ignore_case(&self) -> bool {
+        !self.query.chars().any(|c| c.is_uppercase())
+    }
+
+    /// Byte ranges of non-overlapping occurrences of the query in `text`.
+    pub fn ranges(&self, text: &str) -> Vec<(usize, usize)> {
+        if self.query.is_empty() {
+            return vec![];
+        }
+        // ASCII folding keeps byte offsets identical to the original text
+        let (haystack, needle) = if self.ignore_case() {
+            (text.to_ascii_lowercase(), self.query.to_ascii_lowercase())
+        } else {
+            (text.to_string(), self.query.clone())
+        };
+        haystack
+            .match_indices(&needle)
+            .map(|(start, m)| (start, start + m.len()))
+            .collect()
+    }
+
+    /// Move to the first match after `cell` (or at it, when `inclusive`), wrapping around.
+    pub fn next_from(&mut self, cell: (usize, usize), inclusive: bool) -> Option<(usize, usize)> {
+        let idx = self
+            .cells
+            .iter()
+            .position(|c| if inclusive { *c >= cell } else { *c > cell })
+            .or(if self.cells.is_empty() { None } else { Some(0) })?;
+        self.current = Some(idx);
+        Some(self.cells[idx])
+    }
+
+    /// Move to the last match before `cell`, wrapping around.
+    pub fn prev_from(&mut self, cell: (usize, usize)) -> Option<(usize, usize)> {
+        let idx = self
+            .cells
+            .iter()
+            .rposition(|c| *c < cell)
+            .or(self.cells.len().checked_sub(1))?;
+        self.current = Some(idx);
+        Some(self.cells[idx])
+    }
+
+    pub fn current_cell(&self) -> Option<(usize, usize)> {
+        self.current.and_then(|idx| self.cells.get(idx).copied())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ParquetSampleData {
     pub flattened_columns: Vec<String>,
     pub rows: Vec<Vec<String>>,
     pub total_columns: usize,
     pub total_rows: usize,
+    /// The rows as read, for typed access to nested values
+    pub frame: DataFrame,
 }
 
 // TODO: in future create a independent crate that does the parsing,
@@ -14,12 +128,46 @@ impl ParquetSampleData {
     pub fn read_sample_data(
         file_path: &str,
     ) -> Result<ParquetSampleData, Box<dyn std::error::Error>> {
-        const MAX_ROWS: usize = 200;
-
         // Read parquet file using polars LazyFrame
-        let df = LazyFrame::scan_parquet(PlPath::new(file_path), Default::default())?
-            .limit(MAX_ROWS as u32)
-            .collect()?;
+        let lf = LazyFrame::scan_parquet(PlPath::new(file_path), Default::default())?;
+        Self::from_lazy_frame(lf)
+    }
+
+    /// Read the first rows of the whole file matching `filter`, ordered by `sort`
+    /// (column name, descending). Filtering and sorting run over the full file
+    /// through polars, comparing typed values rather than strings. Returns the
+    /// rows and, when filtering, the total number of matching rows.
+    pub fn read_view(
+        file_path: &str,
+        filter: Option<&FilterExpr>,
+        sort: Option<(&str, bool)>,
+    ) -> Result<(ParquetSampleData, Option<usize>), Box<dyn std::error::Error>> {
+        let mut lf = LazyFrame::scan_parquet(PlPath::new(file_path), Default::default())?;
+        let mut matches = None;
+        if let Some(filter) = filter {
+            let schema = lf.collect_schema()?;
+            lf = lf.filter(filter.to_expr(&schema)?);
+            let count = lf.clone().select([len()]).collect()?;
+            matches = count
+                .get_columns()
+                .first()
+                .and_then(|c| c.get(0).ok())
+                .and_then(|v| v.extract::<usize>());
+        }
+        if let Some((column, descending)) = sort {
+            lf = lf.sort(
+                [column],
+                SortMultipleOptions::default()
+                    .with_order_descending(descending)
+                    .with_nulls_last(true)
+                    .with_maintain_order(true),
+            );
+        }
+        Ok((Self::from_lazy_frame(lf)?, matches))
+    }
+
+    fn from_lazy_frame(lf: LazyFrame) -> Result<ParquetSampleData, Box<dyn std::error::Error>> {
+        let df = lf.limit(MAX_ROWS as u32).collect()?;
 
         // Flatten struct columns
         let df = Self::flatten_struct_columns(df)?;
@@ -50,6 +198,7 @@ impl ParquetSampleData {
             flattened_columns,
             rows,
             total_rows: df.height(),
+            frame: df,
         })
     }
 
@@ -73,23 +222,75 @@ impl ParquetSampleData {
             Err(_) => "NULL".to_string(),
         }
     }
+}
 
-    /// Filter rows to those where any cell contains `query` as a substring.
-    pub fn filter_rows(&self, query: &str) -> ParquetSampleData {
-        if query.is_empty() {
-            return self.clone();
-        }
-        let rows: Vec<Vec<String>> = self
-            .rows
-            .iter()
-            .filter(|row| row.iter().any(|cell| cell.contains(query)))
-            .cloned()
-            .collect();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort_spec_toggle_cycle() {
+        let asc = SortSpec::toggle(None, 2);
+        assert_eq!(
+            Some(SortSpec {
+                column: 2,
+                descending: false
+            }),
+            asc
+        );
+
+        let desc = SortSpec::toggle(asc, 2);
+        assert_eq!(Some(true), desc.map(|s| s.descending));
+        assert_eq!(None, SortSpec::toggle(desc, 2));
+    }
+
+    #[test]
+    fn test_sort_spec_toggle_new_column() {
+        let desc = Some(SortSpec {
+            column: 1,
+            descending: true,
+        });
+        let toggled = SortSpec::toggle(desc, 3).unwrap();
+        assert_eq!(3, toggled.column);
+        assert!(!toggled.descending);
+        assert_eq!("▲", toggled.indicator());
+    }
+
+    fn grid(rows: &[&[&str]]) -> ParquetSampleData {
         ParquetSampleData {
-            flattened_columns: self.flattened_columns.clone(),
-            total_columns: self.total_columns,
+            flattened_columns: (0..rows[0].len()).map(|i| format!("c{i}")).collect(),
+            rows: rows
+                .iter()
+                .map(|r| r.iter().map(|c| c.to_string()).collect())
+                .collect(),
+            total_columns: rows[0].len(),
             total_rows: rows.len(),
-            rows,
+            frame: DataFrame::empty(),
         }
     }
+
+    #[test]
+    fn test_find_smart_case() {
+        let data = grid(&[&["Berlin", "x"], &["bern", "BERLIN"]]);
+
+        let find = FindMatches::search_columns(&data, "ber", &[0, 1]);
+        assert_eq!(vec![(0, 0), (1, 0), (1, 1)], find.cells);
+        assert_eq!(vec![(0, 3)], find.ranges("Berlin"));
+
+        let find = FindMatches::search_columns(&data, "BER", &[0, 1]);
+        assert_eq!(vec![(1, 1)], find.cells);
+    }
+
+    #[test]
+    fn test_find_next_prev_wrap() {
+        let data = grid(&[&["a", "b"], &["a", "a"]]);
+        let mut find = FindMatches::search_columns(&data, "a", &[0, 1]);
+
+        assert_eq!(Some((0, 0)), find.next_from((0, 0), true));
+        assert_eq!(Some((1, 0)), find.next_from((0, 0), false));
+        assert_eq!(Some((1, 1)), find.next_from((1, 0), false));
+        assert_eq!(Some((0, 0)), find.next_from((1, 1), false));
+        assert_eq!(Some((1, 1)), find.prev_from((0, 0)));
+        assert_eq!(Some(2), find.current);
+    }
 }
@@ -4,11 +4,12 @@ use parquet::basic::{LogicalType, TimeUnit, Type as PhysicalType};
 use parquet::file::metadata::ParquetMetaData;
 use parquet::schema::types::Type as ParquetType;
 use ratatui::{
-    style::{Color, Stylize},
+    style::Stylize,
     widgets::{Cell, Row},
 };
 
 use crate::file::utils::format_size;
+use crate::theme::theme;
 
 #[derive(Debug, Clone)]
 pub struct ColumnStats {
@@ -41,12 +42,15 @@ pub enum SchemaInfo {
     Primitive {
         name: String,
         display: String,
+        /// Nesting level below the root (top-level fields are 1)
+        depth: usize,
         info: Box<ColumnSchemaInfo>,
         stats: ColumnStats,
     },
     Group {
         name: String,
         display: String,
+        depth: usize,
         repetition: String,
     },
 }
@@ -95,6 +99,7 @@ impl FileSchema {
             traverse(
                 child.as_ref(),
                 "   ".to_string(),
+                1,
                 idx == count - 1,
                 &mut lines,
                 &mut leaf_idx,
@@ -178,19 +183,15 @@ impl FileSchema {
                     ]);
 
                     if is_selected {
-                        row = row.style(
-                            ratatui::style::Style::default()
-                                .bg(Color::Yellow)
-                                .fg(Color::Black),
-                        );
+                        row = row.style(theme().selected());
                     }
 
                     primitive_index += 1;
                     Some(row)
                 } else if let SchemaInfo::Group { repetition, .. } = col {
                     let row = Row::new(vec![
-                        Cell::from(repetition.clone().green()),
-                        Cell::from("group".green()),
+                        Cell::from(repetition.clone().fg(theme().key)),
+                        Cell::from("group".fg(theme().key)),
                     ]);
                     Some(row)
                 } else {
@@ -280,11 +281,7 @@ impl FileSchema {
                     let mut row = Row::new(visible_cells);
 
                     if is_selected {
-                        row = row.style(
-                            ratatui::style::Style::default()
-                                .bg(Color::Yellow)
-                                .fg(Color::Black),
-                        );
+                        row = row.style(theme().selected());
                     }
 
                     primitive_index += 1;
@@ -319,7 +316,7 @@ impl FileSchema {
                         .enumerate()
                         .map(|(idx, content)| {
                             if idx == 0 || idx == 1 {
-                                Cell::from(content.green())
+                                Cell::from(content.fg(theme().key))
                             } else {
                                 Cell::from(content)
                             }
@@ -338,9 +335,11 @@ impl FileSchema {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn traverse(
     node: &ParquetType,
     prefix: String,
+    depth: usize,
     is_last: bool,
     lines: &mut Vec<SchemaInfo>,
     leaf_idx: &mut usize,
@@ -373,6 +372,7 @@ fn traverse(
         lines.push(SchemaInfo::Primitive {
             name: node.name().to_string(),
             display: line,
+            depth,
             info: Box::new(info),
             stats,
         });
@@ -382,6 +382,7 @@ fn traverse(
         lines.push(SchemaInfo::Group {
             name: node.name().to_string(),
             display: line,
+            depth,
             repetition: format!("{:?}", node.get_basic_info().repetition()),
         });
     }
@@ -394,6 +395,7 @@ fn traverse(
             traverse(
                 child.as_ref(),
                 next_prefix,
+                depth + 1,
                 idx == count - 1,
                 lines,
                 leaf_idx,
@@ -1,7 +1,14 @@
 //! Run SQL against a Parquet file using polars-sql.
+//!
+//! One [`SqlSession`] lives for the whole run, so tables attached with `.attach` or
+//! `--table` and tables made by `CREATE TABLE ... AS SELECT` stay available.
+//!
+//! Queries run on the polars thread pool so the TUI keeps drawing; only a window of
+//! `limit` rows is collected and turned into strings, more can be fetched later.
 
 use polars::prelude::*;
 use polars_sql::SQLContext;
+use std::time::{Duration, Instant};
 
 use crate::file::sample_data::ParquetSampleData;
 
@@ -12,29 +19,232 @@ pub enum SqlResult {
     Err(String),
 }
 
-/// Execute a SQL query against the Parquet file at `path`.
-/// The table is registered as "parquet". Returns result data or an error string.
-pub fn run_sql(path: &str, query: &str) -> SqlResult {
-    if query.trim().is_empty() {
-        return SqlResult::Err("Empty query".to_string());
-    }
-    let lf = match LazyFrame::scan_parquet(PlPath::new(path), Default::default()) {
-        Ok(lf) => lf,
-        Err(e) => return SqlResult::Err(e.to_string()),
-    };
-    let mut ctx = SQLContext::new();
-    ctx.register("parquet", lf);
-    let result_lf = match ctx.execute(query) {
-        Ok(lf) => lf,
-        Err(e) => return SqlResult::Err(e.to_string()),
-    };
-    let df = match result_lf.collect() {
-        Ok(df) => df,
-        Err(e) => return SqlResult::Err(e.to_string()),
-    };
-    dataframe_to_sample_data(df)
-        .map(SqlResult::Ok)
-        .unwrap_or_else(SqlResult::Err)
+/// SQL context shared by every query of the SQL tab. The opened file is the table
+/// "parquet"; more Parquet files can be attached under their own names.
+#[derive(Clone)]
+pub struct SqlSession {
+    ctx: SQLContext,
+}
+
+impl Default for SqlSession {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SqlSession {
+    pub fn new() -> SqlSession {
+        SqlSession {
+            ctx: SQLContext::new(),
+        }
+    }
+
+    /// Session with the Parquet file at `path` registered as "parquet".
+    pub fn for_file(path: &str) -> Result<SqlSession, String> {
+        let mut session = SqlSession::new();
+        session.attach("parquet", path)?;
+        Ok(session)
+    }
+
+    /// Register the Parquet file at `path` as table `name`, replacing any table of that name.
+    pub fn attach(&mut self, name: &str, path: &str) -> Result<(), String> {
+        let valid = name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid {
+            return Err(format!("Invalid table name '{name}'"));
+        }
+        if !std::path::Path::new(path).is_file() {
+            return Err(format!("No such file: {path}"));
+        }
+        let lf = LazyFrame::scan_parquet(PlPath::new(path), Default::default())
+            .map_err(|e| e.to_string())?;
+        self.ctx.register(name, lf);
+        Ok(())
+    }
+
+    pub fn tables(&self) -> Vec<String> {
+        let mut tables = self.ctx.get_tables();
+        tables.sort();
+        tables
+    }
+
+    /// Lazy plan of a query. Statements like `CREATE TABLE` take effect on the session.
+    fn plan(&mut self, query: &str) -> Result<LazyFrame, String> {
+        if query.trim().is_empty() {
+            return Err("Empty query".to_string());
+        }
+        self.ctx.execute(query).map_err(|e| e.to_string())
+    }
+}
+
+/// Parse a `.attach <name> <path>` command typed in the SQL tab; `None` if the
+/// query is not an `.attach` command, `Some(Err)` if it is malformed.
+pub fn parse_attach(query: &str) -> Option<Result<(String, String), String>> {
+    let rest = query.trim().strip_prefix(".attach")?;
+    let mut parts = rest.trim().splitn(2, char::is_whitespace);
+    Some(match (parts.next(), parts.next().map(str::trim)) {
+        (Some(name), Some(path)) if !name.is_empty() && !path.is_empty() => {
+            Ok((name.to_string(), path.to_string()))
+        }
+        _ => Err("Usage: .attach <name> <path to parquet file>".to_string()),
+    })
+}
+
+/// Polars plans of a query before and after optimization, as shown by the Explain pane.
+#[derive(Debug, Clone)]
+pub struct QueryPlan {
+    pub logical: String,
+    pub optimized: String,
+}
+
+/// How a line of a plan is highlighted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanLine {
+    Scan,
+    /// Projection or predicate pushed into the scan
+    Pushdown,
+    /// A filter or full-width projection the optimizer could not push into the scan
+    NotPushed,
+    Plain,
+}
+
+impl QueryPlan {
+    /// Plans are computed on a copy of the session so explaining `CREATE TABLE` does
+    /// not create the table.
+    pub fn explain(session: &SqlSession, query: &str) -> Result<QueryPlan, String> {
+        let lf = session.clone().plan(query)?;
+        Ok(QueryPlan {
+            logical: lf.describe_plan().map_err(|e| e.to_string())?,
+            optimized: lf.describe_optimized_plan().map_err(|e| e.to_string())?,
+        })
+    }
+
+    pub fn classify(line: &str) -> PlanLine {
+        let line = line.trim_start();
+        if line.contains(" SCAN ") {
+            PlanLine::Scan
+        } else if line.starts_with("SELECTION:") {
+            PlanLine::Pushdown
+        } else if let Some(columns) = line.strip_prefix("PROJECT ") {
+            // "PROJECT 2/10 COLUMNS" reads two columns, "PROJECT */10" reads all of them
+            if columns.starts_with('*') {
+                PlanLine::NotPushed
+            } else {
+                PlanLine::Pushdown
+            }
+        } else if line.starts_with("FILTER ") {
+            PlanLine::NotPushed
+        } else {
+            PlanLine::Plain
+        }
+    }
+
+    /// One-line verdict on what reached the scan in the optimized plan.
+    pub fn pushdown_summary(&self) -> String {
+        let lines: Vec<PlanLine> = self.optimized.lines().map(Self::classify).collect();
+        let projection = self
+            .optimized
+            .lines()
+            .map(str::trim_start)
+            .find_map(|l| l.strip_prefix("PROJECT "))
+            .map_or("all columns read".to_string(), |p| {
+                format!("{} read", p.to_lowercase())
+            });
+        let predicate = if self
+            .optimized
+            .lines()
+            .any(|l| l.trim_start().starts_with("SELECTION:"))
+        {
+            "filter pushed into scan"
+        } else if lines.contains(&PlanLine::NotPushed) {
+            "filter NOT pushed into scan"
+        } else {
+            "no filter"
+        };
+        format!("Projection: {projection}; predicate: {predicate}")
+    }
+}
+
+/// Rows fetched per query run and per "fetch more".
+pub const DEFAULT_ROW_LIMIT: usize = 1_000;
+
+/// A query running in the background. Dropping the job cancels the query.
+pub struct SqlJob {
+    pub query: String,
+    /// First result row fetched by this job; non-zero when fetching more rows
+    pub offset: usize,
+    pub limit: usize,
+    started: Instant,
+    handle: InProcessQuery,
+}
+
+/// Rows `offset..offset + limit` of a query result.
+pub struct SqlBatch {
+    pub data: ParquetSampleData,
+    /// The same rows as a DataFrame, for the column summary
+    pub frame: DataFrame,
+    /// Whether the result has rows after this batch
+    pub has_more: bool,
+    pub elapsed: Duration,
+}
+
+impl SqlJob {
+    /// Plan a SQL query in the session and start collecting rows `offset..offset + limit`
+    /// in the background.
+    pub fn start(
+        session: &mut SqlSession,
+        query: &str,
+        offset: usize,
+        limit: usize,
+    ) -> Result<SqlJob, String> {
+        let result_lf = session.plan(query)?;
+        // One extra row tells whether there is more to fetch
+        let handle = result_lf
+            .slice(offset as i64, (limit + 1) as IdxSize)
+            .collect_concurrently()
+            .map_err(|e| e.to_string())?;
+        Ok(SqlJob {
+            query: query.to_string(),
+            offset,
+            limit,
+            started: Instant::now(),
+            handle,
+        })
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// The result once the query has finished, without blocking.
+    pub fn poll(&self) -> Option<Result<SqlBatch, String>> {
+        self.handle.fetch().map(|result| self.batch(result))
+    }
+
+    /// Block until the query has finished.
+    pub fn wait(&self) -> Result<SqlBatch, String> {
+        self.batch(self.handle.fetch_blocking())
+    }
+
+    fn batch(&self, result: PolarsResult<DataFrame>) -> Result<SqlBatch, String> {
+        let df = result.map_err(|e| e.to_string())?;
+        let has_more = df.height() > self.limit;
+        let frame = df.head(Some(self.limit));
+        dataframe_to_sample_data(frame.clone()).map(|data| SqlBatch {
+            data,
+            frame,
+            has_more,
+            elapsed: self.elapsed(),
+        })
+    }
+
+    /// Ask polars to stop the query; it is abandoned either way.
+    pub fn cancel(self) {
+        self.handle.cancel();
+    }
 }
 
 fn dataframe_to_sample_data(df: DataFrame) -> Result<ParquetSampleData, String> {
@@ -58,6 +268,7 @@ fn dataframe_to_sample_data(df: DataFrame) -> Result<ParquetSampleData, String>
         total_columns,
         total_rows: df.height(),
         rows,
+        frame: df,
     })
 }
 
@@ -73,3 +284,119 @@ fn get_value_as_string(col: &Series, row_idx: usize) -> String {
         Err(_) => "NULL".to_string(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::write_test_parquet;
+
+    fn write_ids(name: &str) -> String {
+        write_test_parquet(
+            name,
+            &mut df!("id" => (0..25i64).collect::<Vec<_>>()).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_job_fetches_rows_in_batches() {
+        let path = write_ids("sql-batches");
+        let session = &mut SqlSession::for_file(&path).unwrap();
+        let query = "SELECT id FROM parquet WHERE id >= 5 ORDER BY id";
+
+        let batch = SqlJob::start(session, query, 0, 10)
+            .unwrap()
+            .wait()
+            .unwrap();
+        assert_eq!(10, batch.data.total_rows);
+        assert_eq!("5", batch.data.rows[0][0]);
+        assert!(batch.has_more);
+
+        let batch = SqlJob::start(session, query, 10, 10)
+            .unwrap()
+            .wait()
+            .unwrap();
+        assert_eq!(10, batch.data.total_rows);
+        assert_eq!("15", batch.data.rows[0][0]);
+        assert!(!batch.has_more);
+
+        let plan = QueryPlan::explain(session, query).unwrap();
+        assert!(plan.logical.contains("FILTER"));
+        assert_eq!(
+            "Projection: 1/1 columns read; predicate: filter pushed into scan",
+            plan.pushdown_summary()
+        );
+
+        let missing_column = SqlJob::start(session, "SELECT nope FROM parquet", 0, 10);
+        assert!(missing_column.and_then(|job| job.wait()).is_err());
+        assert!(SqlJob::start(session, "  ", 0, 10).is_err());
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_attach_and_create_table() {
+        let path = write_ids("sql-attach");
+        let mut session = SqlSession::for_file(&path).unwrap();
+        session.attach("lookup", &path).unwrap();
+        assert!(session.attach("bad name", &path).is_err());
+        assert!(session.attach("missing", "/no/such/file.parquet").is_err());
+
+        let query = "SELECT count(*) FROM parquet JOIN lookup ON parquet.id = lookup.id";
+        let batch = SqlJob::start(&mut session, query, 0, 10)
+            .unwrap()
+            .wait()
+            .unwrap();
+        assert_eq!("25", batch.data.rows[0][0]);
+
+        let create = "CREATE TABLE small AS SELECT * FROM parquet WHERE id < 3";
+        QueryPlan::explain(&session, create).unwrap();
+        assert_eq!(vec!["lookup", "parquet"], session.tables());
+        SqlJob::start(&mut session, create, 0, 10)
+            .unwrap()
+            .wait()
+            .unwrap();
+        let batch = SqlJob::start(&mut session, "SELECT * FROM small", 0, 10)
+            .unwrap()
+            .wait()
+            .unwrap();
+        assert_eq!(3, batch.data.total_rows);
+        std::fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_parse_attach() {
+        assert_eq!(None, parse_attach("SELECT 1"));
+        assert_eq!(
+            Some(Ok((
+                "orders".to_string(),
+                "data/my orders.parquet".to_string()
+            ))),
+            parse_attach(" .attach orders  data/my orders.parquet ")
+        );
+        assert!(parse_attach(".attach orders").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_classify_plan_lines() {
+        assert_eq!(
+            PlanLine::Scan,
+            QueryPlan::classify("  Parquet SCAN [a.parquet]")
+        );
+        assert_eq!(
+            PlanLine::Pushdown,
+            QueryPlan::classify("  PROJECT 2/3 COLUMNS")
+        );
+        assert_eq!(
+            PlanLine::NotPushed,
+            QueryPlan::classify("  PROJECT */3 COLUMNS")
+        );
+        assert_eq!(
+            PlanLine::Pushdown,
+            QueryPlan::classify("  SELECTION: [(col(\"id\")) > (1)]")
+        );
+        assert_eq!(
+            PlanLine::NotPushed,
+            QueryPlan::classify("FILTER [(col(\"id\")) > (1)]")
+        );
+        assert_eq!(PlanLine::Plain, QueryPlan::classify("SORT BY [col(\"b\")]"));
+    }
+}
@@ -0,0 +1,527 @@
+//! Text buffer behind the SQL tab: a multi-line query with a cursor, undo,
+//! completion of column names and keywords, and a persistent query history.
+
+use std::fs;
+use std::path::PathBuf;
+
+use crate::config::state_dir;
+
+const SQL_KEYWORDS: &[&str] = &[
+    "SELECT", "FROM", "WHERE", "GROUP", "BY", "ORDER", "HAVING", "LIMIT", "OFFSET", "AS", "AND",
+    "OR", "NOT", "IN", "IS", "NULL", "LIKE", "ILIKE", "BETWEEN", "DISTINCT", "CASE", "WHEN",
+    "THEN", "ELSE", "END", "JOIN", "LEFT", "INNER", "ON", "UNION", "ALL", "ASC", "DESC", "COUNT",
+    "SUM", "AVG", "MIN", "MAX", "CAST", "WITH", "TRUE", "FALSE", "parquet", "EXPLAIN",
+];
+
+const MAX_UNDO: usize = 200;
+const MAX_HISTORY: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditKind {
+    Insert,
+    Delete,
+    Other,
+}
+
+/// Multi-line query text with a cursor kept on a char boundary.
+#[derive(Debug, Clone, Default)]
+pub struct SqlEditor {
+    text: String,
+    cursor: usize,
+    /// Column the cursor tries to keep when moving across lines of different lengths
+    goal_column: Option<usize>,
+    undo: Vec<(String, usize)>,
+    last_edit: Option<EditKind>,
+}
+
+impl SqlEditor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.trim().is_empty()
+    }
+
+    pub fn lines(&self) -> Vec<&str> {
+        self.text.split('\n').collect()
+    }
+
+    /// Cursor as (line, column), both counted in chars.
+    pub fn cursor_position(&self) -> (usize, usize) {
+        let before = &self.text[..self.cursor];
+        let line = before.matches('\n').count();
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        (line, before[line_start..].chars().count())
+    }
+
+    /// Replace the whole text, e.g. with a history entry; the cursor goes to the end.
+    pub fn set_text(&mut self, text: &str) {
+        self.snapshot(EditKind::Other);
+        self.text = text.to_string();
+        self.cursor = self.text.len();
+        self.goal_column = None;
+    }
+
+    pub fn clear(&mut self) {
+        if !self.text.is_empty() {
+            self.set_text("");
+        }
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        self.snapshot(EditKind::Insert);
+        self.text.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+        self.goal_column = None;
+    }
+
+    pub fn insert_str(&mut self, s: &str) {
+        self.snapshot(EditKind::Insert);
+        self.text.insert_str(self.cursor, s);
+        self.cursor += s.len();
+        self.goal_column = None;
+    }
+
+    pub fn backspace(&mut self) {
+        if let Some(prev) = self.prev_boundary(self.cursor) {
+            self.snapshot(EditKind::Delete);
+            self.text.replace_range(prev..self.cursor, "");
+            self.cursor = prev;
+            self.goal_column = None;
+        }
+    }
+
+    pub fn delete(&mut self) {
+        if let Some(next) = self.next_boundary(self.cursor) {
+            self.snapshot(EditKind::Delete);
+            self.text.replace_range(self.cursor..next, "");
+            self.goal_column = None;
+        }
+    }
+
+    pub fn delete_word_back(&mut self) {
+        let start = self.word_start(self.cursor);
+        if start < self.cursor {
+            self.snapshot(EditKind::Other);
+            self.text.replace_range(start..self.cursor, "");
+            self.cursor = start;
+            self.goal_column = None;
+        }
+    }
+
+    pub fn undo(&mut self) {
+        if let Some((text, cursor)) = self.undo.pop() {
+            self.text = text;
+            self.cursor = cursor;
+            self.goal_column = None;
+            self.last_edit = None;
+        }
+    }
+
+    pub fn left(&mut self) {
+        self.move_to(self.prev_boundary(self.cursor).unwrap_or(0));
+    }
+
+    pub fn right(&mut self) {
+        self.move_to(self.next_boundary(self.cursor).unwrap_or(self.text.len()));
+    }
+
+    pub fn word_left(&mut self) {
+        self.move_to(self.word_start(self.cursor));
+    }
+
+    pub fn word_right(&mut self) {
+        let rest = &self.text[self.cursor..];
+        let skip_space = rest.len() - rest.trim_start_matches(|c: char| !is_word_char(c)).len();
+        let word = rest[skip_space..]
+            .find(|c: char| !is_word_char(c))
+            .unwrap_or(rest.len() - skip_space);
+        self.move_to(self.cursor + skip_space + word);
+    }
+
+    pub fn home(&mut self) {
+        let line_start = self.text[..self.cursor].rfind('\n').map_or(0, |i| i + 1);
+        self.move_to(line_start);
+    }
+
+    pub fn end(&mut self) {
+        let line_end = self.text[self.cursor..]
+            .find('\n')
+            .map_or(self.text.len(), |i| self.cursor + i);
+        self.move_to(line_end);
+    }
+
+    /// Move one line up; returns false on the first line so the caller can go back in history.
+    pub fn up(&mut self) -> bool {
+        let (line, column) = self.cursor_position();
+        if line == 0 {
+            return false;
+        }
+        self.move_to_line(line - 1, self.goal_column.unwrap_or(column));
+        true
+    }
+
+    /// Move one line down; returns false on the last line.
+    pub fn down(&mut self) -> bool {
+        let (line, column) = self.cursor_position();
+        if line + 1 >= self.lines().len() {
+            return false;
+        }
+        self.move_to_line(line + 1, self.goal_column.unwrap_or(column));
+        true
+    }
+
+    /// Complete the word before the cursor from column names and SQL keywords.
+    /// A unique match is inserted in full, several matches are extended to their
+    /// common prefix; the matches are returned so the caller can list them.
+    pub fn complete(&mut self, columns: &[String]) -> Vec<String> {
+        let start = self.text[..self.cursor]
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| is_word_char(*c))
+            .last()
+            .map_or(self.cursor, |(i, _)| i);
+        let prefix = self.text[start..self.cursor].to_string();
+        if prefix.is_empty() {
+            return vec![];
+        }
+        let lower = prefix.to_lowercase();
+        let mut names: Vec<&str> = columns
+            .iter()
+            .map(String::as_str)
+            .chain(SQL_KEYWORDS.iter().copied())
+            .filter(|name| name.to_lowercase().starts_with(&lower) && name.len() > prefix.len())
+            .collect();
+        names.dedup();
+
+        let replacement = match names.as_slice() {
+            [] => return vec![],
+            [only] => format!("{} ", quote_identifier(only)),
+            _ => {
+                // Keep what was typed, the candidates may only agree up to case
+                let common = common_prefix(&names);
+                format!("{prefix}{}", common.get(prefix.len()..).unwrap_or(""))
+            }
+        };
+        if replacement != prefix {
+            self.snapshot(EditKind::Other);
+            self.text.replace_range(start..self.cursor, &replacement);
+            self.cursor = start + replacement.len();
+            self.goal_column = None;
+        }
+        names.iter().map(|name| quote_identifier(name)).collect()
+    }
+
+    fn snapshot(&mut self, kind: EditKind) {
+        // Runs of typing or deleting undo as one step
+        if kind == EditKind::Other || self.last_edit != Some(kind) {
+            self.undo.push((self.text.clone(), self.cursor));
+            if self.undo.len() > MAX_UNDO {
+                self.undo.remove(0);
+            }
+        }
+        self.last_edit = Some(kind);
+    }
+
+    fn move_to(&mut self, cursor: usize) {
+        self.cursor = cursor;
+        self.goal_column = None;
+        self.last_edit = None;
+    }
+
+    fn move_to_line(&mut self, line: usize, column: usize) {
+        let line_start: usize = self.lines()[..line].iter().map(|l| l.len() + 1).sum();
+        let text = self.lines()[line];
+        let offset = text
+            .char_indices()
+            .nth(column)
+            .map_or(text.len(), |(i, _)| i);
+        self.cursor = line_start + offset;
+        self.goal_column = Some(column);
+        self.last_edit = None;
+    }
+
+    fn prev_boundary(&self, from: usize) -> Option<usize> {
+        self.text[..from].char_indices().next_back().map(|(i, _)| i)
+    }
+
+    fn next_boundary(&self, from: usize) -> Option<usize> {
+        self.text[from..]
+            .chars()
+            .next()
+            .map(|c| from + c.len_utf8())
+    }
+
+    /// Start of the word ending at `from`, skipping whitespace before it.
+    fn word_start(&self, from: usize) -> usize {
+        let before = &self.text[..from];
+        let trimmed = before.trim_end_matches(|c: char| !is_word_char(c));
+        trimmed
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| is_word_char(*c))
+            .last()
+            .map_or(trimmed.len(), |(i, _)| i)
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '.'
+}
+
+/// Column names that are not plain identifiers need double quotes in SQL.
+fn quote_identifier(name: &str) -> String {
+    let plain = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if plain {
+        name.to_string()
+    } else {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+}
+
+/// Longest prefix shared by all words ignoring case, in the case of the first word.
+fn common_prefix(words: &[&str]) -> String {
+    let first = words[0];
+    let len = words[1..].iter().fold(first.len(), |len, word| {
+        first
+            .char_indices()
+            .zip(word.chars())
+            .take_while(|((i, a), b)| *i < len && a.eq_ignore_ascii_case(b))
+            .last()
+            .map_or(0, |((i, a), _)| i + a.len_utf8())
+    });
+    first[..len].to_string()
+}
+
+/// Queries run from the SQL tab, oldest first, saved to a file between sessions.
+/// Newlines are escaped so every entry takes one line of the file.
+#[derive(Debug, Clone, Default)]
+pub struct SqlHistory {
+    entries: Vec<String>,
+    /// Entry being shown while browsing with ↑ / ↓; `None` when editing a new query
+    position: Option<usize>,
+    /// The unsaved query to come back to after browsing past the newest entry
+    draft: String,
+    path: Option<PathBuf>,
+}
+
+impl SqlHistory {
+    /// Load the history from `$XDG_STATE_HOME/parqeye/sql_history`, falling back to
+    /// `~/.local/state`; a missing or unreadable file starts an empty history.
+    pub fn load() -> Self {
+        match state_dir().map(|dir| dir.join("sql_history")) {
+            Some(path) => Self::from_file(path),
+            None => Self::default(),
+        }
+    }
+
+    pub fn from_file(path: PathBuf) -> Self {
+        let entries = fs::read_to_string(&path)
+            .map(|content| content.lines().map(unescape).collect())
+            .unwrap_or_default();
+        Self {
+            entries,
+            position: None,
+            draft: String::new(),
+            path: Some(path),
+        }
+    }
+
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Record a query that was run and save the history. Saving is best-effort:
+    /// a read-only home directory should not stop queries from running.
+    pub fn push(&mut self, query: &str) {
+        self.position = None;
+        let query = query.trim();
+        if query.is_empty() || self.entries.last().is_some_and(|last| last == query) {
+            return;
+        }
+        self.entries.push(query.to_string());
+        if self.entries.len() > MAX_HISTORY {
+            self.entries.remove(0);
+        }
+        if let Some(path) = &self.path {
+            let content: String = self
+                .entries
+                .iter()
+                .map(|e| format!("{}\n", escape(e)))
+                .collect();
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir).ok();
+            }
+            fs::write(path, content).ok();
+        }
+    }
+
+    /// Step back to an older query; `current` is kept to return to later.
+    pub fn older(&mut self, current: &str) -> Option<&str> {
+        let position = match self.position {
+            None if self.entries.is_empty() => return None,
+            None => {
+                self.draft = current.to_string();
+                self.entries.len() - 1
+            }
+            Some(0) => return None,
+            Some(p) => p - 1,
+        };
+        self.position = Some(position);
+        Some(&self.entries[position])
+    }
+
+    /// Step forward to a newer query, ending at the query that was being typed.
+    pub fn newer(&mut self) -> Option<&str> {
+        let position = self.position?;
+        if position + 1 < self.entries.len() {
+            self.position = Some(position + 1);
+            Some(&self.entries[position + 1])
+        } else {
+            self.position = None;
+            Some(&self.draft)
+        }
+    }
+
+    /// Stop browsing, e.g. once the shown entry is edited.
+    pub fn reset_position(&mut self) {
+        self.position = None;
+    }
+}
+
+fn escape(query: &str) -> String {
+    query.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                out.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                out.push('\\');
+                chars.next();
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::test_path;
+
+    fn editor(text: &str) -> SqlEditor {
+        let mut editor = SqlEditor::new();
+        editor.insert_str(text);
+        editor
+    }
+
+    #[test]
+    fn test_cursor_movement() {
+        let mut e = editor("SELECT id,\n  name\nFROM parquet");
+        assert_eq!((2, 12), e.cursor_position());
+        assert!(e.up());
+        assert_eq!((1, 6), e.cursor_position());
+        assert!(e.up());
+        // Goal column survives the short line in between
+        assert_eq!((0, 10), e.cursor_position());
+        assert!(!e.up());
+        e.home();
+        e.word_right();
+        assert_eq!((0, 6), e.cursor_position());
+        e.word_right();
+        assert_eq!((0, 9), e.cursor_position());
+        e.word_left();
+        assert_eq!((0, 7), e.cursor_position());
+        e.end();
+        assert!(e.down());
+        assert!(e.down());
+        assert!(!e.down());
+    }
+
+    #[test]
+    fn test_editing_and_undo() {
+        let mut e = editor("SELECT 1");
+        e.insert_char('\n');
+        e.insert_str("FROM t");
+        e.left();
+        e.backspace();
+        e.delete();
+        assert_eq!("SELECT 1\nFROM", e.text());
+        // Consecutive deletes undo together
+        e.undo();
+        assert_eq!("SELECT 1\nFROM t", e.text());
+        e.end();
+        e.delete_word_back();
+        assert_eq!("SELECT 1\nFROM ", e.text());
+        e.insert_char('é');
+        e.left();
+        assert_eq!((1, 5), e.cursor_position());
+        e.undo();
+        e.undo();
+        e.undo();
+        assert_eq!("", e.text());
+    }
+
+    #[test]
+    fn test_completion() {
+        let columns = vec![
+            "country".to_string(),
+            "count_rows".to_string(),
+            "a.b".to_string(),
+        ];
+        let mut e = editor("sel");
+        assert_eq!(vec!["SELECT"], e.complete(&columns));
+        assert_eq!("SELECT ", e.text());
+
+        let mut e = editor("SELECT cou");
+        let matches = e.complete(&columns);
+        assert_eq!(3, matches.len());
+        assert_eq!("SELECT count", e.text());
+        e.insert_char('r');
+        e.complete(&columns);
+        assert_eq!("SELECT country ", e.text());
+
+        let mut e = editor("SELECT a.");
+        assert_eq!(vec!["\"a.b\""], e.complete(&columns));
+        assert_eq!("SELECT \"a.b\" ", e.text());
+        assert!(editor("SELECT ").complete(&columns).is_empty());
+    }
+
+    #[test]
+    fn test_history_browsing_and_persistence() {
+        let path = test_path("history");
+        fs::remove_file(&path).ok();
+        let mut history = SqlHistory::from_file(path.clone());
+        history.push("SELECT 1");
+        history.push("SELECT a,\n b\\n FROM t");
+        history.push("SELECT a,\n b\\n FROM t");
+
+        assert_eq!(Some("SELECT a,\n b\\n FROM t"), history.older("draft"));
+        assert_eq!(Some("SELECT 1"), history.older("ignored"));
+        assert_eq!(None, history.older("ignored"));
+        assert_eq!(Some("SELECT a,\n b\\n FROM t"), history.newer());
+        assert_eq!(Some("draft"), history.newer());
+        assert_eq!(None, history.newer());
+
+        let reloaded = SqlHistory::from_file(path.clone());
+        assert_eq!(history.entries(), reloaded.entries());
+        fs::remove_file(path).ok();
+    }
+}
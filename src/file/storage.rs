@@ -0,0 +1,239 @@
+//! Per-column storage breakdown: which columns (and nested groups) make the file big.
+
+use crate::file::schema::{FileSchema, SchemaInfo};
+
+#[derive(Debug, Clone)]
+pub struct StorageNode {
+    pub name: String,
+    pub is_group: bool,
+    pub compressed_size: u64,
+    pub uncompressed_size: u64,
+    pub leaf_count: usize,
+    /// Indices into `StorageBreakdown::nodes`
+    pub children: Vec<usize>,
+}
+
+/// Schema tree with sizes aggregated bottom-up from the leaf `ColumnStats`.
+/// Node 0 is the root.
+#[derive(Debug, Clone)]
+pub struct StorageBreakdown {
+    pub nodes: Vec<StorageNode>,
+    pub num_rows: usize,
+}
+
+impl StorageBreakdown {
+    pub fn from_schema(schema: &FileSchema, num_rows: usize) -> Self {
+        let mut nodes = vec![StorageNode {
+            name: "root".to_string(),
+            is_group: true,
+            compressed_size: 0,
+            uncompressed_size: 0,
+            leaf_count: 0,
+            children: vec![],
+        }];
+        // Stack of open group node indices; stack[d] is the parent of nodes at depth d + 1
+        let mut stack: Vec<usize> = vec![0];
+
+        for column in &schema.columns {
+            let (node, depth) = match column {
+                SchemaInfo::Root { .. } => continue,
+                SchemaInfo::Primitive {
+                    name, depth, stats, ..
+                } => (
+                    StorageNode {
+                        name: name.clone(),
+                        is_group: false,
+                        compressed_size: stats.total_compressed_size,
+                        uncompressed_size: stats.total_uncompressed_size,
+                        leaf_count: 1,
+                        children: vec![],
+                    },
+                    *depth,
+                ),
+                SchemaInfo::Group { name, depth, .. } => (
+                    StorageNode {
+                        name: name.clone(),
+                        is_group: true,
+                        compressed_size: 0,
+                        uncompressed_size: 0,
+                        leaf_count: 0,
+                        children: vec![],
+                    },
+                    *depth,
+                ),
+            };
+
+            stack.truncate(depth.max(1));
+            let parent = *stack.last().unwrap_or(&0);
+            let idx = nodes.len();
+            let is_group = node.is_group;
+            nodes.push(node);
+            nodes[parent].children.push(idx);
+            if is_group {
+                stack.push(idx);
+            }
+        }
+
+        Self::aggregate(&mut nodes, 0);
+        Self { nodes, num_rows }
+    }
+
+    fn aggregate(nodes: &mut [StorageNode], idx: usize) -> (u64, u64, usize) {
+        if !nodes[idx].is_group {
+            let n = &nodes[idx];
+            return (n.compressed_size, n.uncompressed_size, n.leaf_count);
+        }
+        let children = nodes[idx].children.clone();
+        let totals = children
+            .into_iter()
+            .map(|child| Self::aggregate(nodes, child))
+            .fold((0, 0, 0), |acc, c| (acc.0 + c.0, acc.1 + c.1, acc.2 + c.2));
+        let node = &mut nodes[idx];
+        node.compressed_size = totals.0;
+        node.uncompressed_size = totals.1;
+        node.leaf_count = totals.2;
+        totals
+    }
+
+    pub fn root(&self) -> &StorageNode {
+        &self.nodes[0]
+    }
+
+    pub fn total_compressed_size(&self) -> u64 {
+        self.root().compressed_size
+    }
+
+    /// Children of `node`, largest compressed size first.
+    pub fn ranked_children(&self, node: usize) -> Vec<usize> {
+        let mut children = self
+            .nodes
+            .get(node)
+            .map(|n| n.children.clone())
+            .unwrap_or_default();
+        children.sort_by(|a, b| {
+            self.nodes[*b]
+                .compressed_size
+                .cmp(&self.nodes[*a].compressed_size)
+        });
+        children
+    }
+
+    /// Share of the total compressed size of all columns, in percent.
+    pub fn percent_of_file(&self, node: usize) -> f64 {
+        let total = self.total_compressed_size();
+        if total == 0 {
+            return 0.0;
+        }
+        self.nodes[node].compressed_size as f64 * 100.0 / total as f64
+    }
+
+    pub fn bytes_per_row(&self, node: usize) -> f64 {
+        if self.num_rows == 0 {
+            return 0.0;
+        }
+        self.nodes[node].compressed_size as f64 / self.num_rows as f64
+    }
+
+    /// Names from the root down to `path.last()`, e.g. `root / nested`.
+    pub fn breadcrumb(&self, path: &[usize]) -> String {
+        std::iter::once(0)
+            .chain(path.iter().copied())
+            .filter_map(|idx| self.nodes.get(idx).map(|n| n.name.as_str()))
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::schema::{ColumnSchemaInfo, ColumnStats};
+
+    fn leaf(name: &str, depth: usize, compressed: u64) -> SchemaInfo {
+        SchemaInfo::Primitive {
+            name: name.to_string(),
+            display: name.to_string(),
+            depth,
+            info: Box::new(ColumnSchemaInfo {
+                name: name.to_string(),
+                repetition: "OPTIONAL".to_string(),
+                physical: "INT32".to_string(),
+                logical: String::new(),
+                codec: String::new(),
+                converted_type: String::new(),
+                encoding: String::new(),
+                dictionary_values: None,
+            }),
+            stats: ColumnStats {
+                min: None,
+                max: None,
+                nulls: 0,
+                distinct: None,
+                total_compressed_size: compressed,
+                total_uncompressed_size: compressed * 2,
+            },
+        }
+    }
+
+    fn group(name: &str, depth: usize) -> SchemaInfo {
+        SchemaInfo::Group {
+            name: name.to_string(),
+            display: name.to_string(),
+            depth,
+            repetition: "OPTIONAL".to_string(),
+        }
+    }
+
+    fn nested_schema() -> FileSchema {
+        FileSchema {
+            columns: vec![
+                SchemaInfo::Root {
+                    name: "root".to_string(),
+                    display: "root".to_string(),
+                },
+                leaf("id", 1, 10),
+                group("address", 1),
+                leaf("street", 2, 50),
+                group("geo", 2),
+                leaf("lat", 3, 20),
+                leaf("lon", 3, 20),
+                leaf("name", 1, 100),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_groups_aggregate_children() {
+        let breakdown = StorageBreakdown::from_schema(&nested_schema(), 10);
+
+        assert_eq!(200, breakdown.total_compressed_size());
+        assert_eq!(5, breakdown.root().leaf_count);
+
+        let address = &breakdown.nodes[2];
+        assert_eq!("address", address.name);
+        assert_eq!(90, address.compressed_size);
+        assert_eq!(180, address.uncompressed_size);
+        assert_eq!(3, address.leaf_count);
+    }
+
+    #[test]
+    fn test_ranked_children() {
+        let breakdown = StorageBreakdown::from_schema(&nested_schema(), 10);
+        let names: Vec<_> = breakdown
+            .ranked_children(0)
+            .into_iter()
+            .map(|idx| breakdown.nodes[idx].name.clone())
+            .collect();
+
+        assert_eq!(vec!["name", "address", "id"], names);
+    }
+
+    #[test]
+    fn test_percent_and_bytes_per_row() {
+        let breakdown = StorageBreakdown::from_schema(&nested_schema(), 10);
+
+        assert_eq!(50.0, breakdown.percent_of_file(7));
+        assert_eq!(10.0, breakdown.bytes_per_row(7));
+        assert_eq!("root / address / geo", breakdown.breadcrumb(&[2, 4]));
+    }
+}
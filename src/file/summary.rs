@@ -0,0 +1,131 @@
+//! Per-column summary of a result set (type, nulls, min, max, mean and distinct
+//! count), computed in one polars pass over the rows that were fetched.
+
+use polars::prelude::*;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnSummary {
+    pub name: String,
+    pub dtype: String,
+    pub null_count: usize,
+    /// Only for numeric, temporal, string and boolean columns.
+    pub min: Option<String>,
+    pub max: Option<String>,
+    /// Only for numeric and boolean columns (share of `true` values).
+    pub mean: Option<f64>,
+    /// Not computed for nested columns.
+    pub distinct: Option<usize>,
+}
+
+impl ColumnSummary {
+    pub fn from_frame(df: &DataFrame) -> Result<Vec<ColumnSummary>, String> {
+        let columns: Vec<(String, DataType)> = df
+            .get_columns()
+            .iter()
+            .map(|c| (c.name().to_string(), c.dtype().clone()))
+            .collect();
+        if columns.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut exprs = Vec::new();
+        for (i, (name, dtype)) in columns.iter().enumerate() {
+            exprs.push(
+                col(name.as_str())
+                    .null_count()
+                    .cast(DataType::UInt64)
+                    .alias(format!("{i}_nulls")),
+            );
+            if has_order(dtype) {
+                exprs.push(col(name.as_str()).min().alias(format!("{i}_min")));
+                exprs.push(col(name.as_str()).max().alias(format!("{i}_max")));
+            }
+            if dtype.is_primitive_numeric() || dtype.is_decimal() || dtype.is_bool() {
+                exprs.push(
+                    col(name.as_str())
+                        .cast(DataType::Float64)
+                        .mean()
+                        .alias(format!("{i}_mean")),
+                );
+            }
+            if !dtype.is_nested() {
+                exprs.push(
+                    col(name.as_str())
+                        .drop_nulls()
+                        .n_unique()
+                        .cast(DataType::UInt64)
+                        .alias(format!("{i}_distinct")),
+                );
+            }
+        }
+        let stats = df
+            .clone()
+            .lazy()
+            .select(exprs)
+            .collect()
+            .map_err(|e| e.to_string())?;
+
+        let value = |key: String| -> Option<AnyValue<'_>> {
+            stats
+                .column(&key)
+                .ok()
+                .and_then(|c| c.get(0).ok())
+                .filter(|v| !v.is_null())
+        };
+        Ok(columns
+            .into_iter()
+            .enumerate()
+            .map(|(i, (name, dtype))| ColumnSummary {
+                name,
+                dtype: dtype.to_string(),
+                null_count: value(format!("{i}_nulls"))
+                    .and_then(|v| v.extract::<usize>())
+                    .unwrap_or(0),
+                min: value(format!("{i}_min")).map(|v| format!("{v}")),
+                max: value(format!("{i}_max")).map(|v| format!("{v}")),
+                mean: value(format!("{i}_mean")).and_then(|v| v.extract::<f64>()),
+                distinct: value(format!("{i}_distinct")).and_then(|v| v.extract::<usize>()),
+            })
+            .collect())
+    }
+}
+
+fn has_order(dtype: &DataType) -> bool {
+    dtype.is_primitive_numeric()
+        || dtype.is_decimal()
+        || dtype.is_temporal()
+        || dtype.is_string()
+        || dtype.is_bool()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary() {
+        let df = df!(
+            "id" => [1i64, 2, 3, 3],
+            "name" => [Some("b"), None, Some("a"), Some("a")],
+            "flag" => [true, false, true, true],
+        )
+        .unwrap();
+        let summary = ColumnSummary::from_frame(&df).unwrap();
+        assert_eq!(3, summary.len());
+
+        assert_eq!("id", summary[0].name);
+        assert_eq!("i64", summary[0].dtype);
+        assert_eq!(0, summary[0].null_count);
+        assert_eq!(Some("1".to_string()), summary[0].min);
+        assert_eq!(Some("3".to_string()), summary[0].max);
+        assert_eq!(Some(2.25), summary[0].mean);
+        assert_eq!(Some(3), summary[0].distinct);
+
+        assert_eq!(1, summary[1].null_count);
+        assert_eq!(None, summary[1].mean);
+        assert_eq!(Some(2), summary[1].distinct);
+
+        assert_eq!(Some(0.75), summary[2].mean);
+        assert_eq!(Some(2), summary[2].distinct);
+    }
+}
@@ -39,6 +39,64 @@ pub fn truncate_str(s: &str, width: usize) -> String {
     }
 }
 
+/// Break `text` into lines of at most `width` chars, at spaces where possible and
+/// mid-word otherwise; newlines in the text are kept.
+pub fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    for paragraph in text.split('\n') {
+        let mut line = String::new();
+        let mut len = 0;
+        for word in paragraph.split(' ') {
+            let word_len = word.chars().count();
+            if len > 0 && len + 1 + word_len > width {
+                lines.push(std::mem::take(&mut line));
+                len = 0;
+            } else if len > 0 {
+                line.push(' ');
+                len += 1;
+            }
+            let mut chars = word.chars().peekable();
+            while chars.peek().is_some() {
+                if len == width {
+                    lines.push(std::mem::take(&mut line));
+                    len = 0;
+                }
+                let take = width - len;
+                let chunk: String = chars.by_ref().take(take).collect();
+                len += chunk.chars().count();
+                line.push_str(&chunk);
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// A cell value for reading in full: JSON objects and arrays pretty-printed, anything
+/// else as is.
+pub fn pretty_value(value: &str) -> String {
+    let trimmed = value.trim_start();
+    if (trimmed.starts_with('{') || trimmed.starts_with('['))
+        && let Ok(json) = serde_json::from_str::<serde_json::Value>(value)
+    {
+        return serde_json::to_string_pretty(&json).unwrap_or_else(|_| value.to_string());
+    }
+    value.to_string()
+}
+
+/// Compact display of a statistic: integral values without decimals, very large or
+/// small values in scientific notation.
+pub fn format_number(v: f64) -> String {
+    if v.fract() == 0.0 && v.abs() < 1e15 {
+        format!("{v:.0}")
+    } else if v.abs() >= 1e6 || v.abs() < 1e-3 {
+        format!("{v:.3e}")
+    } else {
+        format!("{v:.3}")
+    }
+}
+
 pub fn commas(n: u64) -> String {
     let s = n.to_string();
     let mut out = String::with_capacity(s.len() + s.len() / 3);
@@ -110,6 +168,22 @@ mod tests {
         assert_eq!(truncate_str("hello", 3), "he…");
     }
 
+    #[test]
+    fn test_wrap_text() {
+        assert_eq!(vec!["hello", "world"], wrap_text("hello world", 8));
+        assert_eq!(vec!["hello world"], wrap_text("hello world", 11));
+        assert_eq!(vec!["abcd", "efgh", "ij k"], wrap_text("abcdefghij k", 4));
+        assert_eq!(vec!["a", "", "b"], wrap_text("a\n\nb", 4));
+        assert_eq!(vec![""], wrap_text("", 4));
+    }
+
+    #[test]
+    fn test_pretty_value() {
+        assert_eq!("{\n  \"a\": [\n    1\n  ]\n}", pretty_value("{\"a\":[1]}"));
+        assert_eq!("{1,\"x\"}", pretty_value("{1,\"x\"}"));
+        assert_eq!("plain", pretty_value("plain"));
+    }
+
     #[test]
     fn test_commas() {
         assert_eq!(commas(0), "0");
@@ -0,0 +1,126 @@
+//! Watch mode (`--watch`): notice when the file changes on disk, for example when a
+//! job rewrites it, and read it again in the background.
+
+use crate::file::parquet_ctx::ParquetCtx;
+use std::fs;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::SystemTime;
+
+/// How often the file is checked for changes.
+pub const WATCH_INTERVAL_MS: u64 = 500;
+
+/// What tells a rewritten file apart: its modification time and size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileStamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl FileStamp {
+    /// The stamp of the file at `path`, `None` while it does not exist.
+    pub fn of(path: &str) -> Option<Self> {
+        let meta = fs::metadata(path).ok()?;
+        Some(Self {
+            modified: meta.modified().ok(),
+            len: meta.len(),
+        })
+    }
+}
+
+pub struct FileWatch {
+    path: String,
+    /// Stamp of the file as last read
+    loaded: Option<FileStamp>,
+    /// Stamp seen by the previous check
+    seen: Option<FileStamp>,
+    /// Reload running in the background, with the stamp it started from
+    reload: Option<(FileStamp, Receiver<Result<ParquetCtx, String>>)>,
+}
+
+impl FileWatch {
+    /// Watch the file at `path`, as it is now.
+    pub fn new(path: &str) -> Self {
+        let stamp = FileStamp::of(path);
+        Self {
+            path: path.to_string(),
+            loaded: stamp,
+            seen: stamp,
+            reload: None,
+        }
+    }
+
+    /// Check the file: start reading it again once it changed and then held still for
+    /// one check, so that a file still being written is not read half way. Returns the
+    /// result of a finished reload.
+    pub fn poll(&mut self) -> Option<Result<ParquetCtx, String>> {
+        if let Some((stamp, receiver)) = &self.reload {
+            let result = match receiver.try_recv() {
+                Ok(result) => result,
+                Err(TryRecvError::Empty) => return None,
+                Err(TryRecvError::Disconnected) => Err("the reload stopped".to_string()),
+            };
+            // A failed read is not retried until the file changes again
+            self.loaded = Some(*stamp);
+            self.reload = None;
+            return Some(result);
+        }
+
+        let stamp = FileStamp::of(&self.path);
+        let settled = stamp == self.seen;
+        self.seen = stamp;
+        if let Some(stamp) = stamp
+            && settled
+            && Some(stamp) != self.loaded
+        {
+            let (sender, receiver) = mpsc::channel();
+            let path = self.path.clone();
+            thread::spawn(move || {
+                let result = ParquetCtx::from_file(&path).map_err(|e| e.to_string());
+                // The receiver is gone when the file was closed meanwhile
+                let _ = sender.send(result);
+            });
+            self.reload = Some((stamp, receiver));
+        }
+        None
+    }
+
+    pub fn is_reloading(&self) -> bool {
+        self.reload.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::test_path;
+
+    #[test]
+    fn test_reload_after_change_settles() {
+        let path = test_path("watch");
+        let path = path.to_str().unwrap().to_string();
+        fs::write(&path, "a").unwrap();
+        let mut watch = FileWatch::new(&path);
+        assert!(watch.poll().is_none());
+        assert!(!watch.is_reloading());
+
+        // Seen changing first, read once it holds still
+        fs::write(&path, "ab").unwrap();
+        assert!(watch.poll().is_none());
+        assert!(!watch.is_reloading());
+        assert!(watch.poll().is_none());
+        assert!(watch.is_reloading());
+
+        let result = loop {
+            if let Some(result) = watch.poll() {
+                break result;
+            }
+            thread::sleep(std::time::Duration::from_millis(10));
+        };
+        // Not a Parquet file: the error is reported once, not retried
+        assert!(result.is_err());
+        assert!(watch.poll().is_none());
+        assert!(!watch.is_reloading());
+        fs::remove_file(&path).ok();
+    }
+}
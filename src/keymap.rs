@@ -0,0 +1,707 @@
+//! Named actions and the key chords bound to them.
+//!
+//! A [`KeyMap`] resolves the keys a user presses into the action they are bound to,
+//! so a preset or the config file can bind `j`, `Ctrl+D` or a sequence such as `g g`.
+//! Each action starts out bound to its built-in key (`↓`, `u`, `Ctrl+X`, ...), which
+//! is unbound once the action is bound to other keys.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    NextTab,
+    PrevTab,
+    Cancel,
+    Help,
+    Palette,
+    Files,
+    Up,
+    Down,
+    Left,
+    Right,
+    PageUp,
+    PageDown,
+    Top,
+    Bottom,
+    Search,
+    RowDetail,
+    Sort,
+    Find,
+    FindNext,
+    FindPrev,
+    GroupBy,
+    Columns,
+    NarrowColumn,
+    WidenColumn,
+    ExpandCell,
+    WrapRow,
+    Mark,
+    Yank,
+    Profile,
+    Compression,
+    Pushdown,
+    EditQuery,
+    FetchMore,
+    Explain,
+    Summary,
+    Chart,
+    SavedQueries,
+}
+
+impl Action {
+    pub const ALL: [Action; 38] = [
+        Action::Quit,
+        Action::NextTab,
+        Action::PrevTab,
+        Action::Cancel,
+        Action::Help,
+        Action::Palette,
+        Action::Files,
+        Action::Up,
+        Action::Down,
+        Action::Left,
+        Action::Right,
+        Action::PageUp,
+        Action::PageDown,
+        Action::Top,
+        Action::Bottom,
+        Action::Search,
+        Action::RowDetail,
+        Action::Sort,
+        Action::Find,
+        Action::FindNext,
+        Action::FindPrev,
+        Action::GroupBy,
+        Action::Columns,
+        Action::NarrowColumn,
+        Action::WidenColumn,
+        Action::ExpandCell,
+        Action::WrapRow,
+        Action::Mark,
+        Action::Yank,
+        Action::Profile,
+        Action::Compression,
+        Action::Pushdown,
+        Action::EditQuery,
+        Action::FetchMore,
+        Action::Explain,
+        Action::Summary,
+        Action::Chart,
+        Action::SavedQueries,
+    ];
+
+    /// Name used in the config file.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::NextTab => "next_tab",
+            Action::PrevTab => "prev_tab",
+            Action::Cancel => "cancel",
+            Action::Help => "help",
+            Action::Palette => "palette",
+            Action::Files => "files",
+            Action::Up => "up",
+            Action::Down => "down",
+            Action::Left => "left",
+            Action::Right => "right",
+            Action::PageUp => "page_up",
+            Action::PageDown => "page_down",
+            Action::Top => "top",
+            Action::Bottom => "bottom",
+            Action::Search => "search",
+            Action::RowDetail => "row_detail",
+            Action::Sort => "sort",
+            Action::Find => "find",
+            Action::FindNext => "find_next",
+            Action::FindPrev => "find_prev",
+            Action::GroupBy => "group_by",
+            Action::Columns => "columns",
+            Action::NarrowColumn => "narrow_column",
+            Action::WidenColumn => "widen_column",
+            Action::ExpandCell => "expand_cell",
+            Action::WrapRow => "wrap_row",
+            Action::Mark => "mark",
+            Action::Yank => "yank",
+            Action::Profile => "profile",
+            Action::Compression => "compression",
+            Action::Pushdown => "pushdown",
+            Action::EditQuery => "edit_query",
+            Action::FetchMore => "fetch_more",
+            Action::Explain => "explain",
+            Action::Summary => "summary",
+            Action::Chart => "chart",
+            Action::SavedQueries => "saved_queries",
+        }
+    }
+
+    /// What the action does, for the help overlay and the command palette.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Action::Quit => "Quit",
+            Action::NextTab => "Next tab",
+            Action::PrevTab => "Previous tab",
+            Action::Cancel => "Cancel, clear the filter or close",
+            Action::Help => "Show key bindings",
+            Action::Palette => "Command palette",
+            Action::Files => "Open files: switch, open or close",
+            Action::Up => "Move up",
+            Action::Down => "Move down",
+            Action::Left => "Move left",
+            Action::Right => "Move right",
+            Action::PageUp => "Page up",
+            Action::PageDown => "Page down",
+            Action::Top => "Go to the first row",
+            Action::Bottom => "Go to the last row",
+            Action::Search => "Filter rows",
+            Action::RowDetail => "Open the row detail",
+            Action::Sort => "Sort by the current column",
+            Action::Find => "Find in the table",
+            Action::FindNext => "Next find match",
+            Action::FindPrev => "Previous find match",
+            Action::GroupBy => "Value counts of the current column",
+            Action::Columns => "Choose, pin and reorder columns",
+            Action::NarrowColumn => "Narrow the current column",
+            Action::WidenColumn => "Widen the current column",
+            Action::ExpandCell => "Expand the current cell",
+            Action::WrapRow => "Wrap the selected row",
+            Action::Mark => "Mark a corner of a selection",
+            Action::Yank => "Copy a cell, row, column name or selection",
+            Action::Profile => "Profile the selected column",
+            Action::Compression => "Simulate compression codecs",
+            Action::Pushdown => "Preview predicate pushdown",
+            Action::EditQuery => "Edit the query",
+            Action::FetchMore => "Fetch more result rows",
+            Action::Explain => "Explain the query",
+            Action::Summary => "Summarize the result columns",
+            Action::Chart => "Chart the result",
+            Action::SavedQueries => "Open saved queries",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Action> {
+        Action::ALL.into_iter().find(|a| a.name() == name)
+    }
+
+    /// Tab the action belongs to; `None` for actions available everywhere. Actions of
+    /// different tabs may share a key.
+    pub fn tab(&self) -> Option<&'static str> {
+        match self {
+            Action::Sort
+            | Action::Find
+            | Action::FindNext
+            | Action::FindPrev
+            | Action::GroupBy
+            | Action::Columns => Some("Visualize"),
+            Action::Profile | Action::Compression => Some("Schema"),
+            Action::Pushdown => Some("Row Groups"),
+            Action::EditQuery
+            | Action::FetchMore
+            | Action::Explain
+            | Action::Summary
+            | Action::Chart
+            | Action::SavedQueries => Some("SQL"),
+            _ => None,
+        }
+    }
+
+    /// The key the action is bound to unless a preset or the config file binds others.
+    pub fn key(&self) -> KeyChord {
+        let char = |c| KeyChord::new(KeyCode::Char(c), KeyModifiers::NONE);
+        match self {
+            Action::Quit => KeyChord::new(KeyCode::Char('x'), KeyModifiers::CONTROL),
+            Action::NextTab => KeyChord::new(KeyCode::Tab, KeyModifiers::NONE),
+            Action::PrevTab => KeyChord::new(KeyCode::BackTab, KeyModifiers::NONE),
+            Action::Cancel => KeyChord::new(KeyCode::Esc, KeyModifiers::NONE),
+            Action::Help => char('?'),
+            Action::Palette => char(':'),
+            Action::Files => char('b'),
+            Action::Up => KeyChord::new(KeyCode::Up, KeyModifiers::NONE),
+            Action::Down => KeyChord::new(KeyCode::Down, KeyModifiers::NONE),
+            Action::Left => KeyChord::new(KeyCode::Left, KeyModifiers::NONE),
+            Action::Right => KeyChord::new(KeyCode::Right, KeyModifiers::NONE),
+            Action::PageUp => char('u'),
+            Action::PageDown => char('d'),
+            Action::Top => KeyChord::new(KeyCode::Home, KeyModifiers::NONE),
+            Action::Bottom => KeyChord::new(KeyCode::End, KeyModifiers::NONE),
+            Action::Search => char('/'),
+            Action::RowDetail => char('v'),
+            Action::Sort => char('s'),
+            Action::Find => char('f'),
+            Action::FindNext => char('n'),
+            Action::FindPrev => char('N'),
+            Action::GroupBy => char('g'),
+            Action::Columns => char('c'),
+            Action::NarrowColumn => char('<'),
+            Action::WidenColumn => char('>'),
+            Action::ExpandCell => char('x'),
+            Action::WrapRow => char('w'),
+            Action::Mark => char(' '),
+            Action::Yank => char('y'),
+            Action::Profile => char('p'),
+            Action::Compression => char('c'),
+            Action::Pushdown => char('w'),
+            Action::EditQuery => char('i'),
+            Action::FetchMore => char('m'),
+            Action::Explain => char('e'),
+            Action::Summary => char('s'),
+            Action::Chart => char('c'),
+            Action::SavedQueries => char('o'),
+        }
+    }
+}
+
+/// A key with its modifiers. Shift is folded into the character, so `G` and
+/// `Shift+g` are the same chord.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let modifiers = match code {
+            KeyCode::Char(_) | KeyCode::BackTab => modifiers - KeyModifiers::SHIFT,
+            _ => modifiers,
+        };
+        Self { code, modifiers }
+    }
+
+    /// Parse `q`, `G`, `ctrl+d`, `alt+<`, `shift+tab`, `pgdn`, `space`, ...
+    pub fn parse(text: &str) -> Result<KeyChord, String> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = text;
+        loop {
+            let lower = rest.to_ascii_lowercase();
+            let (modifier, len) = if lower.starts_with("ctrl+") {
+                (KeyModifiers::CONTROL, 5)
+            } else if lower.starts_with("alt+") {
+                (KeyModifiers::ALT, 4)
+            } else if lower.starts_with("shift+") {
+                (KeyModifiers::SHIFT, 6)
+            } else {
+                break;
+            };
+            // A lone "+" after a modifier is the key itself
+            if rest.len() == len {
+                break;
+            }
+            modifiers |= modifier;
+            rest = &rest[len..];
+        }
+
+        let code = match rest.to_ascii_lowercase().as_str() {
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "pgup" | "pageup" => KeyCode::PageUp,
+            "pgdn" | "pagedown" => KeyCode::PageDown,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "enter" => KeyCode::Enter,
+            "esc" => KeyCode::Esc,
+            "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "space" => KeyCode::Char(' '),
+            _ => {
+                let mut chars = rest.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) if modifiers.contains(KeyModifiers::SHIFT) => {
+                        KeyCode::Char(c.to_ascii_uppercase())
+                    }
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => return Err(format!("unknown key '{text}'")),
+                }
+            }
+        };
+        Ok(KeyChord::new(code, modifiers))
+    }
+
+    /// Short label for hints, e.g. `↓`, `Ctrl+D`, `G`.
+    pub fn label(&self) -> String {
+        let key = match self.code {
+            KeyCode::Up => "↑".to_string(),
+            KeyCode::Down => "↓".to_string(),
+            KeyCode::Left => "←".to_string(),
+            KeyCode::Right => "→".to_string(),
+            KeyCode::PageUp => "PgUp".to_string(),
+            KeyCode::PageDown => "PgDn".to_string(),
+            KeyCode::BackTab => "Shift+Tab".to_string(),
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) if self.modifiers.is_empty() => c.to_string(),
+            KeyCode::Char(c) => c.to_ascii_uppercase().to_string(),
+            code => code.to_string(),
+        };
+        let mut label = String::new();
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            label.push_str("Ctrl+");
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            label.push_str("Alt+");
+        }
+        label + &key
+    }
+}
+
+impl From<KeyEvent> for KeyChord {
+    fn from(event: KeyEvent) -> Self {
+        KeyChord::new(event.code, event.modifiers)
+    }
+}
+
+/// One or more chords pressed in order, written space-separated: `g g`, `ctrl+x ctrl+c`.
+pub type KeySequence = Vec<KeyChord>;
+
+pub fn parse_sequence(text: &str) -> Result<KeySequence, String> {
+    let sequence = text
+        .split_whitespace()
+        .map(KeyChord::parse)
+        .collect::<Result<KeySequence, String>>()?;
+    if sequence.is_empty() {
+        return Err("empty key binding".to_string());
+    }
+    Ok(sequence)
+}
+
+/// What a key press amounts to in a [`KeyMap`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyInput {
+    /// The first keys of a multi-key binding, waiting for the rest
+    Pending,
+    Action(Action),
+    /// A key bound to no action, such as Enter
+    Unbound(KeyEvent),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    Default,
+    Vim,
+    Emacs,
+}
+
+impl Preset {
+    pub fn from_name(name: &str) -> Option<Preset> {
+        match name {
+            "default" => Some(Preset::Default),
+            "vim" => Some(Preset::Vim),
+            "emacs" => Some(Preset::Emacs),
+            _ => None,
+        }
+    }
+
+    /// Bindings that differ from the built-in keys.
+    fn overrides(&self) -> &'static [(Action, &'static [&'static str])] {
+        match self {
+            Preset::Default => &[],
+            Preset::Vim => &[
+                (Action::Quit, &["q", "ctrl+x"]),
+                (Action::Up, &["k", "up"]),
+                (Action::Down, &["j", "down"]),
+                (Action::Left, &["h", "left"]),
+                (Action::Right, &["l", "right"]),
+                (Action::PageUp, &["ctrl+u", "pgup"]),
+                (Action::PageDown, &["ctrl+d", "pgdn"]),
+                (Action::Top, &["g g", "home"]),
+                (Action::Bottom, &["G", "end"]),
+                (Action::GroupBy, &["g b"]),
+            ],
+            Preset::Emacs => &[
+                (Action::Quit, &["ctrl+x ctrl+c"]),
+                (Action::Cancel, &["ctrl+g", "esc"]),
+                (Action::Up, &["ctrl+p", "up"]),
+                (Action::Down, &["ctrl+n", "down"]),
+                (Action::Left, &["ctrl+b", "left"]),
+                (Action::Right, &["ctrl+f", "right"]),
+                (Action::PageUp, &["alt+v", "pgup"]),
+                (Action::PageDown, &["ctrl+v", "pgdn"]),
+                (Action::Top, &["alt+<", "home"]),
+                (Action::Bottom, &["alt+>", "end"]),
+                (Action::Search, &["ctrl+s", "/"]),
+                (Action::Palette, &[":", "alt+x"]),
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct KeyMap {
+    bindings: HashMap<Action, Vec<KeySequence>>,
+}
+
+impl Default for KeyMap {
+    fn default() -> Self {
+        KeyMap::preset(Preset::Default)
+    }
+}
+
+impl KeyMap {
+    pub fn preset(preset: Preset) -> KeyMap {
+        let mut bindings: HashMap<Action, Vec<KeySequence>> = Action::ALL
+            .into_iter()
+            .map(|action| (action, vec![vec![action.key()]]))
+            .collect();
+        // The palette also opens with Ctrl+P unless the preset binds it elsewhere
+        if let Some(palette) = bindings.get_mut(&Action::Palette) {
+            palette.push(vec![KeyChord::new(
+                KeyCode::Char('p'),
+                KeyModifiers::CONTROL,
+            )]);
+        }
+        let mut keymap = KeyMap { bindings };
+        for (action, keys) in preset.overrides() {
+            let sequences = keys
+                .iter()
+                .map(|k| parse_sequence(k).expect("preset key bindings are valid"))
+                .collect();
+            keymap.bind(*action, sequences);
+        }
+        keymap
+    }
+
+    /// Replace the bindings of an action. Its keys are taken from the other actions
+    /// of the same tab, or the other actions available everywhere, so that a key
+    /// means one thing.
+    pub fn bind(&mut self, action: Action, sequences: Vec<KeySequence>) {
+        for (other, bound) in self.bindings.iter_mut() {
+            if *other != action && other.tab() == action.tab() {
+                bound.retain(|sequence| !sequences.contains(sequence));
+            }
+        }
+        self.bindings.insert(action, sequences);
+    }
+
+    pub fn bindings(&self, action: Action) -> &[KeySequence] {
+        self.bindings.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    /// Label of the first binding of an action, for hints.
+    pub fn hint(&self, action: Action) -> String {
+        match self.bindings(action).first() {
+            Some(sequence) => sequence
+                .iter()
+                .map(KeyChord::label)
+                .collect::<Vec<_>>()
+                .join(" "),
+            None => action.key().label(),
+        }
+    }
+
+    /// Labels of every binding of an action, e.g. `k, ↑`.
+    pub fn labels(&self, action: Action) -> String {
+        self.bindings(action)
+            .iter()
+            .map(|sequence| {
+                sequence
+                    .iter()
+                    .map(KeyChord::label)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Bindings of every action, grouped by the tab they belong to, global ones first:
+    /// (tab, [(keys, description)]).
+    pub fn help(&self) -> Vec<(&'static str, Vec<(String, &'static str)>)> {
+        let mut sections: Vec<(&'static str, Vec<(String, &'static str)>)> = Vec::new();
+        for action in Action::ALL {
+            let tab = action.tab().unwrap_or("Everywhere");
+            let entry = (self.labels(action), action.description());
+            match sections.iter_mut().find(|(name, _)| *name == tab) {
+                Some((_, entries)) => entries.push(entry),
+                None => sections.push((tab, vec![entry])),
+            }
+        }
+        sections
+    }
+
+    /// Resolve a key press into the action it is bound to in `tab`. `pending` holds
+    /// the keys typed so far of a multi-key binding. Bindings of the tab win over
+    /// bindings of actions available everywhere.
+    pub fn resolve(&self, tab: &str, pending: &mut KeySequence, event: KeyEvent) -> KeyInput {
+        let of_tab = Action::ALL.iter().filter(|a| a.tab() == Some(tab));
+        let global = Action::ALL.iter().filter(|a| a.tab().is_none());
+        let actions: Vec<Action> = of_tab.chain(global).copied().collect();
+        self.resolve_among(&actions, pending, event)
+    }
+
+    /// Resolve a key press among the bindings of `actions` only, the first of them
+    /// winning, e.g. the actions that still answer while a popup takes keys as typed.
+    pub fn resolve_among(
+        &self,
+        actions: &[Action],
+        pending: &mut KeySequence,
+        event: KeyEvent,
+    ) -> KeyInput {
+        pending.push(KeyChord::from(event));
+        let mut is_prefix = false;
+        for action in actions {
+            for sequence in self.bindings(*action) {
+                if sequence == pending {
+                    pending.clear();
+                    return KeyInput::Action(*action);
+                }
+                is_prefix |= sequence.len() > pending.len() && sequence.starts_with(pending);
+            }
+        }
+        if is_prefix {
+            return KeyInput::Pending;
+        }
+        // An unfinished sequence followed by a key that does not continue it: drop the
+        // sequence and try the key on its own
+        if pending.len() > 1 {
+            pending.clear();
+            return self.resolve_among(actions, pending, event);
+        }
+        pending.clear();
+        KeyInput::Unbound(event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_parse_chords() {
+        assert_eq!(
+            KeyChord::new(KeyCode::Char('d'), KeyModifiers::CONTROL),
+            KeyChord::parse("ctrl+d").unwrap()
+        );
+        assert_eq!(
+            KeyChord::new(KeyCode::Char('G'), KeyModifiers::NONE),
+            KeyChord::parse("shift+g").unwrap()
+        );
+        assert_eq!(
+            KeyChord::parse("G").unwrap(),
+            KeyChord::parse("shift+g").unwrap()
+        );
+        assert_eq!(
+            KeyChord::new(KeyCode::Char('+'), KeyModifiers::ALT),
+            KeyChord::parse("alt++").unwrap()
+        );
+        assert_eq!(
+            KeyChord::new(KeyCode::BackTab, KeyModifiers::NONE),
+            KeyChord::parse("shift+tab").unwrap()
+        );
+        assert!(KeyChord::parse("hyper+x").is_err());
+        assert_eq!(2, parse_sequence("g g").unwrap().len());
+        assert_eq!("Ctrl+D", KeyChord::parse("ctrl+d").unwrap().label());
+        assert_eq!("↓", KeyChord::parse("down").unwrap().label());
+    }
+
+    #[test]
+    fn test_vim_resolution() {
+        let keys = KeyMap::preset(Preset::Vim);
+        let mut pending = Vec::new();
+        let mut resolve = |event| keys.resolve("Visualize", &mut pending, event);
+
+        assert_eq!(
+            KeyInput::Action(Action::Down),
+            resolve(key(KeyCode::Char('j')))
+        );
+        assert_eq!(
+            KeyInput::Action(Action::Quit),
+            resolve(key(KeyCode::Char('q')))
+        );
+        // "g g" goes to the top, "g b" groups by; "G" arrives with Shift
+        assert_eq!(KeyInput::Pending, resolve(key(KeyCode::Char('g'))));
+        assert_eq!(
+            KeyInput::Action(Action::Top),
+            resolve(key(KeyCode::Char('g')))
+        );
+        assert_eq!(KeyInput::Pending, resolve(key(KeyCode::Char('g'))));
+        assert_eq!(
+            KeyInput::Action(Action::GroupBy),
+            resolve(key(KeyCode::Char('b')))
+        );
+        assert_eq!(
+            KeyInput::Action(Action::Bottom),
+            resolve(KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT))
+        );
+        // A broken sequence falls back to the last key
+        assert_eq!(KeyInput::Pending, resolve(key(KeyCode::Char('g'))));
+        assert_eq!(
+            KeyInput::Action(Action::Up),
+            resolve(key(KeyCode::Char('k')))
+        );
+        // Ctrl+D pages down; plain "d" is no longer bound to anything
+        assert_eq!(
+            KeyInput::Action(Action::PageDown),
+            resolve(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL))
+        );
+        assert_eq!(
+            KeyInput::Unbound(key(KeyCode::Char('d'))),
+            resolve(key(KeyCode::Char('d')))
+        );
+        assert_eq!("k", keys.hint(Action::Up));
+        assert_eq!("g g", keys.hint(Action::Top));
+    }
+
+    #[test]
+    fn test_rebinding_frees_the_built_in_key() {
+        let mut keys = KeyMap::default();
+        keys.bind(Action::Sort, vec![parse_sequence("o").unwrap()]);
+        let mut pending = Vec::new();
+        assert_eq!(
+            KeyInput::Action(Action::Sort),
+            keys.resolve("Visualize", &mut pending, key(KeyCode::Char('o')))
+        );
+        assert_eq!(
+            KeyInput::Unbound(key(KeyCode::Char('s'))),
+            keys.resolve("Visualize", &mut pending, key(KeyCode::Char('s')))
+        );
+    }
+
+    #[test]
+    fn test_help() {
+        let help = KeyMap::preset(Preset::Vim).help();
+        assert_eq!("Everywhere", help[0].0);
+        assert!(help[0].1.contains(&("k, ↑".to_string(), "Move up")));
+        let entries: usize = help.iter().map(|(_, entries)| entries.len()).sum();
+        assert_eq!(Action::ALL.len(), entries);
+    }
+
+    #[test]
+    fn test_binding_takes_the_key() {
+        let ctrl_p = KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL);
+        let mut pending = Vec::new();
+        assert_eq!(
+            KeyInput::Action(Action::Palette),
+            KeyMap::default().resolve("SQL", &mut pending, ctrl_p)
+        );
+        let emacs = KeyMap::preset(Preset::Emacs);
+        assert_eq!(
+            KeyInput::Action(Action::Up),
+            emacs.resolve("SQL", &mut pending, ctrl_p)
+        );
+        assert_eq!(":, Alt+X", emacs.labels(Action::Palette));
+    }
+
+    #[test]
+    fn test_tab_scope() {
+        let mut keys = KeyMap::default();
+        keys.bind(Action::Chart, vec![parse_sequence("x").unwrap()]);
+        let mut pending = Vec::new();
+        assert_eq!(
+            KeyInput::Action(Action::Chart),
+            keys.resolve("SQL", &mut pending, key(KeyCode::Char('x')))
+        );
+        assert_eq!(
+            KeyInput::Action(Action::ExpandCell),
+            keys.resolve("Schema", &mut pending, key(KeyCode::Char('x')))
+        );
+    }
+}
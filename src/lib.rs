@@ -1,7 +1,12 @@
 pub mod app;
+pub mod clipboard;
 pub mod components;
+pub mod config;
 pub mod file;
+pub mod keymap;
+pub mod palette;
 pub mod tabs;
+pub mod theme;
 pub mod ui;
 
 pub use app::App;
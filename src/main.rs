@@ -1,8 +1,11 @@
 use parqeye::app::App;
+use parqeye::config::Config;
 use parqeye::file::parquet_ctx::ParquetCtx;
+use parqeye::theme::set_theme;
 use std::io;
 
 use clap::Parser;
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
 
 #[derive(Parser)]
 #[command(
@@ -13,21 +16,61 @@ use clap::Parser;
 pub struct Opts {
     /// Path to the parquet file
     pub path: String,
+
+    /// Extra Parquet file to query in the SQL tab as a named table, e.g. `orders=orders.parquet`
+    #[arg(long = "table", value_name = "NAME=PATH", value_parser = parse_table)]
+    pub tables: Vec<(String, String)>,
+
+    /// Reload the file when it changes on disk, keeping the current tab, cursor and filters
+    #[arg(long)]
+    pub watch: bool,
+}
+
+fn parse_table(arg: &str) -> Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((name, path)) if !name.is_empty() && !path.is_empty() => {
+            Ok((name.to_string(), path.to_string()))
+        }
+        _ => Err(format!("expected NAME=PATH, got '{arg}'")),
+    }
 }
 
 fn main() -> io::Result<()> {
     let opts = Opts::parse();
-    tui(&opts.path)?;
+    tui(&opts)?;
     Ok(())
 }
 
-fn tui(path: &str) -> io::Result<()> {
+fn tui(opts: &Opts) -> io::Result<()> {
+    let config = Config::load().map_err(io::Error::other)?;
+    set_theme(config.theme);
+
+    // Everything that can fail on bad arguments happens before the terminal is taken over
+    let file_info =
+        ParquetCtx::from_file(&opts.path).map_err(|e| io::Error::other(e.to_string()))?;
+    let mut app = App::new(file_info)
+        .with_keymap(config.keymap)
+        .with_watch(opts.watch);
+    for (name, path) in &opts.tables {
+        app.attach_table(name, path)
+            .map_err(|e| io::Error::other(format!("--table {name}={path}: {e}")))?;
+    }
+
     let mut terminal = ratatui::init();
+    let _restore = RestoreTerminal;
+    if config.mouse {
+        crossterm::execute!(io::stdout(), EnableMouseCapture)?;
+    }
+    app.run(&mut terminal)
+}
 
-    let file_info = ParquetCtx::from_file(path).map_err(|e| io::Error::other(e.to_string()))?;
+/// Puts the terminal back when dropped, so that errors leave it usable too.
+struct RestoreTerminal;
 
-    let mut app = App::new(&file_info);
-    app.run(&mut terminal)?;
-    ratatui::restore();
-    Ok(())
+impl Drop for RestoreTerminal {
+    fn drop(&mut self) {
+        // Mouse capture is left on by nothing else, so turning it off is always safe
+        let _ = crossterm::execute!(io::stdout(), DisableMouseCapture);
+        ratatui::restore();
+    }
 }
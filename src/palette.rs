@@ -0,0 +1,102 @@
+//! Command palette (":" or Ctrl+P): the actions of the current tab and commands without
+//! a key of their own, picked by fuzzy search over their labels.
+
+use crate::file::column_layout::fuzzy_match;
+use crate::file::query_library::SavedQuery;
+use crate::keymap::Action;
+
+#[derive(Debug, Clone)]
+pub enum Command {
+    /// Run an action as if its key was pressed
+    Action(Action),
+    /// Switch to the tab at this index
+    Tab(usize),
+    /// Show the open file at this index
+    File(usize),
+    /// Browse for a file to open
+    OpenFile,
+    /// Ask for a row number and move the cursor there
+    GoToRow,
+    SwitchTheme,
+    RunQuery(SavedQuery),
+}
+
+#[derive(Debug, Clone)]
+pub struct PaletteItem {
+    pub label: String,
+    /// Key hint shown next to the label, empty for commands without a key
+    pub key: String,
+    pub command: Command,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Palette {
+    pub query: String,
+    /// Line selected among the matches
+    pub selected: usize,
+    pub items: Vec<PaletteItem>,
+}
+
+impl Palette {
+    pub fn new(items: Vec<PaletteItem>) -> Self {
+        Self {
+            items,
+            ..Default::default()
+        }
+    }
+
+    /// Items whose label matches the query, with the positions of the matched
+    /// characters; labels starting with the query come first.
+    pub fn matches(&self) -> Vec<(usize, Vec<usize>)> {
+        let mut matches: Vec<(usize, Vec<usize>)> = self
+            .items
+            .iter()
+            .enumerate()
+            .filter_map(|(i, item)| fuzzy_match(&self.query, &item.label).map(|chars| (i, chars)))
+            .collect();
+        // Stable: items keep their order within each group
+        matches.sort_by_key(|(_, chars)| chars.iter().enumerate().any(|(i, &c)| i != c));
+        matches
+    }
+
+    /// The command on the selected line.
+    pub fn selected_command(&self) -> Option<Command> {
+        let (item, _) = self.matches().into_iter().nth(self.selected)?;
+        Some(self.items[item].command.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(label: &str) -> PaletteItem {
+        PaletteItem {
+            label: label.to_string(),
+            key: String::new(),
+            command: Command::GoToRow,
+        }
+    }
+
+    #[test]
+    fn test_matches() {
+        let mut palette = Palette::new(vec![
+            item("Go to tab: Schema"),
+            item("Sort by the current column"),
+            item("Switch theme"),
+        ]);
+        palette.query = "sw".to_string();
+        assert_eq!(
+            vec![2],
+            palette.matches().iter().map(|m| m.0).collect::<Vec<_>>()
+        );
+        palette.query = "s".to_string();
+        // "Sort ..." and "Switch ..." start with the query
+        assert_eq!(
+            vec![1, 2, 0],
+            palette.matches().iter().map(|m| m.0).collect::<Vec<_>>()
+        );
+        palette.selected = 1;
+        assert!(matches!(palette.selected_command(), Some(Command::GoToRow)));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::file::Renderable;
+use crate::keymap::{Action, KeyMap};
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::style::Stylize;
@@ -11,7 +12,9 @@ use crate::tabs::metadata::MetadataTab;
 use crate::tabs::row_groups::RowGroupsTab;
 use crate::tabs::schema::SchemaTab;
 use crate::tabs::sql::SqlTab;
+use crate::tabs::storage::StorageTab;
 use crate::tabs::visualize::VisualizeTab;
+use crate::theme::theme;
 
 pub struct TabManager {
     pub tabs: Vec<Box<dyn Tab>>,
@@ -39,6 +42,7 @@ impl TabManager {
                         .with_max_horizontal_scroll(num_row_groups - 1)
                         .with_max_vertical_scroll(num_columns),
                 ),
+                Box::new(StorageTab::new()),
                 Box::new(SqlTab::new()),
             ],
             active_tab: 0,
@@ -58,33 +62,64 @@ impl TabManager {
         }
     }
 
+    /// Tab whose title is at column `x` of the tab bar drawn in `area`.
+    pub fn tab_at(&self, area: Rect, x: u16) -> Option<usize> {
+        // Titles are padded by a space on each side and separated by a one-space divider
+        let mut start = area.x;
+        for (i, tab) in self.tabs.iter().enumerate() {
+            let end = start + tab.to_string().chars().count() as u16 + 2;
+            if (start..end).contains(&x) {
+                return Some(i);
+            }
+            start = end + 1;
+        }
+        None
+    }
+
     #[allow(clippy::borrowed_box)]
     pub fn active_tab(&self) -> &Box<dyn Tab> {
         &self.tabs[self.active_tab]
     }
 
-    pub fn render_instructions(&self, area: Rect, buf: &mut Buffer) {
-        let mut span = self.active_tab().instructions();
-        if !span.is_empty() {
-            span.push(" - ".into());
-        }
-        span.extend(vec![
-            "[Tab]".green(),
+    /// Tab instructions followed by the keys available everywhere, on the right. On a
+    /// narrow terminal the tab instructions are cut so that help stays in view.
+    pub fn render_instructions(&self, keys: &KeyMap, area: Rect, buf: &mut Buffer) {
+        use ratatui::layout::{Constraint, Layout};
+
+        let global = Line::from(vec![
+            format!("[{}]", keys.hint(Action::Help)).fg(theme().key),
+            " Help".into(),
+            ", ".into(),
+            format!("[{}]", keys.hint(Action::Palette)).fg(theme().key),
+            " Commands".into(),
+            ", ".into(),
+            format!("[{}]", keys.hint(Action::NextTab)).fg(theme().key),
             " Next Tab".into(),
             ", ".into(),
-            "[Ctrl+X]".blue(),
+            format!("[{}]", keys.hint(Action::Quit)).fg(theme().key_alt),
             " Quit".into(),
         ]);
+        let [rest, global_area] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(global.width() as u16),
+        ])
+        .areas(area);
+        global.render(global_area, buf);
+
+        let mut span = self.active_tab().instructions(keys);
+        if span.is_empty() {
+            return;
+        }
+        span.push(" - ".into());
         let line = Line::from(span);
 
         // Calculate the width of the instruction text
         let instruction_width = line.width() as u16;
 
         // Create a layout that positions the instructions on the right
-        use ratatui::layout::{Constraint, Layout};
         let [_, instruction_area] =
             Layout::horizontal([Constraint::Fill(1), Constraint::Length(instruction_width)])
-                .areas(area);
+                .areas(rest);
 
         line.render(instruction_area, buf);
     }
@@ -1,6 +1,6 @@
-use crossterm::event::KeyEvent;
 use std::io;
 
+use crate::keymap::{Action, KeyMap};
 use crate::{app::AppState, tabs::Tab};
 use ratatui::text::Span;
 
@@ -36,11 +36,11 @@ impl MetadataTab {
 
 impl Tab for MetadataTab {
     #[allow(unused_variables)]
-    fn on_event(&self, key_event: KeyEvent, state: &mut AppState) -> Result<(), io::Error> {
+    fn on_action(&self, action: Action, state: &mut AppState) -> Result<(), io::Error> {
         Ok(())
     }
 
-    fn instructions(&self) -> Vec<Span<'static>> {
+    fn instructions(&self, _keys: &KeyMap) -> Vec<Span<'static>> {
         vec![]
     }
 
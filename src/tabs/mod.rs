@@ -3,21 +3,25 @@ pub mod metadata;
 pub mod row_groups;
 pub mod schema;
 pub mod sql;
+pub mod storage;
 pub mod visualize;
 
 pub use manager::TabManager;
 pub use metadata::MetadataTab;
 pub use schema::SchemaTab;
 pub use sql::SqlTab;
+pub use storage::StorageTab;
 pub use visualize::VisualizeTab;
 
 use crate::app::AppState;
-use crossterm::event::KeyEvent;
+use crate::keymap::{Action, KeyMap};
 use ratatui::text::Span;
 use std::io;
 
 pub trait Tab {
-    fn on_event(&self, key_event: KeyEvent, state: &mut AppState) -> Result<(), io::Error>;
-    fn instructions(&self) -> Vec<Span<'static>>;
+    /// Act on a key bound to `action`, for actions that move within the tab.
+    fn on_action(&self, action: Action, state: &mut AppState) -> Result<(), io::Error>;
+    /// Key hints for the footer, using the keys bound in `keys`.
+    fn instructions(&self, keys: &KeyMap) -> Vec<Span<'static>>;
     fn to_string(&self) -> String;
 }
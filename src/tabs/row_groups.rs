@@ -1,5 +1,6 @@
+use crate::keymap::{Action, KeyMap};
+use crate::theme::theme;
 use crate::{app::AppState, tabs::Tab};
-use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::style::Stylize;
 use ratatui::text::Span;
 use std::io;
@@ -35,16 +36,21 @@ impl RowGroupsTab {
 }
 
 impl Tab for RowGroupsTab {
-    fn on_event(&self, key_event: KeyEvent, state: &mut AppState) -> Result<(), io::Error> {
-        match key_event.code {
-            KeyCode::Up if state.vertical_offset() > 0 => state.up(),
-            KeyCode::Down
+    fn on_action(&self, action: Action, state: &mut AppState) -> Result<(), io::Error> {
+        match action {
+            Action::Up if state.vertical_offset() > 0 => state.up(),
+            Action::Down
                 if state.vertical_offset() < self.max_vertical_scroll.unwrap_or(usize::MAX) =>
             {
                 state.down()
             }
-            KeyCode::Left if state.horizontal_offset() > 0 => state.left(),
-            KeyCode::Right
+            Action::Top => state.first_row(state.visible_data_rows(), 0),
+            Action::Bottom => {
+                let rows = self.max_vertical_scroll.map_or(0, |max| max + 1);
+                state.last_row(state.visible_data_rows(), rows)
+            }
+            Action::Left if state.horizontal_offset() > 0 => state.left(),
+            Action::Right
                 if state.horizontal_offset() < self.max_horizontal_scroll.unwrap_or(usize::MAX) =>
             {
                 state.right()
@@ -54,19 +60,23 @@ impl Tab for RowGroupsTab {
         Ok(())
     }
 
-    fn instructions(&self) -> Vec<Span<'static>> {
+    fn instructions(&self, keys: &KeyMap) -> Vec<Span<'static>> {
         vec![
-            "→".green(),
-            "/".white(),
-            "←".blue(),
+            keys.hint(Action::Right).fg(theme().key),
+            "/".fg(theme().text),
+            keys.hint(Action::Left).fg(theme().key_alt),
             " : ".into(),
             "Iterate Row Groups".into(),
             ", ".into(),
-            "↑".green(),
-            "/".white(),
-            "↓".blue(),
+            keys.hint(Action::Up).fg(theme().key),
+            "/".fg(theme().text),
+            keys.hint(Action::Down).fg(theme().key_alt),
             " : ".into(),
             "Schema".into(),
+            ", ".into(),
+            keys.hint(Action::Pushdown).fg(theme().key),
+            " : ".into(),
+            "Pushdown preview".into(),
         ]
     }
 
@@ -1,9 +1,10 @@
-use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::style::Stylize;
 use ratatui::text::Span;
 use std::io;
 
 use crate::file::sql::SqlResult;
+use crate::keymap::{Action, KeyMap};
+use crate::theme::theme;
 use crate::{app::AppState, tabs::Tab};
 
 pub struct SqlTab;
@@ -21,40 +22,112 @@ impl Default for SqlTab {
 }
 
 impl Tab for SqlTab {
-    fn on_event(&self, key_event: KeyEvent, state: &mut AppState) -> Result<(), io::Error> {
-        match key_event.code {
-            KeyCode::Backspace => {
-                state.sql_query.pop();
+    /// Keys while navigating the results; the editor handles keys while a query is edited.
+    fn on_action(&self, action: Action, state: &mut AppState) -> Result<(), io::Error> {
+        let (max_rows, max_columns) = match &state.sql_result {
+            Some(SqlResult::Ok(data)) => (data.total_rows, data.total_columns),
+            _ => (0, 0),
+        };
+        let visible_rows = state.visible_data_rows();
+
+        match action {
+            Action::EditQuery => {
+                state.sql_editing = true;
+            }
+            Action::Up if state.vertical_offset() > 0 => {
+                state.up();
+                state.adjust_scroll_to_selection(visible_rows, max_rows);
+            }
+            Action::Down if state.vertical_offset() < max_rows.saturating_sub(1) => {
+                state.down();
+                state.adjust_scroll_to_selection(visible_rows, max_rows);
+            }
+            Action::PageUp => {
+                state.page_up(visible_rows, max_rows);
+            }
+            Action::PageDown => {
+                state.page_down(visible_rows, max_rows);
             }
-            KeyCode::Char('v') | KeyCode::Char('V') => {
-                if state
-                    .sql_result
-                    .as_ref()
-                    .is_some_and(|r| matches!(r, SqlResult::Ok(_)))
-                {
-                    state.row_detail_row = Some(state.vertical_offset());
-                    state.detail_scroll_offset = 0;
-                    state.detail_scroll_horizontal = 0;
-                }
+            Action::Top => state.first_row(visible_rows, max_rows),
+            Action::Bottom => state.last_row(visible_rows, max_rows),
+            Action::Left if state.horizontal_offset() > 0 => state.left(),
+            Action::Right if state.horizontal_offset() < max_columns.saturating_sub(1) => {
+                state.right()
             }
-            KeyCode::Char(c) => {
-                state.sql_query.push(c);
+            Action::RowDetail if max_rows > 0 => {
+                state.row_detail_row = Some(state.vertical_offset());
+                state.detail_scroll_offset = 0;
+                state.detail_scroll_horizontal = 0;
+                state.detail_cursor = 0;
             }
             _ => {}
         }
         Ok(())
     }
 
-    fn instructions(&self) -> Vec<Span<'static>> {
+    fn instructions(&self, keys: &KeyMap) -> Vec<Span<'static>> {
         vec![
-            "Enter".green(),
+            "Enter".fg(theme().key),
             " : Run query".into(),
-            " | ".white(),
-            "[Esc]".into(),
-            " : Clear".into(),
-            " | ".white(),
-            "v".green(),
+            " | ".fg(theme().text),
+            "Alt+Enter".fg(theme().key),
+            " : New line".into(),
+            " | ".fg(theme().text),
+            "Ctrl+Space".fg(theme().key),
+            " : Complete".into(),
+            " | ".fg(theme().text),
+            "↑".fg(theme().key),
+            "/".fg(theme().text),
+            "↓".fg(theme().key_alt),
+            " : History".into(),
+            " | ".fg(theme().text),
+            format!("[{}]", keys.hint(Action::Cancel)).into(),
+            " : Results".into(),
+            " | ".fg(theme().text),
+            keys.hint(Action::EditQuery).fg(theme().key),
+            " : Edit".into(),
+            " | ".fg(theme().text),
+            keys.hint(Action::RowDetail).fg(theme().key),
             " : Row detail".into(),
+            " | ".fg(theme().text),
+            keys.hint(Action::FetchMore).fg(theme().key),
+            " : More rows".into(),
+            " | ".fg(theme().text),
+            keys.hint(Action::Summary).fg(theme().key),
+            " : Summary".into(),
+            " | ".fg(theme().text),
+            keys.hint(Action::ExpandCell).fg(theme().key),
+            " : Expand".into(),
+            " | ".fg(theme().text),
+            keys.hint(Action::WrapRow).fg(theme().key),
+            " : Wrap".into(),
+            " | ".fg(theme().text),
+            keys.hint(Action::NarrowColumn).fg(theme().key),
+            "/".fg(theme().text),
+            keys.hint(Action::WidenColumn).fg(theme().key_alt),
+            " : Width".into(),
+            " | ".fg(theme().text),
+            keys.hint(Action::Mark).fg(theme().key),
+            " : Mark".into(),
+            " | ".fg(theme().text),
+            keys.hint(Action::Yank).fg(theme().key),
+            " : Copy".into(),
+            " | ".fg(theme().text),
+            keys.hint(Action::Chart).fg(theme().key),
+            " : Chart".into(),
+            " | ".fg(theme().text),
+            keys.hint(Action::Explain).fg(theme().key),
+            "/".fg(theme().text),
+            "Alt+E".fg(theme().key_alt),
+            " : Explain".into(),
+            " | ".fg(theme().text),
+            "Ctrl+S".fg(theme().key),
+            " : Save".into(),
+            " | ".fg(theme().text),
+            keys.hint(Action::SavedQueries).fg(theme().key),
+            "/".fg(theme().text),
+            "Ctrl+O".fg(theme().key_alt),
+            " : Saved queries".into(),
         ]
     }
 
@@ -0,0 +1,56 @@
+use ratatui::style::Stylize;
+use ratatui::text::Span;
+use std::io;
+
+use crate::keymap::{Action, KeyMap};
+use crate::theme::theme;
+use crate::{app::AppState, tabs::Tab};
+
+pub struct StorageTab;
+
+impl StorageTab {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for StorageTab {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Tab for StorageTab {
+    fn on_action(&self, action: Action, state: &mut AppState) -> Result<(), io::Error> {
+        match action {
+            Action::Up if state.vertical_offset() > 0 => state.up(),
+            Action::Down if state.vertical_offset() + 1 < state.storage_level_len => state.down(),
+            Action::Top => state.first_row(state.visible_data_rows(), 0),
+            Action::Bottom => state.last_row(state.visible_data_rows(), state.storage_level_len),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn instructions(&self, keys: &KeyMap) -> Vec<Span<'static>> {
+        vec![
+            keys.hint(Action::Up).fg(theme().key),
+            "/".fg(theme().text),
+            keys.hint(Action::Down).fg(theme().key_alt),
+            " : ".into(),
+            "Column".into(),
+            ", ".into(),
+            "Enter".fg(theme().key),
+            " : ".into(),
+            "Open group".into(),
+            ", ".into(),
+            "Backspace".fg(theme().key_alt),
+            " : ".into(),
+            "Up a level".into(),
+        ]
+    }
+
+    fn to_string(&self) -> String {
+        "Storage".to_string()
+    }
+}
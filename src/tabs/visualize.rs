@@ -1,8 +1,9 @@
-use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::style::Stylize;
 use ratatui::text::Span;
 use std::io;
 
+use crate::keymap::{Action, KeyMap};
+use crate::theme::theme;
 use crate::{app::AppState, tabs::Tab};
 
 pub struct VisualizeTab {
@@ -43,72 +44,109 @@ impl VisualizeTab {
 }
 
 impl Tab for VisualizeTab {
-    fn on_event(&self, key_event: KeyEvent, state: &mut AppState) -> Result<(), io::Error> {
+    fn on_action(&self, action: Action, state: &mut AppState) -> Result<(), io::Error> {
         let max_rows = self.max_rows.unwrap_or(0);
         let visible_rows = state.visible_data_rows();
 
-        match key_event.code {
+        match action {
             // Row navigation (Up/Down arrows)
-            KeyCode::Up => {
-                if state.vertical_offset() > 0 {
-                    state.up();
-                    state.adjust_scroll_to_selection(visible_rows, max_rows);
-                }
+            Action::Up if state.vertical_offset() > 0 => {
+                state.up();
+                state.adjust_scroll_to_selection(visible_rows, max_rows);
             }
-            KeyCode::Down => {
-                if state.vertical_offset() < max_rows.saturating_sub(1) {
-                    state.down();
-                    state.adjust_scroll_to_selection(visible_rows, max_rows);
-                }
+            Action::Down if state.vertical_offset() < max_rows.saturating_sub(1) => {
+                state.down();
+                state.adjust_scroll_to_selection(visible_rows, max_rows);
             }
             // Page navigation (u/d keys)
-            KeyCode::Char('u') | KeyCode::Char('U') => {
+            Action::PageUp => {
                 state.page_up(visible_rows, max_rows);
             }
-            KeyCode::Char('d') | KeyCode::Char('D') => {
+            Action::PageDown => {
                 state.page_down(visible_rows, max_rows);
             }
+            Action::Top => state.first_row(visible_rows, max_rows),
+            Action::Bottom => state.last_row(visible_rows, max_rows),
             // Column navigation (Left/Right arrows)
-            KeyCode::Left if state.horizontal_offset() > 0 => state.left(),
-            KeyCode::Right
-                if state.horizontal_offset()
-                    < self.max_horizontal_scroll.unwrap_or(usize::MAX) - 1 =>
+            Action::Left if state.horizontal_offset() > 0 => state.left(),
+            // Hidden columns are skipped
+            Action::Right
+                if state.horizontal_offset() + 1 < state.column_layout.visible_count() =>
             {
                 state.right()
             }
-            KeyCode::Char('v') | KeyCode::Char('V') => {
+            Action::RowDetail => {
                 state.row_detail_row = Some(state.vertical_offset());
                 state.detail_scroll_offset = 0;
                 state.detail_scroll_horizontal = 0;
+                state.detail_cursor = 0;
             }
             _ => {}
         }
         Ok(())
     }
 
-    fn instructions(&self) -> Vec<Span<'static>> {
+    fn instructions(&self, keys: &KeyMap) -> Vec<Span<'static>> {
         vec![
-            "↑".green(),
-            "/".white(),
-            "↓".blue(),
+            keys.hint(Action::Up).fg(theme().key),
+            "/".fg(theme().text),
+            keys.hint(Action::Down).fg(theme().key_alt),
             " : ".into(),
             "Row".into(),
-            " | ".white(),
-            "→".green(),
-            "/".white(),
-            "←".blue(),
+            " | ".fg(theme().text),
+            keys.hint(Action::Right).fg(theme().key),
+            "/".fg(theme().text),
+            keys.hint(Action::Left).fg(theme().key_alt),
             " : ".into(),
             "Column".into(),
-            " | ".white(),
-            "u".green(),
-            "/".white(),
-            "d".blue(),
+            " | ".fg(theme().text),
+            keys.hint(Action::PageUp).fg(theme().key),
+            "/".fg(theme().text),
+            keys.hint(Action::PageDown).fg(theme().key_alt),
             " : ".into(),
             "Page".into(),
-            " | ".white(),
-            "v".green(),
+            " | ".fg(theme().text),
+            keys.hint(Action::RowDetail).fg(theme().key),
             " : ".into(),
             "Row detail".into(),
+            " | ".fg(theme().text),
+            keys.hint(Action::Sort).fg(theme().key),
+            " : ".into(),
+            "Sort".into(),
+            " | ".fg(theme().text),
+            keys.hint(Action::Find).fg(theme().key),
+            " : ".into(),
+            "Find".into(),
+            " | ".fg(theme().text),
+            keys.hint(Action::GroupBy).fg(theme().key),
+            " : ".into(),
+            "Group by".into(),
+            " | ".fg(theme().text),
+            keys.hint(Action::Columns).fg(theme().key),
+            " : ".into(),
+            "Columns".into(),
+            " | ".fg(theme().text),
+            keys.hint(Action::ExpandCell).fg(theme().key),
+            " : ".into(),
+            "Expand".into(),
+            " | ".fg(theme().text),
+            keys.hint(Action::WrapRow).fg(theme().key),
+            " : ".into(),
+            "Wrap".into(),
+            " | ".fg(theme().text),
+            keys.hint(Action::NarrowColumn).fg(theme().key),
+            "/".fg(theme().text),
+            keys.hint(Action::WidenColumn).fg(theme().key_alt),
+            " : ".into(),
+            "Width".into(),
+            " | ".fg(theme().text),
+            keys.hint(Action::Mark).fg(theme().key),
+            " : ".into(),
+            "Mark".into(),
+            " | ".fg(theme().text),
+            keys.hint(Action::Yank).fg(theme().key),
+            " : ".into(),
+            "Copy".into(),
         ]
     }
 
@@ -0,0 +1,300 @@
+//! Colors used across the UI, by role. A built-in theme is picked in the config file
+//! (`theme = "light"`) and single colors can be overridden in its `[colors]` table;
+//! `NO_COLOR` switches to a theme without colors that marks selections with reverse video.
+
+use ratatui::style::{Color, Modifier, Style};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{LazyLock, OnceLock};
+
+/// Built-in themes "Switch theme" cycles through.
+const THEME_NAMES: [&str; 4] = ["dark", "light", "solarized", "high-contrast"];
+
+static BUILT_IN: LazyLock<[Theme; 4]> =
+    LazyLock::new(|| THEME_NAMES.map(|name| Theme::from_name(name).unwrap_or_default()));
+
+/// The theme of the config file, set once at startup.
+static CONFIGURED: OnceLock<Theme> = OnceLock::new();
+
+/// Built-in theme switched to, as its position in `THEME_NAMES`; `NONE` while the
+/// configured theme is shown.
+static SWITCHED: AtomicUsize = AtomicUsize::new(NONE);
+const NONE: usize = usize::MAX;
+
+/// The current theme, by default the dark theme.
+pub fn theme() -> &'static Theme {
+    match BUILT_IN.get(SWITCHED.load(Ordering::Relaxed)) {
+        Some(theme) => theme,
+        None => CONFIGURED.get().unwrap_or(&BUILT_IN[0]),
+    }
+}
+
+/// Use the theme of the config file; only the first call has an effect.
+pub fn set_theme(theme: Theme) {
+    let _ = CONFIGURED.set(theme);
+}
+
+/// Switch to the built-in theme after the current one and return its name; a custom or
+/// colorless theme switches to the first one.
+pub fn next_theme() -> &'static str {
+    let current = BUILT_IN.iter().position(|built_in| built_in == theme());
+    let next = current.map_or(0, |i| (i + 1) % THEME_NAMES.len());
+    SWITCHED.store(next, Ordering::Relaxed);
+    THEME_NAMES[next]
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Theme {
+    /// Values and plain text that should stand out from the background
+    pub text: Color,
+    /// Secondary text: nulls, types, footnotes
+    pub muted: Color,
+    pub border: Color,
+    /// Panel titles, bars and the focused panel's border
+    pub title: Color,
+    /// Table headers and labels
+    pub header: Color,
+    /// Key hints, prompts and the file name
+    pub key: Color,
+    /// Second key of a hint pair and secondary labels
+    pub key_alt: Color,
+    pub good: Color,
+    pub bad: Color,
+    /// A third color next to `title` and `key_alt`
+    pub accent: Color,
+    pub selected_fg: Color,
+    pub selected_bg: Color,
+    /// Background of the row under the cursor
+    pub cursor_bg: Color,
+    /// Background of find matches
+    pub match_bg: Color,
+    /// Chart series, in order
+    pub series: [Color; 6],
+    /// No colors at all: styles fall back to modifiers
+    pub no_color: bool,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::dark()
+    }
+}
+
+impl Theme {
+    pub fn dark() -> Theme {
+        Theme {
+            text: Color::White,
+            muted: Color::DarkGray,
+            border: Color::DarkGray,
+            title: Color::Cyan,
+            header: Color::Yellow,
+            key: Color::Green,
+            key_alt: Color::Blue,
+            good: Color::Green,
+            bad: Color::Red,
+            accent: Color::Magenta,
+            selected_fg: Color::Black,
+            selected_bg: Color::Yellow,
+            cursor_bg: Color::Rgb(60, 60, 60),
+            match_bg: Color::LightRed,
+            series: [
+                Color::Cyan,
+                Color::Yellow,
+                Color::Magenta,
+                Color::Green,
+                Color::Red,
+                Color::Blue,
+            ],
+            no_color: false,
+        }
+    }
+
+    /// For terminals with a light background, where yellow and white are unreadable.
+    pub fn light() -> Theme {
+        let teal = Color::Rgb(0, 110, 140);
+        let amber = Color::Rgb(150, 90, 0);
+        let green = Color::Rgb(0, 120, 0);
+        let blue = Color::Rgb(40, 80, 200);
+        let red = Color::Rgb(190, 0, 0);
+        let purple = Color::Rgb(150, 0, 150);
+        Theme {
+            text: Color::Black,
+            muted: Color::Rgb(110, 110, 110),
+            border: Color::Rgb(150, 150, 150),
+            title: teal,
+            header: amber,
+            key: green,
+            key_alt: blue,
+            good: green,
+            bad: red,
+            accent: purple,
+            selected_fg: Color::White,
+            selected_bg: blue,
+            cursor_bg: Color::Rgb(225, 225, 225),
+            match_bg: Color::Rgb(255, 180, 180),
+            series: [teal, amber, purple, green, red, blue],
+            no_color: false,
+        }
+    }
+
+    /// Solarized dark.
+    pub fn solarized() -> Theme {
+        let yellow = Color::Rgb(181, 137, 0);
+        let orange = Color::Rgb(203, 75, 22);
+        let red = Color::Rgb(220, 50, 47);
+        let magenta = Color::Rgb(211, 54, 130);
+        let violet = Color::Rgb(108, 113, 196);
+        let blue = Color::Rgb(38, 139, 210);
+        let cyan = Color::Rgb(42, 161, 152);
+        let green = Color::Rgb(133, 153, 0);
+        Theme {
+            text: Color::Rgb(147, 161, 161),
+            muted: Color::Rgb(88, 110, 117),
+            border: Color::Rgb(88, 110, 117),
+            title: cyan,
+            header: yellow,
+            key: green,
+            key_alt: blue,
+            good: green,
+            bad: red,
+            accent: violet,
+            selected_fg: Color::Rgb(0, 43, 54),
+            selected_bg: yellow,
+            cursor_bg: Color::Rgb(7, 54, 66),
+            match_bg: magenta,
+            series: [cyan, yellow, magenta, green, orange, blue],
+            no_color: false,
+        }
+    }
+
+    /// Bright colors only, with white borders and text.
+    pub fn high_contrast() -> Theme {
+        Theme {
+            text: Color::White,
+            muted: Color::Gray,
+            border: Color::White,
+            title: Color::LightCyan,
+            header: Color::LightYellow,
+            key: Color::LightGreen,
+            key_alt: Color::LightBlue,
+            good: Color::LightGreen,
+            bad: Color::LightRed,
+            accent: Color::LightMagenta,
+            selected_fg: Color::Black,
+            selected_bg: Color::LightYellow,
+            cursor_bg: Color::Blue,
+            match_bg: Color::LightMagenta,
+            series: [
+                Color::LightCyan,
+                Color::LightYellow,
+                Color::LightMagenta,
+                Color::LightGreen,
+                Color::LightRed,
+                Color::LightBlue,
+            ],
+            no_color: false,
+        }
+    }
+
+    /// The terminal's own colors everywhere (`NO_COLOR`).
+    pub fn no_color() -> Theme {
+        Theme {
+            text: Color::Reset,
+            muted: Color::Reset,
+            border: Color::Reset,
+            title: Color::Reset,
+            header: Color::Reset,
+            key: Color::Reset,
+            key_alt: Color::Reset,
+            good: Color::Reset,
+            bad: Color::Reset,
+            accent: Color::Reset,
+            selected_fg: Color::Reset,
+            selected_bg: Color::Reset,
+            cursor_bg: Color::Reset,
+            match_bg: Color::Reset,
+            series: [Color::Reset; 6],
+            no_color: true,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Theme> {
+        match name {
+            "dark" => Some(Theme::dark()),
+            "light" => Some(Theme::light()),
+            "solarized" => Some(Theme::solarized()),
+            "high-contrast" => Some(Theme::high_contrast()),
+            "no-color" => Some(Theme::no_color()),
+            _ => None,
+        }
+    }
+
+    /// Override one color, e.g. `set_color("header", "#b58900")`.
+    pub fn set_color(&mut self, role: &str, color: &str) -> Result<(), String> {
+        let color =
+            Color::from_str(color).map_err(|_| format!("{role}: unknown color '{color}'"))?;
+        let field = match role {
+            "text" => &mut self.text,
+            "muted" => &mut self.muted,
+            "border" => &mut self.border,
+            "title" => &mut self.title,
+            "header" => &mut self.header,
+            "key" => &mut self.key,
+            "key_alt" => &mut self.key_alt,
+            "good" => &mut self.good,
+            "bad" => &mut self.bad,
+            "accent" => &mut self.accent,
+            "selected_fg" => &mut self.selected_fg,
+            "selected_bg" => &mut self.selected_bg,
+            "cursor_bg" => &mut self.cursor_bg,
+            "match_bg" => &mut self.match_bg,
+            _ => return Err(format!("unknown color role '{role}'")),
+        };
+        *field = color;
+        Ok(())
+    }
+
+    /// The selected item of a list or table.
+    pub fn selected(&self) -> Style {
+        if self.no_color {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().fg(self.selected_fg).bg(self.selected_bg)
+        }
+    }
+
+    /// The row under the cursor in a data table.
+    pub fn cursor(&self) -> Style {
+        if self.no_color {
+            Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+        } else {
+            Style::default().bg(self.cursor_bg)
+        }
+    }
+
+    /// The cursor of a text input.
+    pub fn text_cursor(&self) -> Style {
+        if self.no_color {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().fg(self.selected_fg).bg(self.title)
+        }
+    }
+
+    /// Text matching the find query; the current match stands out more.
+    pub fn find_match(&self, current: bool) -> Style {
+        match (self.no_color, current) {
+            (true, true) => Style::default().add_modifier(Modifier::REVERSED | Modifier::BOLD),
+            (true, false) => Style::default().add_modifier(Modifier::UNDERLINED),
+            (false, true) => Style::default()
+                .fg(self.selected_fg)
+                .bg(self.match_bg)
+                .add_modifier(Modifier::BOLD),
+            (false, false) => self.selected(),
+        }
+    }
+
+    pub fn series_color(&self, i: usize) -> Color {
+        self.series[i % self.series.len()]
+    }
+}
@@ -2,19 +2,33 @@ use ratatui::{
     Frame,
     buffer::Buffer,
     layout::{Constraint, Layout, Rect},
-    prelude::{Color, Position},
+    prelude::Position,
     style::{Style, Stylize},
     text::{Line, Span, Text},
-    widgets::{Block, BorderType, Borders, Paragraph, Widget},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Widget},
 };
+use std::time::Duration;
 
 use crate::app::AppRenderView;
+use crate::components::data_table::{TableLayout, highlight_matches};
 use crate::components::{
-    DataTable, FileSchemaTable, RowGroupColumnMetadataComponent, RowGroupMetadata,
-    RowGroupProgressBar, SchemaTreeComponent, ScrollbarComponent,
+    ColumnProfileComponent, ColumnSummaryStrip, CompressionSimulationComponent, DataTable,
+    FileSchemaTable, GroupByTable, ResultChart, RowGroupColumnMetadataComponent, RowGroupMetadata,
+    RowGroupProgressBar, SchemaTreeComponent, ScrollbarComponent, StorageBreakdownComponent,
 };
 use crate::file::Renderable;
-use crate::file::sql::SqlResult;
+use crate::file::browser::FileBrowser;
+use crate::file::chart::ChartSpec;
+use crate::file::column_layout::ColumnChooser;
+use crate::file::query_library::QueryLibrary;
+use crate::file::record::RecordTree;
+use crate::file::sample_data::FindMatches;
+use crate::file::sql::{PlanLine, QueryPlan, SqlResult};
+use crate::file::summary::ColumnSummary;
+use crate::file::utils::{commas, wrap_text};
+use crate::keymap::Action;
+use crate::palette::Palette;
+use crate::theme::theme;
 
 pub fn render_app<'a, 'b>(app: &'b AppRenderView<'a>, frame: &mut Frame)
 where
@@ -25,6 +39,29 @@ where
 
 struct AppWidget<'a>(&'a AppRenderView<'a>);
 
+/// Where the last frame drew the parts that respond to the mouse, and the table
+/// layout that resizing a column with < / > starts from.
+#[derive(Debug, Clone, Default)]
+pub struct HitAreas {
+    pub tabs: Option<Rect>,
+    /// Data table of the Visualize tab or the SQL result
+    pub table: Option<TableLayout>,
+    /// Schema tree and its scroll offset
+    pub schema_tree: Option<(Rect, usize)>,
+    pub row_groups: Option<Rect>,
+}
+
+/// Width of the column chooser next to the Visualize table.
+const COLUMN_CHOOSER_WIDTH: u16 = 44;
+
+/// Width of the command palette popup.
+const PALETTE_WIDTH: u16 = 64;
+
+/// Width of the column list next to a chart of the SQL result.
+pub const CHART_COLUMNS_WIDTH: u16 = 30;
+
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
 impl<'a> AppWidget<'a> {
     // Helper function to calculate the tree index of the selected primitive column
     fn calculate_selected_tree_index(&self, vertical_offset: usize) -> Option<usize> {
@@ -137,49 +174,147 @@ impl<'a> AppWidget<'a> {
         let block = Block::default()
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(Color::LightYellow));
+            .border_style(Style::default().fg(theme().header));
         let inner_area = block.inner(area);
         block.render(area, buf);
 
-        let file_name_length = self.0.file_name().len() as u16;
+        // With several files open, which one this is
+        let files = self.0.files().len();
+        let file_name = if files > 1 {
+            format!(
+                "{} [{}/{files}]",
+                self.0.file_name(),
+                self.0.active_file() + 1
+            )
+        } else {
+            self.0.file_name().to_string()
+        };
+        let file_name_length = file_name.chars().count() as u16;
 
         let [tabs_area, file_name_area] =
             Layout::horizontal([Constraint::Min(0), Constraint::Length(file_name_length)])
                 .areas(inner_area);
         self.0.tabs().render_content(tabs_area, buf);
-        self.0.file_name().green().render(file_name_area, buf);
+        self.0.hits().borrow_mut().tabs = Some(tabs_area);
+        file_name.fg(theme().key).render(file_name_area, buf);
     }
 
     fn render_footer_view(&self, area: Rect, buf: &mut Buffer) {
         let title_width = self.0.title.len() as u16;
         let [title_area, footer_area] =
             Layout::horizontal([Constraint::Length(title_width), Constraint::Fill(1)]).areas(area);
-        self.0.title.bold().fg(Color::Green).render(title_area, buf);
+        self.0.title.bold().fg(theme().key).render(title_area, buf);
 
-        if self.0.state().search_mode {
+        if let Some(notice) = &self.0.state().notice {
+            Line::from(notice.as_str().fg(theme().header)).render(footer_area, buf);
+        } else if self.0.state().search_mode {
             let prompt = format!("Search: {}|", self.0.state().search_query);
-            let line = Line::from(vec![prompt.green(), "  Enter=filter, Esc=cancel".into()]);
+            let line = Line::from(vec![
+                prompt.fg(theme().key),
+                "  e.g. col:text col>10 col~regex col is null, AND/OR/NOT; Enter=filter, Esc=cancel"
+                    .into(),
+            ]);
             line.render(footer_area, buf);
-        } else if self.0.state().search_filter.is_some() {
-            let n = self
-                .0
-                .state()
-                .filtered_sample_data
+        } else if self.0.state().pruning_mode {
+            let prompt = format!("Pushdown filter: {}|", self.0.state().pruning_query);
+            let line = Line::from(vec![
+                prompt.fg(theme().key),
+                "  e.g. ts>='2024-01-01' AND country=DE; Enter=preview, Esc=cancel".into(),
+            ]);
+            line.render(footer_area, buf);
+        } else if self.0.state().save_query_mode {
+            let state = self.0.state();
+            let prompt = format!(
+                "Save query as ({} library): {}|",
+                state.save_query_scope.describe(),
+                state.save_query_name
+            );
+            let line = Line::from(vec![
+                prompt.fg(theme().key),
+                "  Tab=user/project library, Enter=save, Esc=cancel".into(),
+            ]);
+            line.render(footer_area, buf);
+        } else if let Some(prompt) = &self.0.state().query_parameters {
+            let text = format!(
+                ":{} ({}/{}): {}|",
+                prompt.current().unwrap_or_default(),
+                prompt.values.len() + 1,
+                prompt.names.len(),
+                prompt.input
+            );
+            let line = Line::from(vec![
+                text.fg(theme().key),
+                "  SQL literal, quote strings; Enter=next, Esc=cancel".into(),
+            ]);
+            line.render(footer_area, buf);
+        } else if self.0.state().yank_mode {
+            let line = Line::from(vec![
+                "Copy: ".fg(theme().key),
+                "y".fg(theme().key),
+                " cell/selection, ".into(),
+                "r".fg(theme().key),
+                " row (TSV), ".into(),
+                "j".fg(theme().key),
+                " row (JSON), ".into(),
+                "n".fg(theme().key),
+                " column name  Esc=cancel".into(),
+            ]);
+            line.render(footer_area, buf);
+        } else if self.0.state().goto_row_mode {
+            let prompt = format!("Go to row: {}|", self.0.state().goto_row_query);
+            let line = Line::from(vec![
+                prompt.fg(theme().key),
+                "  Enter=go, Esc=cancel".into(),
+            ]);
+            line.render(footer_area, buf);
+        } else if self.0.state().detail_find_mode {
+            let prompt = format!("Find in row: {}|", self.0.state().detail_query);
+            let line = Line::from(vec![
+                prompt.fg(theme().key),
+                "  Enter=find, Esc=cancel".into(),
+            ]);
+            line.render(footer_area, buf);
+        } else if self.0.state().find_mode {
+            let prompt = format!("Find: {}|", self.0.state().find_query);
+            let line = Line::from(vec![
+                prompt.fg(theme().key),
+                "  Enter=find in the rows read, Esc=cancel".into(),
+            ]);
+            line.render(footer_area, buf);
+        } else if self.0.state().search_filter.is_some() || self.0.state().find.is_some() {
+            let state = self.0.state();
+            let mut span = self.0.tabs().active_tab().instructions(self.0.keymap());
+            if let Some(n) = state
+                .search_filter
                 .as_ref()
-                .map(|d| d.total_rows)
-                .unwrap_or(0);
-            use ratatui::text::Line;
-            let mut span = self.0.tabs().active_tab().instructions();
-            if !span.is_empty() {
+                .map(|_| state.search_match_count)
+            {
                 span.push(" - ".into());
+                span.push(format!("{} matches", commas(n.unwrap_or(0) as u64)).fg(theme().key));
             }
-            span.extend(vec![
-                format!("{} rows filtered", n).green(),
-                " (Esc to show all)".into(),
-            ]);
+            if let Some(find) = &state.find {
+                span.push(" - ".into());
+                let status = match (find.current, find.cells.len()) {
+                    (_, 0) => format!("'{}' not found", find.query),
+                    (Some(i), n) => format!("match {} of {n}", i + 1),
+                    (None, n) => format!("{n} matches for '{}'", find.query),
+                };
+                // Find looks through the rows read, not the whole file like the filter
+                let rows = state
+                    .visualize_data(&self.0.parquet_ctx.sample_data)
+                    .total_rows;
+                span.extend(vec![
+                    status.fg(theme().key),
+                    format!(" in the {} rows read", commas(rows as u64)).into(),
+                    " (n/N next/prev)".into(),
+                ]);
+            }
+            span.push(" (Esc to clear)".into());
             Line::from(span).render(footer_area, buf);
         } else {
-            self.0.tabs().render_instructions(footer_area, buf);
+            self.0
+                .tabs()
+                .render_instructions(self.0.keymap(), footer_area, buf);
         }
     }
 
@@ -210,10 +345,86 @@ impl<'a> AppWidget<'a> {
             adjusted_scroll,
             buf,
         );
-        self.render_schema_table(central_area, adjusted_scroll, buf);
+
+        if let Some(job) = &self.0.state().compression_job {
+            render_running(
+                &spinner(job.elapsed()),
+                "Re-encoding a sample with every codec... (Esc to cancel)",
+                central_area,
+                buf,
+            );
+            return;
+        }
+        match &self.0.state().compression_simulation {
+            Some(Ok(simulation)) => CompressionSimulationComponent::new(simulation)
+                .with_vertical_scroll(self.0.state().compression_scroll)
+                .render(central_area, buf),
+            Some(Err(msg)) => {
+                let block = Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(theme().bad))
+                    .title(" Compression what-if failed (Esc close) ");
+                let inner = block.inner(central_area);
+                block.render(central_area, buf);
+                Paragraph::new(Line::from(Span::styled(
+                    msg.as_str(),
+                    Style::default().fg(theme().bad),
+                )))
+                .render(inner, buf);
+            }
+            None => self.render_schema_table_with_profile(central_area, adjusted_scroll, buf),
+        }
+    }
+
+    // Render the schema table, with the column profile alongside it when one is open
+    fn render_schema_table_with_profile(
+        &self,
+        area: Rect,
+        adjusted_scroll: usize,
+        buf: &mut Buffer,
+    ) {
+        let state = self.0.state();
+        if state.column_profile.is_none() && state.profile_job.is_none() {
+            self.render_schema_table(area, adjusted_scroll, buf);
+            return;
+        }
+
+        let [table_area, profile_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(area);
+        self.render_schema_table(table_area, adjusted_scroll, buf);
+
+        if let Some(job) = &state.profile_job {
+            render_running(
+                &spinner(job.elapsed()),
+                "Profiling the column... (Esc to cancel)",
+                profile_area,
+                buf,
+            );
+            return;
+        }
+        match &state.column_profile {
+            None => {}
+            Some(Ok(profile)) => ColumnProfileComponent::new(profile).render(profile_area, buf),
+            Some(Err(msg)) => {
+                let block = Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(theme().bad))
+                    .title(" Profile failed (Esc close) ");
+                let inner = block.inner(profile_area);
+                block.render(profile_area, buf);
+                Paragraph::new(Line::from(Span::styled(
+                    msg.as_str(),
+                    Style::default().fg(theme().bad),
+                )))
+                .render(inner, buf);
+            }
+        }
     }
 
     fn render_schema_tree_with_scroll(&self, area: Rect, scroll_offset: usize, buf: &mut Buffer) {
+        self.0.hits().borrow_mut().schema_tree = Some((area, scroll_offset));
         SchemaTreeComponent::new(&self.0.parquet_ctx.schema.columns)
             .with_title("Schema Tree".to_string())
             .with_selected_index(self.0.state().vertical_offset())
@@ -245,13 +456,19 @@ impl<'a> AppWidget<'a> {
             buf,
         );
 
+        // A pushdown preview adds a line for the rows read in the selected row group
+        let pruning = self.0.state().pruning.as_ref();
+        let progress_height = if pruning.is_some() { 4 } else { 3 };
         let [rg_progress, central_area] =
-            Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).areas(main_area);
+            Layout::vertical([Constraint::Length(progress_height), Constraint::Fill(1)])
+                .areas(main_area);
 
+        self.0.hits().borrow_mut().row_groups = Some(rg_progress);
         RowGroupProgressBar::new(
             &self.0.parquet_ctx.row_groups.row_groups,
             self.0.state().horizontal_offset(),
         )
+        .with_pruning(pruning)
         .render(rg_progress, buf);
 
         if self.0.state().vertical_offset() > 0 {
@@ -271,68 +488,228 @@ impl<'a> AppWidget<'a> {
         }
     }
 
+    fn render_storage_view(&self, area: Rect, buf: &mut Buffer) {
+        StorageBreakdownComponent::new(&self.0.parquet_ctx.storage, &self.0.state().storage_path)
+            .with_selected_index(self.0.state().vertical_offset())
+            .render(area, buf);
+    }
+
     fn render_visualize_view(&self, area: Rect, buf: &mut Buffer) {
+        if let Some(job) = &self.0.state().group_by_job {
+            render_running(
+                &spinner(job.elapsed()),
+                "Counting the values over the whole file... (Esc to cancel)",
+                area,
+                buf,
+            );
+            return;
+        }
+        match &self.0.state().group_by {
+            Some(Ok(group_by)) => {
+                GroupByTable::new(group_by, self.0.state().group_by_selected).render(area, buf);
+                return;
+            }
+            Some(Err(msg)) => {
+                let block = Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(theme().bad))
+                    .title(" Group by failed (Esc close) ");
+                let inner = block.inner(area);
+                block.render(area, buf);
+                Paragraph::new(Line::from(Span::styled(
+                    msg.as_str(),
+                    Style::default().fg(theme().bad),
+                )))
+                .render(inner, buf);
+                return;
+            }
+            None => {}
+        }
+        // The column chooser takes the right of the view; the table previews the layout
+        let area = match &self.0.state().column_chooser {
+            Some(chooser) => {
+                let [table_area, chooser_area] = Layout::horizontal([
+                    Constraint::Fill(1),
+                    Constraint::Length(COLUMN_CHOOSER_WIDTH),
+                ])
+                .areas(area);
+                self.render_column_chooser(chooser, chooser_area, buf);
+                table_area
+            }
+            None => area,
+        };
         let data = self
             .0
             .state()
-            .filtered_sample_data
-            .as_ref()
-            .unwrap_or(&self.0.parquet_ctx.sample_data);
+            .visualize_data(&self.0.parquet_ctx.sample_data);
+        let (columns, pinned) = self.0.state().column_layout.visible();
         let mut table = DataTable::new(data)
-            .with_horizontal_scroll(self.0.state().horizontal_offset())
+            .with_column_order(&columns, pinned)
+            .with_horizontal_scroll(self.0.state().data_horizontal_scroll())
             .with_vertical_scroll(self.0.state().data_vertical_scroll())
-            .with_selected_row(Some(self.0.state().vertical_offset()));
-        if self.0.state().search_filter.is_some() {
-            table = table.with_title(format!("Data (filtered: {} rows)", data.total_rows));
+            .with_selected_row(Some(self.0.state().vertical_offset()))
+            .with_sort(self.0.state().sort)
+            .with_current_column(Some(self.0.state().horizontal_offset()))
+            .with_find(self.0.state().find.as_ref())
+            .with_column_widths(&self.0.state().visualize_column_widths)
+            .with_wrapped_row(self.0.state().wrap_row)
+            .with_selection(self.0.state().selection());
+        if let Some(view) = &self.0.state().view_job {
+            table = table.with_title(format!(
+                "Data (reading the whole file {}, Esc to cancel)",
+                spinner(view.elapsed())
+            ));
+        } else if self.0.state().search_filter.is_some() {
+            let matches = self.0.state().search_match_count.unwrap_or(0);
+            let title = if matches > data.total_rows {
+                format!(
+                    "Data (filtered: {} matches, showing first {})",
+                    commas(matches as u64),
+                    data.total_rows
+                )
+            } else {
+                format!("Data (filtered: {} matches)", commas(matches as u64))
+            };
+            table = table.with_title(title);
         }
+        self.0.hits().borrow_mut().table = Some(table.layout(area));
         table.render(area, buf)
     }
 
     fn render_sql_view(&self, area: Rect, buf: &mut Buffer) {
+        const PROMPT_WIDTH: usize = 5;
+        let state = self.0.state();
+        let editor = &state.sql_editor;
+        let lines = editor.lines();
+
+        // The editor grows with the query, up to half of the tab
+        let max_input_height = (area.height / 2).max(3);
+        let input_height = (lines.len() as u16 + 2).clamp(3, max_input_height);
         let [input_area, results_area] =
-            Layout::vertical([Constraint::Length(3), Constraint::Fill(1)]).areas(area);
+            Layout::vertical([Constraint::Length(input_height), Constraint::Fill(1)]).areas(area);
 
+        let keys = self.0.keymap();
+        let (border_color, title) = if state.sql_editing {
+            let cancel = keys.hint(Action::Cancel);
+            (theme().title, format!(" SQL ({cancel}: results) "))
+        } else {
+            let edit = keys.hint(Action::EditQuery);
+            (theme().muted, format!(" SQL ({edit}: edit query) "))
+        };
         let block = Block::default()
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(Color::Cyan))
-            .title(" SQL ");
+            .border_style(Style::default().fg(border_color))
+            .title(title);
         let inner_input = block.inner(input_area);
         block.render(input_area, buf);
 
-        let query_line = format!("SQL> {}", self.0.state().sql_query);
-        Paragraph::new(Line::from(Span::raw(query_line))).render(inner_input, buf);
+        let text: Vec<Line> = lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let prompt = if i == 0 { "SQL> " } else { "   > " };
+                Line::from(vec![prompt.fg(theme().muted), Span::raw(*line)])
+            })
+            .collect();
+
+        // Scroll so that the cursor stays inside the input box
+        let (cursor_line, cursor_column) = editor.cursor_position();
+        let visible_lines = inner_input.height.max(1) as usize;
+        let vertical_scroll = (cursor_line + 1).saturating_sub(visible_lines);
+        let horizontal_scroll =
+            (PROMPT_WIDTH + cursor_column + 1).saturating_sub(inner_input.width as usize);
+        Paragraph::new(Text::from(text))
+            .scroll((vertical_scroll as u16, horizontal_scroll as u16))
+            .render(inner_input, buf);
+
+        if state.sql_editing {
+            let cursor_x =
+                inner_input.x + (PROMPT_WIDTH + cursor_column - horizontal_scroll) as u16;
+            let cursor_y = inner_input.y + (cursor_line - vertical_scroll) as u16;
+            if cursor_x < inner_input.x + inner_input.width
+                && let Some(cell) = buf.cell_mut(Position::new(cursor_x, cursor_y))
+            {
+                cell.set_style(theme().text_cursor());
+            }
+        }
+
+        if let Some(plan) = &state.sql_plan {
+            self.render_sql_plan(plan, results_area, buf);
+            return;
+        }
+        if let Some(library) = &state.query_library {
+            self.render_query_library(library, results_area, buf);
+            return;
+        }
+        if let Some(spec) = &state.sql_chart {
+            self.render_sql_chart(spec, results_area, buf);
+            return;
+        }
 
-        // Cursor at end of SQL input
-        let cursor_x = inner_input.x + 5 + (self.0.state().sql_query.chars().count() as u16);
-        let cursor_y = inner_input.y;
-        if cursor_x < inner_input.x + inner_input.width
-            && let Some(cell) = buf.cell_mut(Position::new(cursor_x, cursor_y))
-        {
-            cell.set_symbol(" ");
-            cell.set_style(Style::default().bg(Color::Cyan).fg(Color::Black));
+        let running = state
+            .sql_job
+            .as_ref()
+            .map(|job| (job.offset, spinner(job.elapsed())));
+        if let Some((0, status)) = &running {
+            render_running(
+                status,
+                "Running query... (Esc to cancel)",
+                results_area,
+                buf,
+            );
+            return;
         }
 
-        match &self.0.state().sql_result {
+        match &state.sql_result {
             Some(SqlResult::Ok(data)) => {
-                DataTable::new(data)
-                    .with_title("Query result".to_string())
-                    .with_horizontal_scroll(self.0.state().horizontal_offset())
-                    .with_vertical_scroll(self.0.state().data_vertical_scroll())
-                    .with_selected_row(Some(self.0.state().vertical_offset()))
-                    .render(results_area, buf);
+                let mut title = format!("Query result: {} rows", commas(data.total_rows as u64));
+                if let Some((_, status)) = &running {
+                    title.push_str(&format!(", fetching more {status} (Esc to cancel)"));
+                } else {
+                    if state.sql_has_more {
+                        title.push_str(", more available (m: fetch more)");
+                    }
+                    if let Some(elapsed) = state.sql_elapsed {
+                        title.push_str(&format!(" in {:.2}s", elapsed.as_secs_f64()));
+                    }
+                }
+                let table_area = match &state.sql_summary {
+                    Some(summary) => {
+                        let [table_area, summary_area] = Layout::vertical([
+                            Constraint::Fill(1),
+                            Constraint::Length(ColumnSummaryStrip::height()),
+                        ])
+                        .areas(results_area);
+                        self.render_sql_summary(summary, data.total_rows, summary_area, buf);
+                        table_area
+                    }
+                    None => results_area,
+                };
+                let table = DataTable::new(data)
+                    .with_title(title)
+                    .with_horizontal_scroll(state.data_horizontal_scroll())
+                    .with_vertical_scroll(state.data_vertical_scroll())
+                    .with_selected_row(Some(state.vertical_offset()))
+                    .with_current_column(Some(state.horizontal_offset()))
+                    .with_column_widths(&state.sql_column_widths)
+                    .with_wrapped_row(state.wrap_row)
+                    .with_selection(state.selection());
+                self.0.hits().borrow_mut().table = Some(table.layout(table_area));
+                table.render(table_area, buf);
             }
             Some(SqlResult::Err(msg)) => {
                 let block = Block::default()
                     .borders(Borders::ALL)
                     .border_type(BorderType::Rounded)
-                    .border_style(Style::default().fg(Color::Red))
+                    .border_style(Style::default().fg(theme().bad))
                     .title(" Error ");
                 let inner = block.inner(results_area);
                 block.render(results_area, buf);
                 Paragraph::new(Line::from(Span::styled(
                     msg.as_str(),
-                    Style::default().fg(Color::Red),
+                    Style::default().fg(theme().bad),
                 )))
                 .render(inner, buf);
             }
@@ -340,90 +717,655 @@ impl<'a> AppWidget<'a> {
                 let block = Block::default()
                     .borders(Borders::ALL)
                     .border_type(BorderType::Rounded)
-                    .border_style(Style::default().fg(Color::DarkGray));
+                    .border_style(Style::default().fg(theme().border));
                 let inner = block.inner(results_area);
                 block.render(results_area, buf);
                 Paragraph::new(Line::from(Span::raw(
-                    "Enter SQL and press Enter to run. Table name: parquet",
+                    "Press Enter to write a query and Enter again to run it, Alt+Enter for a new line, Ctrl+Space to complete. Table name: parquet",
                 )))
                 .render(inner, buf);
             }
         }
     }
 
-    fn render_row_detail_view(&self, area: Rect, buf: &mut Buffer) {
-        let state = self.0.state();
-        let row_idx = match state.row_detail_row {
-            Some(i) => i,
-            None => return,
+    // Optimized and logical plan with the parquet scan and what was pushed into it highlighted
+    fn render_sql_plan(&self, plan: &Result<QueryPlan, String>, area: Rect, buf: &mut Buffer) {
+        let plan = match plan {
+            Ok(plan) => plan,
+            Err(msg) => {
+                let block = Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(theme().bad))
+                    .title(" Explain failed (Esc close) ");
+                let inner = block.inner(area);
+                block.render(area, buf);
+                Paragraph::new(Line::from(Span::styled(
+                    msg.as_str(),
+                    Style::default().fg(theme().bad),
+                )))
+                .render(inner, buf);
+                return;
+            }
         };
-        let (lines, title) = match self.0.tabs().active_tab().to_string().as_str() {
-            "Visualize" => {
-                let data = state
-                    .filtered_sample_data
-                    .as_ref()
-                    .unwrap_or(&self.0.parquet_ctx.sample_data);
-                match data.rows.get(row_idx) {
-                    Some(row) => (
-                        Self::row_detail_lines(&data.flattened_columns, row),
-                        format!("Row {} (Visualize)", row_idx + 1),
-                    ),
-                    None => (
-                        vec![Line::from(Span::raw("Row out of range"))],
-                        "Row detail".to_string(),
-                    ),
-                }
+
+        let plan_lines = |text: &str| -> Vec<Line<'static>> {
+            text.lines()
+                .map(|line| {
+                    let style = match QueryPlan::classify(line) {
+                        PlanLine::Scan => Style::default().fg(theme().title).bold(),
+                        PlanLine::Pushdown => Style::default().fg(theme().good),
+                        PlanLine::NotPushed => Style::default().fg(theme().header),
+                        PlanLine::Plain => Style::default(),
+                    };
+                    Line::from(Span::styled(line.to_string(), style))
+                })
+                .collect()
+        };
+        let mut lines = vec![
+            Line::from(plan.pushdown_summary().bold()),
+            Line::from(""),
+            Line::from("Optimized plan".underlined()),
+        ];
+        lines.extend(plan_lines(&plan.optimized));
+        lines.extend(vec![
+            Line::from(""),
+            Line::from("Logical plan".underlined()),
+        ]);
+        lines.extend(plan_lines(&plan.logical));
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme().title))
+            .title(" Query plan (Esc close, ↑↓ PgUp PgDn scroll) ");
+        let inner = block.inner(area);
+        block.render(area, buf);
+        let max_scroll = lines.len().saturating_sub(inner.height as usize);
+        let scroll = self.0.state().sql_plan_scroll.min(max_scroll) as u16;
+        Paragraph::new(Text::from(lines))
+            .scroll((scroll, 0))
+            .render(inner, buf);
+    }
+
+    // Result columns with the X and Y picks on the left, the chart on the right
+    fn render_sql_chart(&self, spec: &ChartSpec, area: Rect, buf: &mut Buffer) {
+        let state = self.0.state();
+        let [list_area, chart_area] =
+            Layout::horizontal([Constraint::Length(CHART_COLUMNS_WIDTH), Constraint::Fill(1)])
+                .areas(area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme().title))
+            .title(" Columns ")
+            .title_bottom(" x: X, space: Y ".fg(theme().muted));
+        let inner = block.inner(list_area);
+        block.render(list_area, buf);
+        if let Some(frame) = &state.sql_frame {
+            let cursor = state.sql_chart_cursor;
+            let visible = inner.height.max(1) as usize;
+            let scroll = (cursor + 1).saturating_sub(visible);
+            let lines: Vec<Line> = frame
+                .get_columns()
+                .iter()
+                .enumerate()
+                .skip(scroll)
+                .take(visible)
+                .map(|(i, column)| {
+                    let marker = if i == spec.x {
+                        "X ".bold()
+                    } else if let Some(n) = spec.ys.iter().position(|&y| y == i) {
+                        "Y ".fg(theme().series_color(n)).bold()
+                    } else {
+                        "  ".into()
+                    };
+                    let line = Line::from(vec![
+                        marker,
+                        Span::raw(column.name().to_string()),
+                        format!(" {}", column.dtype()).fg(theme().muted),
+                    ]);
+                    if i == cursor {
+                        line.style(theme().selected())
+                    } else {
+                        line
+                    }
+                })
+                .collect();
+            Paragraph::new(Text::from(lines)).render(inner, buf);
+        }
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme().border))
+            .title(format!(
+                " Chart: {} (t: type, Esc: close) ",
+                spec.kind.describe()
+            ));
+        let inner = block.inner(chart_area);
+        block.render(chart_area, buf);
+        match &state.sql_chart_data {
+            Some(Ok(data)) => ResultChart::new(data, spec.kind).render(inner, buf),
+            Some(Err(msg)) => {
+                Paragraph::new(Line::from(Span::styled(
+                    msg.as_str(),
+                    Style::default().fg(theme().bad),
+                )))
+                .render(inner, buf);
             }
-            "SQL" => {
-                if let Some(SqlResult::Ok(data)) = &state.sql_result {
-                    match data.rows.get(row_idx) {
-                        Some(row) => (
-                            Self::row_detail_lines(&data.flattened_columns, row),
-                            format!("Row {} (SQL result)", row_idx + 1),
-                        ),
-                        None => (
-                            vec![Line::from(Span::raw("Row out of range"))],
-                            "Row detail".to_string(),
-                        ),
+            None => {}
+        }
+    }
+
+    fn render_sql_summary(
+        &self,
+        summary: &Result<Vec<ColumnSummary>, String>,
+        rows: usize,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        match summary {
+            Ok(columns) => ColumnSummaryStrip::new(columns, rows)
+                .with_horizontal_scroll(self.0.state().data_horizontal_scroll())
+                .render(area, buf),
+            Err(msg) => {
+                let block = Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(theme().bad))
+                    .title(" Column summary failed (s: hide) ");
+                let inner = block.inner(area);
+                block.render(area, buf);
+                Paragraph::new(Line::from(Span::styled(
+                    msg.as_str(),
+                    Style::default().fg(theme().bad),
+                )))
+                .render(inner, buf);
+            }
+        }
+    }
+
+    // Every column with a filter prompt above, shown [x] or hidden [ ] and pinned ones marked
+    fn render_column_chooser(&self, chooser: &ColumnChooser, area: Rect, buf: &mut Buffer) {
+        let layout = &self.0.state().column_layout;
+        let names = &self.0.parquet_ctx.sample_data.flattened_columns;
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme().title))
+            .title(format!(
+                " Columns: {} / {} shown ",
+                layout.visible_count(),
+                names.len()
+            ))
+            .title_bottom(" Enter show, Tab pin, Shift+↑↓ move, Esc ");
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let [prompt_area, list_area] =
+            Layout::vertical([Constraint::Length(2), Constraint::Fill(1)]).areas(inner);
+        Paragraph::new(Line::from(vec![
+            "Filter: ".fg(theme().muted),
+            Span::raw(chooser.query.clone()),
+            Span::styled(" ", theme().text_cursor()),
+        ]))
+        .render(prompt_area, buf);
+
+        let matches = chooser.matches(layout, names);
+        let visible = list_area.height.max(1) as usize;
+        let scroll = (chooser.selected + 1).saturating_sub(visible);
+        let lines: Vec<Line> = matches
+            .iter()
+            .enumerate()
+            .skip(scroll)
+            .take(visible)
+            .map(|(i, (column, matched))| {
+                let shown = if layout.is_hidden(*column) {
+                    "[ ] ".fg(theme().muted)
+                } else {
+                    "[x] ".fg(theme().good)
+                };
+                let mut spans = vec![shown];
+                spans.extend(names[*column].chars().enumerate().map(|(at, c)| {
+                    if matched.contains(&at) {
+                        c.to_string().fg(theme().header).bold()
+                    } else {
+                        Span::raw(c.to_string())
                     }
+                }));
+                if layout.is_pinned(*column) {
+                    spans.push(" pinned".fg(theme().accent));
+                }
+                let line = Line::from(spans);
+                if i == chooser.selected {
+                    line.style(theme().selected())
                 } else {
-                    (
-                        vec![Line::from(Span::raw("No result data"))],
-                        "Row detail".to_string(),
-                    )
+                    line
                 }
+            })
+            .collect();
+        Paragraph::new(Text::from(lines)).render(list_area, buf);
+    }
+
+    // Saved queries on the left, the selected query's SQL on the right
+    fn render_query_library(&self, library: &QueryLibrary, area: Rect, buf: &mut Buffer) {
+        let [list_area, preview_area] =
+            Layout::horizontal([Constraint::Percentage(40), Constraint::Fill(1)]).areas(area);
+        let selected = self.0.state().query_library_selected;
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme().title))
+            .title(" Saved queries (Enter run, e edit, d delete, Esc close) ");
+        let inner = block.inner(list_area);
+        block.render(list_area, buf);
+        if library.queries.is_empty() {
+            Paragraph::new(Text::from(vec![
+                Line::from("No saved queries."),
+                Line::from("Ctrl+S in the editor saves the current query."),
+            ]))
+            .render(inner, buf);
+        } else {
+            let visible = inner.height.max(1) as usize;
+            let scroll = (selected + 1).saturating_sub(visible);
+            let lines: Vec<Line> = library
+                .queries
+                .iter()
+                .enumerate()
+                .skip(scroll)
+                .take(visible)
+                .map(|(i, (scope, query))| {
+                    let line = Line::from(vec![
+                        format!("{:<8}", scope.describe()).fg(theme().muted),
+                        Span::raw(query.name.clone()),
+                    ]);
+                    if i == selected {
+                        line.style(theme().selected())
+                    } else {
+                        line
+                    }
+                })
+                .collect();
+            Paragraph::new(Text::from(lines)).render(inner, buf);
+        }
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme().border))
+            .title(" SQL ");
+        let inner = block.inner(preview_area);
+        block.render(preview_area, buf);
+        if let Some((_, query)) = library.queries.get(selected) {
+            let mut lines: Vec<Line> = Vec::new();
+            if let Some(description) = &query.description {
+                lines.push(Line::from(description.clone().italic()));
+                lines.push(Line::from(""));
             }
-            _ => (
-                vec![Line::from(Span::raw("No data"))],
-                "Row detail".to_string(),
-            ),
+            lines.extend(query.sql.lines().map(|l| Line::from(l.to_string())));
+            let parameters = query.parameters();
+            if !parameters.is_empty() {
+                lines.push(Line::from(""));
+                lines.push(Line::from(
+                    format!("Parameters: {}", parameters.join(", ")).fg(theme().key),
+                ));
+            }
+            Paragraph::new(Text::from(lines)).render(inner, buf);
+        }
+    }
+
+    /// Popup over the view with a whole cell value, wrapped to the popup width.
+    fn render_expanded_cell(&self, column: &str, value: &str, area: Rect, buf: &mut Buffer) {
+        let [_, popup, _] = Layout::horizontal([
+            Constraint::Percentage(10),
+            Constraint::Percentage(80),
+            Constraint::Percentage(10),
+        ])
+        .areas(area);
+        let [_, popup, _] = Layout::vertical([
+            Constraint::Percentage(10),
+            Constraint::Percentage(80),
+            Constraint::Percentage(10),
+        ])
+        .areas(popup);
+        Clear.render(popup, buf);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme().title))
+            .title(format!(" {column} "))
+            .title_bottom(" ↑↓ scroll, Esc close ");
+        let inner = block.inner(popup);
+        block.render(popup, buf);
+
+        let lines: Vec<Line> = wrap_text(value, inner.width as usize)
+            .into_iter()
+            .skip(self.0.state().expanded_cell_scroll)
+            .map(Line::from)
+            .collect();
+        Paragraph::new(Text::from(lines)).render(inner, buf);
+    }
+
+    /// Every action with its keys, by tab, in as many columns as fit.
+    fn render_help(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme().title))
+            .title(" Key bindings ")
+            .title_bottom(" ↑↓ scroll, Esc close ");
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let sections = self.0.keymap().help();
+        let key_width = sections
+            .iter()
+            .flat_map(|(_, entries)| entries.iter().map(|(keys, _)| keys.chars().count()))
+            .max()
+            .unwrap_or(0);
+        let mut lines: Vec<Line> = Vec::new();
+        for (tab, entries) in sections {
+            if !lines.is_empty() {
+                lines.push(Line::from(""));
+            }
+            lines.push(Line::from(tab.fg(theme().header).bold()));
+            lines.extend(entries.into_iter().map(|(keys, description)| {
+                Line::from(vec![
+                    format!("  {keys:<key_width$}  ").fg(theme().key),
+                    description.fg(theme().text),
+                ])
+            }));
+        }
+
+        // Sections flow into columns when the lines do not fit the height
+        let height = inner.height.max(1) as usize;
+        let column_width = (key_width + 40) as u16;
+        let columns = (inner.width / column_width).max(1) as usize;
+        let rows = lines.len().div_ceil(columns).max(height);
+        let max_scroll = rows.saturating_sub(height);
+        let scroll = self.0.state().help_scroll.min(max_scroll);
+        let areas =
+            Layout::horizontal(vec![Constraint::Length(column_width); columns]).split(inner);
+        for (column, chunk) in lines.chunks(rows).enumerate().take(columns) {
+            Paragraph::new(Text::from(chunk.to_vec()))
+                .scroll((scroll as u16, 0))
+                .render(areas[column], buf);
+        }
+    }
+
+    /// A popup of `height` lines near the top of `area`, with a border and titles.
+    fn popup(area: Rect, height: u16, title: String, keys: &str, buf: &mut Buffer) -> Rect {
+        let width = PALETTE_WIDTH.min(area.width);
+        let height = height.min(area.height);
+        let popup = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + 1.min(area.height - height),
+            width,
+            height,
+        );
+        Clear.render(popup, buf);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme().title))
+            .title(title)
+            .title_bottom(format!(" {keys} "));
+        let inner = block.inner(popup);
+        block.render(popup, buf);
+        inner
+    }
+
+    fn render_file_list(&self, selected: usize, area: Rect, buf: &mut Buffer) {
+        let files = self.0.files();
+        let inner = Self::popup(
+            area,
+            files.len() as u16 + 2,
+            " Open files ".to_string(),
+            "Enter switch, o open file, d close, Esc",
+            buf,
+        );
+        let lines: Vec<Line> = files
+            .iter()
+            .enumerate()
+            .map(|(i, path)| {
+                let marker = if i == self.0.active_file() {
+                    "● "
+                } else {
+                    "  "
+                };
+                let line = Line::from(vec![marker.fg(theme().good), Span::raw(path.to_string())]);
+                if i == selected {
+                    line.style(theme().selected())
+                } else {
+                    line
+                }
+            })
+            .collect();
+        let scroll = (selected + 1).saturating_sub(inner.height.max(1) as usize);
+        Paragraph::new(Text::from(lines))
+            .scroll((scroll as u16, 0))
+            .render(inner, buf);
+    }
+
+    fn render_file_browser(&self, browser: &FileBrowser, area: Rect, buf: &mut Buffer) {
+        let inner = Self::popup(
+            area,
+            20,
+            format!(" Open: {} ", browser.dir.display()),
+            "Enter open, Backspace up, Esc",
+            buf,
+        );
+        let [prompt_area, list_area] =
+            Layout::vertical([Constraint::Length(2), Constraint::Fill(1)]).areas(inner);
+        Paragraph::new(Line::from(vec![
+            "Filter: ".fg(theme().muted),
+            Span::raw(browser.query.clone()),
+            Span::styled(" ", theme().text_cursor()),
+        ]))
+        .render(prompt_area, buf);
+
+        let matches = browser.matches();
+        if matches.is_empty() {
+            Line::from("No Parquet files here".fg(theme().muted)).render(list_area, buf);
+            return;
+        }
+        let visible = list_area.height.max(1) as usize;
+        let scroll = (browser.selected + 1).saturating_sub(visible);
+        let lines: Vec<Line> = matches
+            .iter()
+            .enumerate()
+            .skip(scroll)
+            .take(visible)
+            .map(|(i, (entry, matched))| {
+                let entry = &browser.entries[*entry];
+                let color = if entry.is_dir {
+                    theme().key
+                } else {
+                    theme().text
+                };
+                let mut spans: Vec<Span> = entry
+                    .name
+                    .chars()
+                    .enumerate()
+                    .map(|(at, c)| {
+                        if matched.contains(&at) {
+                            c.to_string().fg(theme().header).bold()
+                        } else {
+                            c.to_string().fg(color)
+                        }
+                    })
+                    .collect();
+                if entry.is_dir {
+                    spans.push("/".fg(color));
+                }
+                let line = Line::from(spans);
+                if i == browser.selected {
+                    line.style(theme().selected())
+                } else {
+                    line
+                }
+            })
+            .collect();
+        Paragraph::new(Text::from(lines)).render(list_area, buf);
+    }
+
+    fn render_palette(&self, palette: &Palette, area: Rect, buf: &mut Buffer) {
+        let inner = Self::popup(
+            area,
+            (palette.items.len() as u16 + 4).min(20),
+            " Commands ".to_string(),
+            "Enter run, Esc close",
+            buf,
+        );
+
+        let [prompt_area, list_area] =
+            Layout::vertical([Constraint::Length(2), Constraint::Fill(1)]).areas(inner);
+        Paragraph::new(Line::from(vec![
+            ": ".fg(theme().muted),
+            Span::raw(palette.query.clone()),
+            Span::styled(" ", theme().text_cursor()),
+        ]))
+        .render(prompt_area, buf);
+
+        let visible = list_area.height.max(1) as usize;
+        let scroll = (palette.selected + 1).saturating_sub(visible);
+        let lines: Vec<Line> = palette
+            .matches()
+            .iter()
+            .enumerate()
+            .skip(scroll)
+            .take(visible)
+            .map(|(i, (item, matched))| {
+                let item = &palette.items[*item];
+                let mut spans: Vec<Span> = item
+                    .label
+                    .chars()
+                    .enumerate()
+                    .map(|(at, c)| {
+                        if matched.contains(&at) {
+                            c.to_string().fg(theme().header).bold()
+                        } else {
+                            Span::raw(c.to_string())
+                        }
+                    })
+                    .collect();
+                // Keys aligned on the right
+                let gap = (list_area.width as usize)
+                    .saturating_sub(item.label.chars().count() + item.key.chars().count());
+                spans.push(Span::raw(" ".repeat(gap)));
+                spans.push(item.key.clone().fg(theme().muted));
+                let line = Line::from(spans);
+                if i == palette.selected {
+                    line.style(theme().selected())
+                } else {
+                    line
+                }
+            })
+            .collect();
+        Paragraph::new(Text::from(lines)).render(list_area, buf);
+    }
+
+    fn render_row_detail_view(&self, area: Rect, buf: &mut Buffer) {
+        let state = self.0.state();
+        let row_idx = match state.row_detail_row {
+            Some(i) => i,
+            None => return,
+        };
+        let source = match self.0.tabs().active_tab().to_string().as_str() {
+            "SQL" => "SQL result",
+            _ => "Visualize",
+        };
+        let keys = if state.detail_raw {
+            "r tree, y copy, ↑↓←→ scroll, [ ] row, Esc close"
+        } else {
+            "→← expand/collapse, +/- all, / find, r JSON, y copy, [ ] row, Esc close"
         };
         let block = Block::default()
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(Color::Yellow))
-            .title(format!(
-                " {} (Esc close, ↑↓ PgUp PgDn scroll, ←→ horizontal) ",
-                title
-            ));
+            .border_style(Style::default().fg(theme().header))
+            .title(format!(" Row {} ({source}) ", row_idx + 1))
+            .title_bottom(Line::from(format!(" {keys} ")).centered());
         let inner = block.inner(area);
         block.render(area, buf);
+
+        let lines = match &state.row_detail {
+            None => vec![Line::from("Reading row...")],
+            Some(Err(msg)) => vec![Line::from(msg.clone().fg(theme().bad))],
+            Some(Ok(tree)) if state.detail_raw => serde_json::to_string_pretty(&tree.to_json())
+                .unwrap_or_default()
+                .lines()
+                .map(|line| Line::from(line.to_string()))
+                .collect(),
+            Some(Ok(tree)) => Self::record_lines(tree, state.detail_cursor, &state.detail_query),
+        };
         // Paragraph scroll is (vertical, horizontal)
-        let max_vertical = lines.len().saturating_sub(inner.height as usize).max(0);
+        let max_vertical = lines.len().saturating_sub(inner.height as usize);
         let vertical_scroll = state.detail_scroll_offset.min(max_vertical) as u16;
         let max_line_width = lines.iter().map(|l| l.width()).max().unwrap_or(0);
-        let max_horizontal = max_line_width.saturating_sub(inner.width as usize).max(0);
-        let horizontal_scroll = state.detail_scroll_horizontal.min(max_horizontal) as u16;
+        let max_horizontal = max_line_width.saturating_sub(inner.width as usize);
+        let horizontal_scroll = if state.detail_raw {
+            state.detail_scroll_horizontal.min(max_horizontal) as u16
+        } else {
+            0
+        };
         Paragraph::new(Text::from(lines))
             .scroll((vertical_scroll, horizontal_scroll))
             .render(inner, buf);
     }
 
-    fn row_detail_lines(columns: &[String], row: &[String]) -> Vec<Line<'static>> {
-        columns
-            .iter()
-            .zip(row.iter())
-            .map(|(c, v)| Line::from(Span::raw(format!("{}: {}", c, v))))
+    /// Visible nodes of a record, indented under their parents, with their types and the
+    /// find query highlighted.
+    fn record_lines(tree: &RecordTree, cursor: usize, query: &str) -> Vec<Line<'static>> {
+        let find = FindMatches {
+            query: query.to_string(),
+            ..Default::default()
+        };
+        tree.visible()
+            .into_iter()
+            .map(|idx| {
+                let node = &tree.nodes[idx];
+                let is_cursor = idx == cursor;
+                let marker = match (node.is_group(), tree.is_collapsed(idx)) {
+                    (false, _) => "  ",
+                    (true, false) => "▾ ",
+                    (true, true) => "▸ ",
+                };
+                let mut spans = vec![Span::raw(format!("{}{marker}", "  ".repeat(node.depth)))];
+                spans.extend(
+                    highlight_matches(
+                        &find,
+                        &node.name,
+                        Style::default().fg(theme().key),
+                        is_cursor,
+                    )
+                    .spans,
+                );
+                if let Some(value) = &node.value {
+                    spans.push(Span::raw(": "));
+                    let style = if node.null {
+                        Style::default().fg(theme().muted).italic()
+                    } else {
+                        Style::default().fg(theme().text)
+                    };
+                    spans.extend(highlight_matches(&find, value, style, is_cursor).spans);
+                }
+                spans.push(Span::styled(
+                    format!("  {}", node.dtype),
+                    Style::default().fg(theme().muted),
+                ));
+                let line = Line::from(spans);
+                if is_cursor {
+                    line.style(theme().cursor())
+                } else {
+                    line
+                }
+            })
             .collect()
     }
 }
@@ -438,6 +1380,7 @@ impl<'a> Widget for AppWidget<'a> {
             Constraint::Length(1),
         ]);
         let [header_area, inner_area, footer_area] = vertical.areas(area);
+        *app.hits().borrow_mut() = HitAreas::default();
 
         self.render_tabs_view(header_area, buf);
         self.render_footer_view(footer_area, buf);
@@ -449,10 +1392,49 @@ impl<'a> Widget for AppWidget<'a> {
                 "Metadata" => self.render_metadata_view(inner_area, buf),
                 "Schema" => self.render_schema_view(inner_area, buf),
                 "Row Groups" => self.render_row_groups_view(inner_area, buf),
+                "Storage" => self.render_storage_view(inner_area, buf),
                 "Visualize" => self.render_visualize_view(inner_area, buf),
                 "SQL" => self.render_sql_view(inner_area, buf),
                 _ => {}
             }
         }
+
+        if let Some((column, value)) = &app.state().expanded_cell {
+            self.render_expanded_cell(column, value, inner_area, buf);
+        }
+        if let Some(selected) = app.state().file_list {
+            self.render_file_list(selected, inner_area, buf);
+        }
+        if let Some(browser) = &app.state().file_browser {
+            self.render_file_browser(browser, inner_area, buf);
+        }
+        if let Some(palette) = &app.state().palette {
+            self.render_palette(palette, inner_area, buf);
+        }
+        if app.state().help_shown {
+            self.render_help(inner_area, buf);
+        }
     }
 }
+
+/// The spinner frame and the time spent so far on background work.
+fn spinner(elapsed: Duration) -> String {
+    let frame = (elapsed.as_millis() / 100) as usize % SPINNER.len();
+    format!("{} {:.1}s", SPINNER[frame], elapsed.as_secs_f64())
+}
+
+/// Placeholder for a pane whose content is computed in the background.
+fn render_running(status: &str, message: &str, area: Rect, buf: &mut Buffer) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme().title))
+        .title(" Running ");
+    let inner = block.inner(area);
+    block.render(area, buf);
+    Paragraph::new(Line::from(vec![
+        status.to_string().fg(theme().title),
+        format!("  {message}").into(),
+    ]))
+    .render(inner, buf);
+}